@@ -0,0 +1,45 @@
+use automata::{dfa::Transition, table::Table};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Number of states in the benchmarked DFA.
+const STATES: usize = 500;
+/// Number of outgoing transitions from each state.
+const TRANSITIONS: usize = 8;
+
+/// Transitions of a DFA in which each state moves to the next few states on distinct labels.
+fn entries() -> Vec<(usize, Transition<usize>, usize)> {
+    (0..STATES)
+        .flat_map(|src| (0..TRANSITIONS).map(move |i| (src, Transition(i), (src + i + 1) % STATES)))
+        .collect()
+}
+
+fn bench_table(c: &mut Criterion) {
+    let entries = entries();
+
+    c.bench_function("table_new_set", |b| {
+        b.iter(|| {
+            let mut table = Table::new();
+            for (src, transition, dest) in entries.iter().cloned() {
+                table.set(src, transition, dest);
+            }
+            black_box(table)
+        })
+    });
+
+    c.bench_function("table_with_capacity_set", |b| {
+        b.iter(|| {
+            let mut table = Table::with_capacity(STATES);
+            for (src, transition, dest) in entries.iter().cloned() {
+                table.set(src, transition, dest);
+            }
+            black_box(table)
+        })
+    });
+
+    c.bench_function("table_from_entries", |b| {
+        b.iter(|| black_box(Table::from_entries(entries.iter().cloned())))
+    });
+}
+
+criterion_group!(benches, bench_table);
+criterion_main!(benches);
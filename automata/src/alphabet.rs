@@ -0,0 +1,131 @@
+use crate::dfa::Disjoin;
+use crate::nfa::{self, NFA};
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Must be implemented by transition symbol types to partition an alphabet, by joining the
+/// symbols that belong to the same equivalence class.
+pub trait Union: Sized {
+    /// Given a set of transition symbols, return a symbol that covers exactly the input covered
+    /// by any of them.
+    fn union(vec: Vec<&Self>) -> Self;
+}
+
+/// A partition of the input into equivalence classes with respect to a set of transition symbols:
+/// two inputs are in the same class if every one of the symbols either covers both or neither.
+/// An automaton therefore never distinguishes between the inputs of a class, and its transitions
+/// may be taken on class numbers rather than on the symbols themselves.
+///
+/// Classes are numbered from 0 in the order of the pieces returned by [`Disjoin::disjoin`], so
+/// for character ranges, in the order of their lowest characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet<T> {
+    classes: Vec<T>,
+}
+
+impl<T> Alphabet<T>
+where
+    T: Disjoin + Union,
+{
+    /// Partition the input covered by the symbols into equivalence classes.
+    #[inline]
+    pub fn new(symbols: Vec<&T>) -> Self {
+        let pieces = T::disjoin(symbols.clone());
+
+        // Group the pieces by the symbols that cover them.
+        let mut groups: Vec<Vec<T>> = Vec::new();
+        let mut group_of: HashMap<Vec<usize>, usize> = HashMap::new();
+        for piece in pieces {
+            let covering: Vec<usize> = symbols
+                .iter()
+                .enumerate()
+                .filter(|(_, t)| t.contains(&piece))
+                .map(|(i, _)| i)
+                .collect();
+            let next = groups.len();
+            let group = *group_of.entry(covering).or_insert(next);
+            if group == next {
+                groups.push(Vec::new());
+            }
+            groups[group].push(piece);
+        }
+
+        let classes = groups
+            .iter()
+            .map(|pieces| T::union(pieces.iter().collect()))
+            .collect();
+        Self { classes }
+    }
+
+    /// Partition the input covered by the transitions of an NFA into equivalence classes.
+    #[inline]
+    pub fn of_nfa(nfa: &NFA<T>) -> Self
+    where
+        T: Clone + Eq + Hash,
+    {
+        let symbols = (&nfa.transition)
+            .into_iter()
+            .filter_map(|(_, t, _)| match t {
+                nfa::Transition::Some(t) => Some(t),
+                nfa::Transition::Epsilon => None,
+            })
+            .collect();
+        Self::new(symbols)
+    }
+
+    /// Return the numbers of the classes that make up a symbol. The symbol must be one of those
+    /// the alphabet was partitioned by, or a union of its classes.
+    #[inline]
+    pub fn classes_of(&self, symbol: &T) -> Vec<usize> {
+        self.classes
+            .iter()
+            .enumerate()
+            .filter(|(_, class)| symbol.contains(class))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Return the symbol that covers exactly the input of the given classes.
+    ///
+    /// Panics if there is no such class.
+    #[inline]
+    pub fn symbol(&self, classes: &[usize]) -> T {
+        T::union(classes.iter().map(|&i| &self.classes[i]).collect())
+    }
+}
+
+impl<T> Alphabet<T> {
+    /// Return the number of the class that contains an input symbol, or [`None`] if none of the
+    /// symbols the alphabet was partitioned by cover it.
+    #[inline]
+    pub fn class_of<S>(&self, input: &S) -> Option<usize>
+    where
+        T: PartialEq<S>,
+    {
+        self.classes.iter().position(|class| *class == *input)
+    }
+
+    /// Return the class with the given number.
+    #[inline]
+    pub fn get(&self, class: usize) -> Option<&T> {
+        self.classes.get(class)
+    }
+
+    /// Return the classes, in order of their numbers.
+    #[inline]
+    pub fn classes(&self) -> &[T] {
+        &self.classes
+    }
+
+    /// Return the number of classes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+}
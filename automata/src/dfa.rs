@@ -1,11 +1,14 @@
+use crate::alphabet::{Alphabet, Union};
 use crate::matching::Match;
 use crate::nfa::{self, NFA};
 use crate::table::Table;
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 use std::iter::Peekable;
+use std::ops::Range;
 use std::rc::Rc;
+use std::sync::OnceLock;
 
 /// Must be implemented by NFA transition symbol types to ensure each DFA state has only one
 /// possible transition on any symbol.
@@ -16,7 +19,19 @@ pub trait Disjoin: Sized {
     fn contains(&self, other: &Self) -> bool;
 }
 
+/// Must be implemented by DFA transition symbol types to complete a DFA, by finding the symbols
+/// on which a state has no transition.
+pub trait Complement: Sized {
+    /// Given a set of transition symbols, return a set of symbols that together cover exactly
+    /// the input not covered by any of the given symbols. If the given symbols cover all input,
+    /// the returned set is empty.
+    fn uncovered(vec: Vec<&Self>) -> Vec<Self>;
+}
+
 /// A deterministic finite automaton, or DFA.
+///
+/// Matching only reads the DFA, so it is `Send` and `Sync` whenever `T` is, and may be shared
+/// across threads to match several inputs concurrently.
 #[derive(Debug, Clone)]
 pub struct DFA<T>
 where
@@ -31,17 +46,37 @@ where
     pub final_states: HashSet<usize>,
     /// A lookup table for transitions between states.
     pub transition: Table<usize, Transition<T>, usize>,
+    /// The dead states, computed on first use (see [`DFA::dead_states`]).
+    dead: OnceLock<HashSet<usize>>,
 }
 
+/// A DFA constructed from an NFA by the subset construction, along with the set of NFA states
+/// that each DFA state stands for.
 #[derive(Debug)]
 pub struct DFAFromNFA<T>
 where
     T: Clone + Eq + Hash,
 {
     pub dfa: DFA<T>,
+    /// Mapping from each DFA state to the set of NFA states it represents: the NFA states that
+    /// may be reached on the inputs that lead to the DFA state. A DFA state is final if any of
+    /// its NFA states is final.
     pub nfa_mapping: HashMap<usize, HashSet<usize>>,
 }
 
+impl<T> DFAFromNFA<T>
+where
+    T: Clone + Eq + Hash,
+{
+    /// Return the set of NFA states represented by a DFA state.
+    ///
+    /// Panics if the DFA has no such state.
+    #[inline]
+    pub fn nfa_states_for(&self, dfa_state: usize) -> &HashSet<usize> {
+        &self.nfa_mapping[&dfa_state]
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Transition<T>(pub T)
 where
@@ -59,6 +94,7 @@ where
             total_states: 1,
             final_states: HashSet::new(),
             transition: Table::new(),
+            dead: OnceLock::new(),
         }
     }
 }
@@ -81,6 +117,7 @@ where
     pub fn add_state(&mut self, is_final: bool) -> usize {
         let label = self.total_states;
         self.total_states += 1;
+        self.dead.take();
         if is_final {
             self.final_states.insert(label);
         }
@@ -93,6 +130,7 @@ where
             None
         } else {
             self.transition.set(start, label, end);
+            self.dead.take();
             Some(())
         }
     }
@@ -101,6 +139,228 @@ where
     pub fn is_final_state(&self, state: &usize) -> bool {
         self.final_states.iter().any(|s| s == state)
     }
+
+    /// Return the dead states, from which no final state can be reached. Once a dead state is
+    /// entered, no further input can produce a match.
+    ///
+    /// The dead states are computed once and cached. Adding states or transitions with
+    /// [`DFA::add_state`] and [`DFA::add_transition`] resets the cache, but changing the fields
+    /// of the DFA directly after matching with it does not.
+    #[inline]
+    pub fn dead_states(&self) -> &HashSet<usize> {
+        self.dead.get_or_init(|| self.compute_dead_states())
+    }
+
+    #[inline]
+    fn compute_dead_states(&self) -> HashSet<usize> {
+        // Walk the transitions backwards from the final states to find the live states.
+        let mut predecessors: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (&src, _, &dest) in &self.transition {
+            predecessors.entry(dest).or_default().push(src);
+        }
+
+        let mut live: HashSet<usize> = self.final_states.iter().copied().collect();
+        let mut stack: Vec<usize> = live.iter().copied().collect();
+        while let Some(state) = stack.pop() {
+            for &src in predecessors.get(&state).into_iter().flatten() {
+                if live.insert(src) {
+                    stack.push(src);
+                }
+            }
+        }
+
+        (0..self.total_states)
+            .filter(|state| !live.contains(state))
+            .collect()
+    }
+
+    /// Determine if the state is dead, i.e. no final state can be reached from it.
+    #[inline]
+    pub fn is_dead(&self, state: usize) -> bool {
+        self.dead_states().contains(&state)
+    }
+
+    /// Remove the transitions into dead states, so that matching stops as soon as no match is
+    /// possible, rather than continuing to consume input. The accepted language is unchanged.
+    #[inline]
+    pub fn remove_dead_transitions(&mut self) {
+        let dead = self.dead_states();
+        if dead.is_empty() {
+            return;
+        }
+
+        let entries: Vec<_> = (&self.transition)
+            .into_iter()
+            .filter(|(_, _, dest)| !dead.contains(dest))
+            .map(|(&src, t, &dest)| (src, t.clone(), dest))
+            .collect();
+        self.transition = Table::from_entries(entries);
+        self.dead.take();
+    }
+
+    /// Construct an NFA that accepts the reversal of the language accepted by this DFA. Every
+    /// transition is reversed, a new initial state has epsilon transitions to each of the final
+    /// states, and the initial state becomes the only final state. Each state of the DFA is
+    /// labeled one higher in the NFA.
+    ///
+    /// Reversing and converting back to a DFA twice produces a DFA for the original language that
+    /// is reduced as in Brzozowski's minimization algorithm. Because the new initial state is
+    /// never re-entered, the initial state of the result may be distinct from an otherwise
+    /// equivalent state, so the result has at most one more state than the minimal DFA.
+    #[inline]
+    pub fn reverse(&self) -> NFA<T> {
+        let mut nfa = NFA::new();
+        nfa.total_states += self.total_states;
+
+        for (&src, Transition(t), &dest) in &self.transition {
+            nfa.add_labeled_transition(dest + 1, src + 1, t.clone());
+        }
+
+        for &state in &self.final_states {
+            nfa.add_epsilon_transition(nfa.initial_state, state + 1);
+        }
+        nfa.final_states.insert(self.initial_state + 1);
+
+        nfa
+    }
+
+    /// Construct an equivalent DFA in which equivalent states, those from which exactly the same
+    /// inputs are accepted, are merged into one. Common suffixes, such as the final states of a
+    /// union of keywords, are shared as a result.
+    #[inline]
+    pub fn minimize(&self) -> DFA<T> {
+        self.minimize_by(|_| ()).0
+    }
+
+    /// Construct an equivalent DFA in which equivalent states are merged, as in
+    /// [`DFA::minimize`], but never merge two states for which `key` differs. This allows final
+    /// states to be kept apart, e.g. if they are associated with different actions. Final and
+    /// non-final states are never merged, whatever their keys.
+    ///
+    /// Returns the new DFA and the mapping from each state of this DFA to the new state it was
+    /// merged into.
+    ///
+    /// States are only considered equivalent if they have transitions on the same symbols, so if
+    /// two states partition their symbols differently (e.g. `[a-c]` versus `[a-b]` and `[c]`),
+    /// they are not merged, and the result may not be strictly minimal.
+    #[inline]
+    pub fn minimize_by<K, F>(&self, key: F) -> (DFA<T>, Vec<usize>)
+    where
+        K: Eq + Hash,
+        F: Fn(usize) -> K,
+    {
+        // Partition the states by whether they are final, and then by key.
+        let mut keys = HashMap::new();
+        let mut block: Vec<usize> = (0..self.total_states)
+            .map(|state| {
+                let next = keys.len();
+                *keys
+                    .entry((self.is_final_state(&state), key(state)))
+                    .or_insert(next)
+            })
+            .collect();
+        let mut count = keys.len();
+
+        // Split blocks until the states in each block have transitions on the same symbols to
+        // the same blocks.
+        loop {
+            let mut representatives: Vec<usize> = Vec::new();
+            let new_block: Vec<usize> = (0..self.total_states)
+                .map(|state| {
+                    let existing = representatives.iter().position(|&rep| {
+                        block[rep] == block[state] && self.same_successors(rep, state, &block)
+                    });
+                    existing.unwrap_or_else(|| {
+                        representatives.push(state);
+                        representatives.len() - 1
+                    })
+                })
+                .collect();
+
+            block = new_block;
+            if representatives.len() == count {
+                break;
+            }
+            count = representatives.len();
+        }
+
+        let mut dfa = DFA::new();
+        dfa.initial_state = block[self.initial_state];
+        dfa.total_states = count;
+        dfa.final_states = self.final_states.iter().map(|&s| block[s]).collect();
+        for (&src, t, &dest) in &self.transition {
+            dfa.add_transition(block[src], block[dest], t.clone());
+        }
+
+        (dfa, block)
+    }
+
+    /// Determine if two states have transitions on the same symbols to states in the same blocks.
+    #[inline]
+    fn same_successors(&self, a: usize, b: usize, block: &[usize]) -> bool {
+        let a_row = self.transition.get_row(&a);
+        let b_row = self.transition.get_row(&b);
+        a_row.len() == b_row.len()
+            && a_row.iter().all(|(t, &&a_dest)| match b_row.get(t) {
+                Some(&&b_dest) => block[a_dest] == block[b_dest],
+                None => false,
+            })
+    }
+}
+
+impl<T> DFA<T>
+where
+    T: Clone + Eq + Hash + Complement,
+{
+    /// Construct a DFA that accepts exactly the inputs this DFA rejects.
+    ///
+    /// The DFA is first completed by adding a trap state, which is entered on every symbol on
+    /// which a state has no transition and is never left. The final and non-final states are then
+    /// swapped, so the trap state is final in the result.
+    #[inline]
+    pub fn complement(&self) -> DFA<T> {
+        let mut dfa = self.clone();
+        let trap = dfa.add_state(false);
+
+        for state in 0..dfa.total_states {
+            let row = self.transition.get_row(&state);
+            let covered = row.keys().map(|Transition(t)| t).collect();
+            for t in T::uncovered(covered) {
+                dfa.add_transition(state, trap, Transition(t));
+            }
+        }
+
+        dfa.final_states = (0..dfa.total_states)
+            .filter(|state| !self.final_states.contains(state))
+            .collect();
+        dfa
+    }
+}
+
+impl DFA<usize> {
+    /// Construct an equivalent DFA with transitions on symbols rather than on the numbers of the
+    /// classes of an alphabet. The classes on which a state moves to the same state are joined
+    /// into one symbol, so each pair of states has at most one transition between them.
+    #[inline]
+    pub fn expand<T>(&self, alphabet: &Alphabet<T>) -> DFA<T>
+    where
+        T: Clone + Disjoin + Union + Eq + Hash,
+    {
+        let mut classes: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+        for (&src, &Transition(class), &dest) in &self.transition {
+            classes.entry((src, dest)).or_default().push(class);
+        }
+
+        let mut dfa = DFA::new();
+        dfa.initial_state = self.initial_state;
+        dfa.total_states = self.total_states;
+        dfa.final_states = self.final_states.clone();
+        dfa.transition = Table::from_entries(classes.into_iter().map(|((src, dest), mut ids)| {
+            ids.sort_unstable();
+            (src, Transition(alphabet.symbol(&ids)), dest)
+        }));
+        dfa
+    }
 }
 
 struct MatchRc<T> {
@@ -130,17 +390,69 @@ where
         let mut state = self.initial_state;
 
         for is in input.into_iter() {
-            let transitions = self.transition.get_row(&state);
-            state = match transitions.iter().find(|(&Transition(t), _)| *t == is) {
-                Some((_, &&s)) => s,
-                // No transition on current symbol from current state: no match.
-                None => return false,
+            state = match self.step(state, &is) {
+                Some(s) if !self.is_dead(s) => s,
+                // No transition on current symbol from current state, or only into a dead state:
+                // no match.
+                _ => return false,
             }
         }
 
         self.is_final_state(&state)
     }
 
+    /// Trace the states visited on the input, beginning with the initial state, each paired with
+    /// the character consumed to enter it ([`None`] for the initial state). The trace stops at
+    /// the end of input, before the first character with no transition, or once a dead state is
+    /// entered, since no match can end after it.
+    #[inline]
+    pub fn walk(&self, input: &str) -> Vec<(usize, Option<char>)>
+    where
+        T: PartialEq<char>,
+    {
+        let dead = self.dead_states();
+
+        let mut state = self.initial_state;
+        let mut path = vec![(state, None)];
+        for c in input.chars() {
+            if dead.contains(&state) {
+                break;
+            }
+
+            state = match self.step(state, &c) {
+                Some(s) => s,
+                None => break,
+            };
+            path.push((state, Some(c)));
+        }
+
+        path
+    }
+
+    /// Return the state entered from `state` on the symbol, or [`None`] if there is no transition
+    /// on it.
+    #[inline]
+    fn step<S>(&self, state: usize, is: &S) -> Option<usize>
+    where
+        T: PartialEq<S>,
+    {
+        self.transition
+            .get_row(&state)
+            .iter()
+            .find(|(&Transition(t), _)| *t == *is)
+            .map(|(_, &&s)| s)
+    }
+
+    /// Determine if the DFA accepts the whole input string, rather than only a prefix of it as
+    /// found by [`DFA::find`]. This is [`DFA::is_match`] on the characters of the string.
+    #[inline]
+    pub fn accepts(&self, input: &str) -> bool
+    where
+        T: PartialEq<char>,
+    {
+        self.is_match(input.chars())
+    }
+
     #[inline]
     pub fn has_match<I>(&self, input: I) -> bool
     where
@@ -195,6 +507,49 @@ where
         self._find_at(input, start, false)
     }
 
+    /// Find the longest match in the input, returning only its index range and the accepting
+    /// state at its end. Unlike [`DFA::find`], the matched symbols are not collected.
+    #[inline]
+    pub fn find_range<I>(&self, input: I) -> Option<(Range<usize>, usize)>
+    where
+        T: PartialEq<I::Item>,
+        I: IntoIterator,
+    {
+        self.find_range_at(input, 0)
+    }
+
+    /// Find the longest match in the input beginning at `start`, returning only its index range
+    /// and the accepting state at its end.
+    #[inline]
+    pub fn find_range_at<I>(&self, input: I, start: usize) -> Option<(Range<usize>, usize)>
+    where
+        T: PartialEq<I::Item>,
+        I: IntoIterator,
+    {
+        let mut state = self.initial_state;
+        let mut last_match = if self.is_final_state(&state) {
+            Some((start..start, state))
+        } else {
+            None
+        };
+
+        let input = input.into_iter().skip(start);
+        for (i, is) in input.enumerate() {
+            state = match self.step(state, &is) {
+                Some(s) if !self.is_dead(s) => s,
+                // No transition on current symbol from current state, or only into a dead state:
+                // no further match.
+                _ => break,
+            };
+
+            if self.is_final_state(&state) {
+                last_match = Some((start..start + i + 1, state));
+            }
+        }
+
+        last_match
+    }
+
     #[inline]
     fn _find_at<I>(&self, input: I, start: usize, shortest: bool) -> Option<(Match<I::Item>, usize)>
     where
@@ -212,18 +567,18 @@ where
             let input = input.into_iter().skip(start);
             let mut span = Vec::new();
             for (i, is) in input.enumerate() {
-                let transitions = self.transition.get_row(&state);
-                state = match transitions.iter().find(|(&Transition(t), _)| *t == is) {
-                    Some((_, &&s)) => s,
-                    // No transition on current symbol from current state: no match.
-                    None => break,
+                state = match self.step(state, &is) {
+                    Some(s) if !self.is_dead(s) => s,
+                    // No transition on current symbol from current state, or only into a dead
+                    // state: no further match.
+                    _ => break,
                 };
 
                 let is_rc = Rc::new(is);
                 span.push(is_rc);
 
                 if self.is_final_state(&state) {
-                    last_match = Some(MatchRc::new(start, i + 1, span.clone()));
+                    last_match = Some(MatchRc::new(start, start + i + 1, span.clone()));
                     if shortest {
                         break;
                     }
@@ -292,16 +647,12 @@ where
             // If there is a transition, consume the symbol and push it to the span.
             while let Some(is_next) = input.peek() {
                 // Find the transition (if it exists) from the current state for the next symbol.
-                let transitions = self.transition.get_row(&state);
-                state = match transitions
-                    .iter()
-                    .find(|(&Transition(t), _)| *t == *is_next)
-                {
+                state = match self.step(state, is_next) {
                     // Transition found, change the current state to the new state.
-                    Some((_, &&s)) => s,
-                    // No transition on next symbol from current state: no further match to be
-                    // found.
-                    None => break,
+                    Some(s) if !self.is_dead(s) => s,
+                    // No transition on next symbol from current state, or only into a dead state:
+                    // no further match to be found.
+                    _ => break,
                 };
 
                 // Actually consume the next symbol from the iterator and push it to the span.
@@ -381,6 +732,90 @@ where
     //   answer](https://stackoverflow.com/a/25832898/8955108) to accomodate character ranges.
     #[inline]
     fn from(nfa: NFA<T>) -> Self {
+        DFAFromNFA::subset_construction(&nfa, |transition_map| {
+            // Isolate transitions.
+            let transitions: Vec<&T> = transition_map.iter().map(|(t, _)| *t).collect();
+            // Disjoin transitions.
+            T::disjoin(transitions)
+                .into_iter()
+                .map(|t| {
+                    let moved_set: HashSet<usize> = transition_map
+                        .iter()
+                        .filter(|(a, _)| a.contains(&t))
+                        .flat_map(|(_, v)| (*v).clone())
+                        .collect();
+                    (t, moved_set)
+                })
+                .collect()
+        })
+    }
+}
+
+impl<T> DFAFromNFA<T>
+where
+    T: Clone + Disjoin + Union + Eq + Hash,
+{
+    /// Create an equivalent DFA from an NFA, like the [`From`] conversion, but by first
+    /// partitioning the alphabet of the NFA into equivalence classes (see [`Alphabet`]).
+    ///
+    /// The subset construction then looks up the classes of each NFA transition instead of
+    /// disjoining the transitions of each DFA state, and the transitions between each pair of
+    /// states are joined into one. Every state therefore partitions its symbols the same way,
+    /// which lets [`DFA::minimize_by`] merge states that differ only in how they split a range.
+    #[inline]
+    pub fn partitioned(nfa: &NFA<T>) -> Self {
+        let alphabet = Alphabet::of_nfa(nfa);
+        let DFAFromNFA { dfa, nfa_mapping } = DFAFromNFA::with_alphabet(nfa, &alphabet);
+        DFAFromNFA {
+            dfa: dfa.expand(&alphabet),
+            nfa_mapping,
+        }
+    }
+}
+
+impl DFAFromNFA<usize> {
+    /// Create an equivalent DFA from an NFA by the subset construction, with transitions on the
+    /// numbers of the classes of an alphabet rather than on the symbols of the NFA.
+    ///
+    /// The alphabet may be any partition in which each transition symbol of the NFA is a union
+    /// of classes, such as [`Alphabet::of_nfa`] or one shared by several NFAs.
+    #[inline]
+    pub fn with_alphabet<T>(nfa: &NFA<T>, alphabet: &Alphabet<T>) -> Self
+    where
+        T: Clone + Disjoin + Union + Eq + Hash,
+    {
+        let mut classes: HashMap<&T, Vec<usize>> = HashMap::new();
+        for (_, t, _) in &nfa.transition {
+            if let nfa::Transition::Some(t) = t {
+                classes.entry(t).or_insert_with(|| alphabet.classes_of(t));
+            }
+        }
+
+        DFAFromNFA::subset_construction(nfa, |transition_map| {
+            let mut moved: BTreeMap<usize, HashSet<usize>> = BTreeMap::new();
+            for (t, v) in transition_map {
+                for &class in &classes[t] {
+                    moved.entry(class).or_default().extend(v.iter().copied());
+                }
+            }
+            moved.into_iter().collect()
+        })
+    }
+}
+
+impl<T> DFAFromNFA<T>
+where
+    T: Clone + Eq + Hash,
+{
+    /// Create an equivalent DFA from an NFA by the subset construction, where `moves` gives the
+    /// transitions of a DFA state, and the NFA states each moves to, from the non-epsilon
+    /// transitions of its NFA states.
+    #[inline]
+    fn subset_construction<U, F>(nfa: &NFA<U>, moves: F) -> Self
+    where
+        U: Clone + Eq + Hash,
+        F: Fn(&[(&U, &HashSet<usize>)]) -> Vec<(T, HashSet<usize>)>,
+    {
         let mut dfa = DFA::new();
         let mut nfa_mapping = HashMap::new();
 
@@ -405,7 +840,7 @@ where
         while let Some(s) = unmarked_states.pop_front() {
             // Get all non-epsilon transitions and destinations from the NFA states in this set
             // state.
-            let transition_map: Vec<(&T, &HashSet<usize>)> = s
+            let transition_map: Vec<(&U, &HashSet<usize>)> = s
                 .clone()
                 .nfa_states
                 .into_iter()
@@ -418,17 +853,7 @@ where
                 })
                 .collect();
 
-            // Isolate transitions.
-            let transitions: Vec<&T> = transition_map.iter().map(|(t, _)| *t).collect();
-            // Disjoin transitions.
-            let disjoint_transitions = T::disjoin(transitions);
-
-            for t in disjoint_transitions {
-                let moved_set: HashSet<usize> = transition_map
-                    .iter()
-                    .filter(|(a, _)| a.contains(&t))
-                    .flat_map(|(_, v)| (*v).clone())
-                    .collect();
+            for (t, moved_set) in moves(&transition_map) {
                 let epsilon_closure = nfa.epsilon_closure_set(&moved_set);
                 let mut new_state = DState::new(0, epsilon_closure);
 
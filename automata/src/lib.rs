@@ -2,11 +2,14 @@
 #![deny(future_incompatible)]
 
 mod matching;
+mod regex;
 
+pub mod alphabet;
 pub mod dfa;
 pub mod nfa;
 pub mod table;
 
+pub use alphabet::Alphabet;
 pub use dfa::DFA;
 pub use matching::Match;
 pub use nfa::NFA;
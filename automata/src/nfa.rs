@@ -1,11 +1,8 @@
 use crate::matching::Match;
 use crate::table::Table;
 
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
-use std::{
-    collections::{HashMap, HashSet},
-    rc::Rc,
-};
 
 include!("macros.rs");
 
@@ -221,28 +218,39 @@ where
     }
 
     /// Computes the function epsilon-closure for some given state in the NFA. Returns the set of
-    /// all states accessible from the given state on epsilon transitions only.
+    /// all states accessible from the given state on epsilon transitions only, including the
+    /// state itself.
     #[inline]
     pub fn epsilon_closure(&self, state: usize) -> HashSet<usize> {
-        let transitions = self.transitions_from(state);
-        let mut closure: HashSet<_> = transitions
-            .into_iter()
-            .filter(|(t, _)| **t == Transition::Epsilon)
-            .flat_map(|(_, dest)| dest.iter().flat_map(|&i| self.epsilon_closure(i)))
-            .collect();
-        closure.insert(state);
-        closure
+        let mut set = HashSet::new();
+        set.insert(state);
+        self.epsilon_closure_set(&set)
     }
 
-    /// Computes the union of epsilon-closures for each state in the given set of states.
+    /// Computes the epsilon-closure of a set of states. Returns the set of all states accessible
+    /// from any of the given states on zero or more epsilon transitions, which includes the given
+    /// states themselves. This is the set of states an NFA simulation may be in after reaching any
+    /// of the given states without consuming input.
     #[inline]
     pub fn epsilon_closure_set(&self, state_set: &HashSet<usize>) -> HashSet<usize> {
-        let mut set = state_set.clone();
-        for state in state_set.iter() {
-            let state_closure = self.epsilon_closure(*state);
-            set = set.union(&state_closure).cloned().collect();
+        let mut closure = state_set.clone();
+
+        // Visit each state once, so that cycles of epsilon transitions terminate.
+        let mut stack: Vec<_> = state_set.iter().copied().collect();
+        while let Some(state) = stack.pop() {
+            let transitions = self.transitions_from(state);
+            let dests = transitions
+                .into_iter()
+                .filter(|(t, _)| **t == Transition::Epsilon)
+                .flat_map(|(_, dest)| dest.iter());
+            for &dest in dests {
+                if closure.insert(dest) {
+                    stack.push(dest);
+                }
+            }
         }
-        set
+
+        closure
     }
 
     #[inline]
@@ -355,45 +363,40 @@ where
         self._find_at(input, start, false)
     }
 
+    /// Find the longest (or shortest) match beginning at `start` by simulating the NFA directly,
+    /// tracking the set of states it may be in. This takes time linear in the length of the
+    /// match, and space linear in the number of states, without constructing a DFA.
     #[inline]
     fn _find_at<I>(&self, input: I, start: usize, shortest: bool) -> Option<Match<I::Item>>
     where
         T: PartialEq<I::Item>,
         I: IntoIterator,
     {
-        struct MatchRc<T> {
-            start: usize,
-            end: usize,
-            span: Vec<Rc<T>>,
-        }
-
-        impl<T> MatchRc<T> {
-            #[inline]
-            fn new(start: usize, end: usize, span: Vec<Rc<T>>) -> Self {
-                Self { start, end, span }
-            }
-        }
+        let is_final =
+            |state_set: &HashSet<usize>| state_set.iter().any(|s| self.is_final_state(s));
 
-        let mut last_match = if self.is_final_state(&self.initial_state) {
-            Some(MatchRc::new(start, start, vec![]))
+        let mut state_set = self.epsilon_closure(self.initial_state);
+        let mut last_end = if is_final(&state_set) {
+            Some(start)
         } else {
             None
         };
 
-        if !(shortest && last_match.is_some()) {
-            let mut state_set = self.epsilon_closure(self.initial_state);
-
+        let mut span = Vec::new();
+        if !(shortest && last_end.is_some()) {
             let input = input.into_iter().skip(start);
-            let mut span = Vec::new();
             for (i, is) in input.enumerate() {
                 let moved_set = self.move_set(&state_set, &is);
                 state_set = self.epsilon_closure_set(&moved_set);
 
-                let is_rc = Rc::new(is);
-                span.push(is_rc);
+                // No states left: no longer match is possible.
+                if state_set.is_empty() {
+                    break;
+                }
 
-                if state_set.iter().any(|s| self.is_final_state(s)) {
-                    last_match = Some(MatchRc::new(start, i + 1, span.clone()));
+                span.push(is);
+                if is_final(&state_set) {
+                    last_end = Some(start + i + 1);
                     if shortest {
                         break;
                     }
@@ -401,19 +404,9 @@ where
             }
         }
 
-        last_match.map(|m: MatchRc<I::Item>| {
-            Match::new(
-                m.start,
-                m.end,
-                m.span
-                    .into_iter()
-                    .map(|rc| match Rc::try_unwrap(rc) {
-                        Ok(v) => v,
-                        // Shouldn't ever have any lingering references.
-                        Err(_) => unreachable!(),
-                    })
-                    .collect(),
-            )
+        last_end.map(|end| {
+            span.truncate(end - start);
+            Match::new(start, end, span)
         })
     }
 }
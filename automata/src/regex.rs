@@ -0,0 +1,257 @@
+use crate::dfa::{Transition, DFA};
+use crate::nfa::{self, NFA};
+
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::Hash;
+use std::iter;
+
+impl<T> DFA<T>
+where
+    T: Clone + Eq + Hash + fmt::Display,
+{
+    /// Construct a regular expression for the language accepted by the DFA by state elimination,
+    /// or return [`None`] if it accepts nothing, which no expression without a symbol for the
+    /// empty language can describe.
+    ///
+    /// Each symbol is written with its [`fmt::Display`] implementation, which must produce an
+    /// atom of the expression syntax, such as a bracketed character class. The empty string is
+    /// written as `()`. The expression is not minimal, but accepts the same language as the DFA,
+    /// so it may be parsed again to construct an equivalent automaton.
+    #[inline]
+    pub fn to_regex(&self) -> Option<String> {
+        let edges = (&self.transition)
+            .into_iter()
+            .map(|(&src, Transition(t), &dest)| (src, Some(t), dest))
+            .collect();
+        eliminate(
+            self.total_states,
+            self.initial_state,
+            &self.final_states,
+            edges,
+        )
+    }
+}
+
+impl<T> NFA<T>
+where
+    T: Clone + Eq + Hash + fmt::Display,
+{
+    /// Construct a regular expression for the language accepted by the NFA by state elimination,
+    /// as [`DFA::to_regex`] does. Epsilon transitions are written as the empty string.
+    #[inline]
+    pub fn to_regex(&self) -> Option<String> {
+        let edges = (&self.transition)
+            .into_iter()
+            .flat_map(|(&src, t, dests)| {
+                let t = match t {
+                    nfa::Transition::Some(t) => Some(t),
+                    nfa::Transition::Epsilon => None,
+                };
+                dests.iter().map(move |&dest| (src, t, dest))
+            })
+            .collect();
+        eliminate(
+            self.total_states,
+            self.initial_state,
+            &self.final_states,
+            edges,
+        )
+    }
+}
+
+/// A regular expression built during state elimination, simplified as it is built so that the
+/// empty string and single alternatives do not clutter the result.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Regex {
+    /// The empty string.
+    Empty,
+    Symbol(String),
+    Concat(Vec<Regex>),
+    Union(Vec<Regex>),
+    Star(Box<Regex>),
+}
+
+impl Regex {
+    #[inline]
+    fn concat(parts: Vec<Regex>) -> Self {
+        let mut flat = Vec::new();
+        for part in parts {
+            match part {
+                Regex::Empty => {}
+                Regex::Concat(inner) => flat.extend(inner),
+                part => flat.push(part),
+            }
+        }
+
+        match flat.len() {
+            0 => Regex::Empty,
+            1 => flat.pop().unwrap(),
+            _ => Regex::Concat(flat),
+        }
+    }
+
+    #[inline]
+    fn union(self, other: Self) -> Self {
+        let mut alternatives = Vec::new();
+        for part in iter::once(self).chain(iter::once(other)) {
+            let parts = match part {
+                Regex::Union(inner) => inner,
+                part => vec![part],
+            };
+            for part in parts {
+                if !alternatives.contains(&part) {
+                    alternatives.push(part);
+                }
+            }
+        }
+
+        // The empty string is redundant next to a starred expression, and together with `xx*`
+        // makes `x*`.
+        if alternatives.contains(&Regex::Empty) {
+            for alternative in alternatives.iter_mut() {
+                if let Regex::Concat(parts) = alternative {
+                    if let [x, Regex::Star(starred)] = parts.as_slice() {
+                        if x == &**starred {
+                            *alternative = x.clone().star();
+                        }
+                    }
+                }
+            }
+            if alternatives.iter().any(|re| matches!(re, Regex::Star(_))) {
+                alternatives.retain(|re| *re != Regex::Empty);
+            }
+        }
+
+        if alternatives.len() == 1 {
+            alternatives.pop().unwrap()
+        } else {
+            Regex::Union(alternatives)
+        }
+    }
+
+    #[inline]
+    fn star(self) -> Self {
+        match self {
+            Regex::Empty => Regex::Empty,
+            Regex::Star(_) => self,
+            _ => Regex::Star(Box::new(self)),
+        }
+    }
+
+    /// Write the expression, with parentheses around subexpressions of lower precedence than
+    /// `precedence`: 0 for a union, 1 for a concatenation, and 2 for an atom. Starred expressions
+    /// are never starred again, so they may be written as atoms.
+    #[inline]
+    fn write(&self, f: &mut fmt::Formatter<'_>, precedence: u8) -> fmt::Result {
+        let own = match self {
+            Regex::Union(_) => 0,
+            Regex::Concat(_) => 1,
+            _ => 2,
+        };
+        if own < precedence {
+            write!(f, "(")?;
+        }
+
+        match self {
+            Regex::Empty => write!(f, "()")?,
+            Regex::Symbol(s) => write!(f, "{}", s)?,
+            Regex::Concat(parts) => {
+                for part in parts {
+                    part.write(f, 2)?;
+                }
+            }
+            Regex::Union(alternatives) => {
+                for (i, alternative) in alternatives.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "|")?;
+                    }
+                    alternative.write(f, 1)?;
+                }
+            }
+            Regex::Star(inner) => {
+                inner.write(f, 2)?;
+                write!(f, "*")?;
+            }
+        }
+
+        if own < precedence {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Regex {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+/// Eliminate the states of an automaton with the given transitions, where [`None`] is an epsilon
+/// transition, leaving one expression from a new initial state to a new final state.
+#[inline]
+fn eliminate<T: fmt::Display>(
+    total_states: usize,
+    initial: usize,
+    finals: &HashSet<usize>,
+    edges: Vec<(usize, Option<&T>, usize)>,
+) -> Option<String> {
+    // The new initial and final states are labeled after the states of the automaton.
+    let (start, end) = (total_states, total_states + 1);
+    let size = total_states + 2;
+    let mut table: Vec<Vec<Option<Regex>>> = vec![vec![None; size]; size];
+
+    let add = |table: &mut Vec<Vec<Option<Regex>>>, src: usize, dest: usize, re: Regex| {
+        table[src][dest] = Some(match table[src][dest].take() {
+            Some(existing) => existing.union(re),
+            None => re,
+        });
+    };
+    add(&mut table, start, initial, Regex::Empty);
+    for &state in finals {
+        add(&mut table, state, end, Regex::Empty);
+    }
+    for (src, t, dest) in edges {
+        let re = t.map_or(Regex::Empty, |t| Regex::Symbol(t.to_string()));
+        add(&mut table, src, dest, re);
+    }
+
+    // Eliminate the states with the fewest paths through them first, to keep the expression
+    // small.
+    let mut remaining: Vec<usize> = (0..total_states).collect();
+    while !remaining.is_empty() {
+        let paths = |k: usize| {
+            let ins = (0..size)
+                .filter(|&i| i != k && table[i][k].is_some())
+                .count();
+            let outs = (0..size)
+                .filter(|&j| j != k && table[k][j].is_some())
+                .count();
+            ins * outs
+        };
+        let (index, _) = remaining
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, &k)| paths(k))
+            .unwrap();
+        let k = remaining.swap_remove(index);
+
+        let looped = table[k][k].take().map_or(Regex::Empty, Regex::star);
+        let ins: Vec<_> = (0..size)
+            .filter_map(|i| table[i][k].take().map(|re| (i, re)))
+            .collect();
+        let outs: Vec<_> = (0..size)
+            .filter_map(|j| table[k][j].take().map(|re| (j, re)))
+            .collect();
+        for (i, into) in &ins {
+            for (j, out) in &outs {
+                let path = Regex::concat(vec![into.clone(), looped.clone(), out.clone()]);
+                add(&mut table, *i, *j, path);
+            }
+        }
+    }
+
+    table[start][end].take().map(|re| re.to_string())
+}
@@ -24,6 +24,30 @@ where
             map: HashMap::new(),
         }
     }
+
+    /// Create an empty table with space for at least `rows` rows without reallocating.
+    #[inline]
+    pub fn with_capacity(rows: usize) -> Self {
+        Self {
+            map: HashMap::with_capacity(rows),
+        }
+    }
+
+    /// Create a table from entries of keys and values, in one pass. Later entries overwrite
+    /// earlier ones with the same keys. Space is reserved up front for as many rows as the
+    /// iterator reports entries, since there are at most that many.
+    #[inline]
+    pub fn from_entries<I>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (T, U, V)>,
+    {
+        let entries = entries.into_iter();
+        let mut table = Self::with_capacity(entries.size_hint().0);
+        for (row, col, val) in entries {
+            table.map.entry(row).or_default().insert(col, val);
+        }
+        table
+    }
 }
 
 impl<T, U, V> Default for Table<T, U, V>
@@ -0,0 +1,245 @@
+use automata::{dfa::Transition, table::Table, DFA, NFA};
+
+use std::cell::Cell;
+use std::collections::HashSet;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+/// Construct a DFA accepting a(b)*.
+fn ab_star() -> DFA<char> {
+    let mut d = DFA::new();
+    let s1 = d.add_state(true);
+    d.add_transition(d.initial_state, s1, Transition('a'));
+    d.add_transition(s1, s1, Transition('b'));
+    d
+}
+
+#[test]
+fn test_find_range() {
+    let d = ab_star();
+
+    assert_eq!(Some((0..3, 1)), d.find_range("abbc".chars()));
+    assert_eq!(Some((0..1, 1)), d.find_range("a".chars()));
+    assert_eq!(None, d.find_range("ba".chars()));
+    assert_eq!(None, d.find_range("".chars()));
+}
+
+#[test]
+fn test_find_range_at() {
+    let d = ab_star();
+
+    assert_eq!(Some((2..4, 1)), d.find_range_at("cdab".chars(), 2));
+    assert_eq!(None, d.find_range_at("abab".chars(), 1));
+
+    let m = d.find_at("cdab".chars(), 2).map(|(m, _)| m.range());
+    assert_eq!(Some(2..4), m);
+}
+
+#[test]
+fn test_find_range_agrees_with_find() {
+    let d = ab_star();
+
+    for input in &["abbbc", "a", "ab", "c", ""] {
+        let m = d.find(input.chars()).map(|(m, _)| m.range());
+        let r = d.find_range(input.chars()).map(|(r, _)| r);
+        assert_eq!(m, r);
+    }
+}
+
+#[test]
+fn test_reverse() {
+    let d = ab_star();
+    let n = d.reverse();
+
+    assert_eq!(d.total_states + 1, n.total_states);
+
+    // The reversal of a(b)* is (b)*a.
+    for input in &["a", "ba", "bbba"] {
+        assert!(n.is_match(input.chars()), "{} not matched", input);
+    }
+    for input in &["", "ab", "b", "aa", "bab"] {
+        assert!(!n.is_match(input.chars()), "{} matched", input);
+    }
+}
+
+#[test]
+fn test_minimize() {
+    // Union of "ab" and "cb", with distinct states for each "b" and final state.
+    let mut d = DFA::new();
+    let (a, c) = (d.add_state(false), d.add_state(false));
+    let (ab, cb) = (d.add_state(true), d.add_state(true));
+    d.add_transition(d.initial_state, a, Transition('a'));
+    d.add_transition(d.initial_state, c, Transition('c'));
+    d.add_transition(a, ab, Transition('b'));
+    d.add_transition(c, cb, Transition('b'));
+
+    let m = d.minimize();
+    assert_eq!(3, m.total_states);
+    for input in &["ab", "cb"] {
+        assert!(m.is_match(input.chars()), "{} not matched", input);
+    }
+    for input in &["", "a", "b", "ac", "abb", "cbb"] {
+        assert!(!m.is_match(input.chars()), "{} matched", input);
+    }
+
+    // Keeping the final states apart prevents their predecessors from being merged.
+    let (m, mapping) = d.minimize_by(|state| if state == cb { 1 } else { 0 });
+    assert_eq!(5, m.total_states);
+    assert_ne!(mapping[ab], mapping[cb]);
+    assert!(m.is_match("ab".chars()));
+}
+
+#[test]
+fn test_minimize_loop() {
+    // a(b)* with a redundant copy of the loop state.
+    let mut d = ab_star();
+    let s2 = d.add_state(true);
+    d.add_transition(1, s2, Transition('b'));
+    d.add_transition(s2, 1, Transition('b'));
+
+    let m = d.minimize();
+    assert_eq!(2, m.total_states);
+    assert!(m.is_match("abbb".chars()));
+    assert!(!m.is_match("ba".chars()));
+}
+
+#[test]
+fn test_minimize_by_keeps_final_states() {
+    // a(a)*, where the initial and final states have the same successors.
+    let mut d = DFA::new();
+    let s1 = d.add_state(true);
+    d.add_transition(d.initial_state, s1, Transition('a'));
+    d.add_transition(s1, s1, Transition('a'));
+
+    // A key that does not tell the final state apart still does not merge it with the initial
+    // state, which would accept the empty string.
+    let (m, mapping) = d.minimize_by(|_| 0);
+    assert_eq!(2, m.total_states);
+    assert_ne!(mapping[d.initial_state], mapping[s1]);
+    assert!(!m.is_match("".chars()));
+    assert!(m.is_match("aa".chars()));
+}
+
+/// Construct a DFA accepting a, with a dead state entered on b that loops on x.
+fn a_or_dead() -> DFA<char> {
+    let mut d = DFA::new();
+    let s1 = d.add_state(true);
+    let dead = d.add_state(false);
+    d.add_transition(d.initial_state, s1, Transition('a'));
+    d.add_transition(d.initial_state, dead, Transition('b'));
+    d.add_transition(dead, dead, Transition('x'));
+    d
+}
+
+#[test]
+fn test_dead_states() {
+    let mut d = a_or_dead();
+    assert_eq!(
+        &vec![2].into_iter().collect::<HashSet<_>>(),
+        d.dead_states()
+    );
+    assert!(d.is_dead(2));
+    assert!(!d.is_dead(d.initial_state));
+    assert!(!d.is_dead(1));
+
+    assert!(ab_star().dead_states().is_empty());
+
+    // Adding a transition to a final state revives the dead state.
+    d.add_transition(2, 1, Transition('a'));
+    assert!(d.dead_states().is_empty());
+    assert!(d.is_match("bxa".chars()));
+}
+
+#[test]
+fn test_find_stops_at_dead_state() {
+    let input = format!("b{}", "x".repeat(100));
+
+    // Count the symbols consumed by matching.
+    let consumed = Cell::new(0);
+    let counted = || {
+        consumed.set(0);
+        input.chars().inspect(|_| consumed.set(consumed.get() + 1))
+    };
+
+    // Matching stops at the mismatching prefix, which enters the dead state.
+    let d = a_or_dead();
+    assert_eq!(None, d.find(counted()));
+    assert_eq!(1, consumed.get());
+    assert_eq!(None, d.find_range(counted()));
+    assert_eq!(1, consumed.get());
+    assert_eq!(None, d.find_mut(&mut counted().peekable()));
+    assert_eq!(1, consumed.get());
+    assert!(!d.is_match(counted()));
+    assert_eq!(1, consumed.get());
+}
+
+#[test]
+fn test_remove_dead_transitions() {
+    let mut d = a_or_dead();
+    d.remove_dead_transitions();
+    assert!((&d.transition).into_iter().all(|(_, _, &dest)| dest != 2));
+
+    // The language is unchanged.
+    assert!(d.is_match("a".chars()));
+    assert!(!d.is_match("b".chars()));
+    assert!(!d.is_match("bx".chars()));
+}
+
+#[test]
+fn test_accepts() {
+    // Accepts a.
+    let mut d = DFA::new();
+    let s1 = d.add_state(true);
+    d.add_transition(d.initial_state, s1, Transition('a'));
+
+    assert!(d.accepts("a"));
+    assert!(!d.accepts("ab"));
+    assert!(!d.accepts(""));
+
+    // Unlike accepts, find matches a prefix.
+    let (m, _) = d.find("ab".chars()).unwrap();
+    assert_eq!((0, 1), (m.start, m.end));
+
+    let d = ab_star();
+    assert!(d.accepts("abbb"));
+    assert!(!d.accepts("abba"));
+}
+
+#[test]
+fn test_send_sync() {
+    assert_send_sync::<DFA<char>>();
+    assert_send_sync::<Transition<char>>();
+    assert_send_sync::<Table<usize, Transition<char>, usize>>();
+    assert_send_sync::<NFA<char>>();
+}
+
+/// Construct a DFA accepting a+b.
+fn a_plus_b() -> DFA<char> {
+    let mut d = DFA::new();
+    let s1 = d.add_state(false);
+    let s2 = d.add_state(true);
+    d.add_transition(d.initial_state, s1, Transition('a'));
+    d.add_transition(s1, s1, Transition('a'));
+    d.add_transition(s1, s2, Transition('b'));
+    d
+}
+
+#[test]
+fn test_walk() {
+    let d = a_plus_b();
+
+    assert_eq!(
+        vec![(0, None), (1, Some('a')), (1, Some('a')), (2, Some('b'))],
+        d.walk("aab")
+    );
+
+    // Stops before the first character with no transition.
+    assert_eq!(vec![(0, None), (1, Some('a'))], d.walk("acb"));
+    assert_eq!(vec![(0, None)], d.walk(""));
+
+    // Stops once a dead state is entered.
+    let d = a_or_dead();
+    let dead = d.walk("bab");
+    assert_eq!(2, dead.len());
+    assert!(d.is_dead(dead[1].0));
+}
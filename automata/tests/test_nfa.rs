@@ -1,5 +1,7 @@
 use automata::{nfa::Transition, NFA};
 
+use std::collections::HashSet;
+
 #[test]
 fn test_new() {
     let n: NFA<bool> = NFA::new();
@@ -80,3 +82,55 @@ fn test_combine() {
     assert_eq!(5, combined.total_states);
     assert_eq!(2, combined.final_states.len());
 }
+
+#[test]
+fn test_epsilon_closure() {
+    // 0 -ε-> 1 -ε-> 2 -a-> 3 -ε-> 4 -ε-> 1
+    // 5 -ε-> 5
+    let mut n: NFA<char> = NFA::new();
+    for _ in 0..5 {
+        n.add_state(false);
+    }
+    n.add_epsilon_transition(0, 1);
+    n.add_epsilon_transition(1, 2);
+    n.add_labeled_transition(2, 3, 'a');
+    n.add_epsilon_transition(3, 4);
+    n.add_epsilon_transition(4, 1);
+    n.add_epsilon_transition(5, 5);
+
+    let set = |states: &[usize]| states.iter().copied().collect::<HashSet<_>>();
+
+    // Epsilon chains are followed transitively, but labeled transitions are not.
+    assert_eq!(set(&[0, 1, 2]), n.epsilon_closure(0));
+    assert_eq!(set(&[2]), n.epsilon_closure(2));
+
+    // Cycles terminate, and the given states are always included.
+    assert_eq!(set(&[1, 2, 3, 4]), n.epsilon_closure(3));
+    assert_eq!(set(&[5]), n.epsilon_closure(5));
+
+    assert_eq!(set(&[]), n.epsilon_closure_set(&set(&[])));
+    assert_eq!(set(&[1, 2, 3, 4, 5]), n.epsilon_closure_set(&set(&[3, 5])));
+    assert_eq!(
+        set(&[0, 1, 2, 3, 4]),
+        n.epsilon_closure_set(&set(&[0, 4, 3]))
+    );
+}
+
+#[test]
+fn test_epsilon_closure_cycle() {
+    // 0 -ε-> 1 -ε-> 2 -ε-> 0, as in the NFA of a starred expression that matches the empty
+    // string, such as (a*)*. Following the cycle recursively would never terminate.
+    let mut n: NFA<char> = NFA::new();
+    let (s1, s2) = (n.add_state(false), n.add_state(true));
+    n.add_epsilon_transition(0, s1);
+    n.add_epsilon_transition(s1, s2);
+    n.add_epsilon_transition(s2, 0);
+    n.add_labeled_transition(s2, s1, 'a');
+
+    let all: HashSet<_> = vec![0, s1, s2].into_iter().collect();
+    for state in 0..3 {
+        assert_eq!(all, n.epsilon_closure(state));
+    }
+    assert!(n.is_match("".chars()));
+    assert!(n.is_match("aa".chars()));
+}
@@ -0,0 +1,22 @@
+use automata::table::Table;
+
+#[test]
+fn test_from_entries() {
+    let table = Table::from_entries(vec![(0, 'a', 1), (0, 'b', 2), (1, 'a', 0), (0, 'a', 3)]);
+
+    // Later entries overwrite earlier ones.
+    assert_eq!(Some(&3), table.get(&0, &'a'));
+    assert_eq!(Some(&2), table.get(&0, &'b'));
+    assert_eq!(Some(&0), table.get(&1, &'a'));
+    assert_eq!(None, table.get(&1, &'b'));
+    assert_eq!(3, table.into_iter().count());
+}
+
+#[test]
+fn test_with_capacity() {
+    let mut table = Table::with_capacity(16);
+    assert_eq!(None, table.get(&0, &'a'));
+
+    table.set(0, 'a', 1);
+    assert_eq!(Some(&1), table.get(&0, &'a'));
+}
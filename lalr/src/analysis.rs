@@ -0,0 +1,97 @@
+use crate::grammar::{FirstSets, FollowSets, Grammar, Lookahead};
+use crate::lr0::LR0Automaton;
+use crate::lr1::LR1Automaton;
+
+use std::cell::OnceCell;
+use std::collections::BTreeSet;
+
+/// The analyses of a grammar that parse table constructions start from: its FIRST and FOLLOW sets
+/// and its LR(0) and LR(1) automata.
+///
+/// Each analysis is computed on first use and kept, so that several tables of the same grammar
+/// may be constructed from one `GrammarAnalysis` without recomputing them. The table
+/// constructions of [`Grammar`] each use a fresh analysis.
+#[derive(Debug)]
+pub struct GrammarAnalysis<'g, T: 'g, N: 'g, A: 'g> {
+    grammar: &'g Grammar<T, N, A>,
+    first_sets: OnceCell<FirstSets<'g, T, N>>,
+    follow_sets: OnceCell<FollowSets<'g, T, N>>,
+    lr0_automaton: OnceCell<LR0Automaton<'g, T, N, A>>,
+    lr1_automaton: OnceCell<LR1Automaton<'g, T, N, A>>,
+}
+
+impl<T, N, A> Grammar<T, N, A>
+where
+    T: Ord,
+    N: Ord,
+{
+    /// Create an analysis of the grammar, from which several parse tables may be constructed.
+    #[inline]
+    pub fn analyze(&self) -> GrammarAnalysis<'_, T, N, A> {
+        GrammarAnalysis::new(self)
+    }
+}
+
+impl<'g, T, N, A> GrammarAnalysis<'g, T, N, A>
+where
+    T: Ord,
+    N: Ord,
+{
+    #[inline]
+    pub fn new(grammar: &'g Grammar<T, N, A>) -> Self {
+        Self {
+            grammar,
+            first_sets: OnceCell::new(),
+            follow_sets: OnceCell::new(),
+            lr0_automaton: OnceCell::new(),
+            lr1_automaton: OnceCell::new(),
+        }
+    }
+
+    /// Return the analyzed grammar.
+    #[inline]
+    pub fn grammar(&self) -> &'g Grammar<T, N, A> {
+        self.grammar
+    }
+
+    /// Return the FIRST sets of the grammar (see [`Grammar::first_sets`]).
+    #[inline]
+    pub fn first_sets(&self) -> &FirstSets<'g, T, N> {
+        self.first_sets.get_or_init(|| self.grammar.first_sets())
+    }
+
+    /// Return the FOLLOW sets of the grammar (see [`Grammar::follow_sets`]).
+    #[inline]
+    pub fn follow_sets(&self) -> &FollowSets<'g, T, N> {
+        self.follow_sets
+            .get_or_init(|| self.grammar.follow_sets(Some(self.first_sets())))
+    }
+
+    /// Return the FOLLOW set of a nonterminal, including the endmarker if it may follow the
+    /// nonterminal.
+    #[inline]
+    pub fn follow(&self, n: &N) -> BTreeSet<Lookahead<'g, T>> {
+        match self.follow_sets().get(n) {
+            Some((terminals, endmarker)) => terminals
+                .iter()
+                .map(|&t| Lookahead::Terminal(t))
+                .chain(Some(Lookahead::EndMarker).filter(|_| *endmarker))
+                .collect(),
+            None => BTreeSet::new(),
+        }
+    }
+
+    /// Return the LR(0) automaton of the grammar (see [`Grammar::lr0_automaton`]).
+    #[inline]
+    pub fn lr0_automaton(&self) -> &LR0Automaton<'g, T, N, A> {
+        self.lr0_automaton
+            .get_or_init(|| self.grammar.lr0_automaton())
+    }
+
+    /// Return the LR(1) automaton of the grammar (see [`Grammar::lr1_automaton`]).
+    #[inline]
+    pub fn lr1_automaton(&self) -> &LR1Automaton<'g, T, N, A> {
+        self.lr1_automaton
+            .get_or_init(|| self.grammar.lr1_automaton_from(self.first_sets()))
+    }
+}
@@ -0,0 +1,181 @@
+use crate::grammar::{Grammar, Rhs, Symbol};
+use crate::lr1::{LR1Action, LR1Table};
+
+use std::fmt::{Debug, Write};
+use std::ptr;
+
+impl<'g, T, N, A> LR1Table<'g, T, N, A>
+where
+    T: Ord + Debug,
+    N: Ord + Debug,
+{
+    /// Generate the Rust source code of a standalone parser for this table, which must have been
+    /// constructed from `grammar`. The ACTION and GOTO tables are embedded as static arrays, so
+    /// that no table construction is needed at runtime.
+    ///
+    /// The generated code is meant to be included in a module with [`include!`], and defines:
+    ///
+    /// ```ignore
+    /// pub fn parse<V, I, F>(input: I, reduce: F) -> Result<V, Option<usize>>
+    /// where
+    ///     I: IntoIterator<Item = (usize, V)>,
+    ///     F: FnMut(usize, Vec<V>) -> V;
+    /// ```
+    ///
+    /// Each input token is a pair of the index of its terminal and its semantic value. Terminals
+    /// are numbered by their order among the terminals used in the grammar, and productions are
+    /// numbered in the order of [`Grammar::rules`]; both numberings are listed in a comment at the
+    /// top of the generated code. On each reduction, `reduce` is called with the index of the
+    /// production and the values of the symbols in its body, and returns the value of the
+    /// nonterminal. If no action exists for a token, the index of its terminal is returned as the
+    /// error, or [`None`] if the end of input was unexpected.
+    #[inline]
+    pub fn codegen(&self, grammar: &'g Grammar<T, N, A>) -> String {
+        let terminals: Vec<_> = grammar.terminals().into_iter().collect();
+        let nonterminals: Vec<_> = grammar.rules.keys().collect();
+        let productions: Vec<(&N, &Rhs<T, N, A>)> = grammar
+            .rules
+            .iter()
+            .flat_map(|(lhs, rhs_set)| rhs_set.iter().map(move |rhs| (lhs, rhs)))
+            .collect();
+
+        let nonterminal_idx = |n: &N| nonterminals.binary_search(&n).unwrap();
+        let production_idx = |rhs: &Rhs<T, N, A>| {
+            productions
+                .iter()
+                .position(|(_, other)| ptr::eq(rhs, *other))
+                .unwrap()
+        };
+
+        let action_code = |action: Option<&LR1Action<'g, T, N, A>>| match action {
+            Some(LR1Action::Shift(dest)) => format!("Action::Shift({})", dest),
+            Some(LR1Action::Reduce(_, rhs)) => format!("Action::Reduce({})", production_idx(rhs)),
+            Some(LR1Action::Accept) => "Action::Accept".to_string(),
+            None => "Action::Error".to_string(),
+        };
+
+        let mut code = String::new();
+
+        // Comment listing the numbering of terminals and productions.
+        writeln!(code, "// Parser generated by `lalr`. Do not edit.").unwrap();
+        writeln!(code, "//\n// Terminals:").unwrap();
+        for (i, t) in terminals.iter().enumerate() {
+            writeln!(code, "//     {}: {:?}", i, t).unwrap();
+        }
+        writeln!(code, "//\n// Productions:").unwrap();
+        for (i, (lhs, rhs)) in productions.iter().enumerate() {
+            write!(code, "//     {}: {:?} ->", i, lhs).unwrap();
+            for sy in &rhs.body {
+                match sy {
+                    Symbol::Terminal(t) => write!(code, " {:?}", t),
+                    Symbol::Nonterminal(n) => write!(code, " {:?}", n),
+                }
+                .unwrap();
+            }
+            writeln!(code).unwrap();
+        }
+
+        writeln!(code, "\n#[derive(Clone, Copy)]\nenum Action {{").unwrap();
+        writeln!(
+            code,
+            "    Error,\n    Shift(usize),\n    Reduce(usize),\n    Accept,\n}}"
+        )
+        .unwrap();
+
+        writeln!(code, "\nconst INITIAL: usize = {};", self.initial).unwrap();
+        writeln!(code, "const ENDMARKER: usize = {};", terminals.len()).unwrap();
+
+        // ACTION table, with a column for each terminal and a final column for the endmarker.
+        writeln!(
+            code,
+            "\nstatic ACTION: [[Action; {}]; {}] = [",
+            terminals.len() + 1,
+            self.states.len()
+        )
+        .unwrap();
+        for state in &self.states {
+            let row: Vec<_> = terminals
+                .iter()
+                .map(|t| action_code(state.actions.get(t)))
+                .chain(Some(action_code(state.endmarker.as_ref())))
+                .collect();
+            writeln!(code, "    [{}],", row.join(", ")).unwrap();
+        }
+        writeln!(code, "];").unwrap();
+
+        // GOTO table, with a column for each nonterminal.
+        writeln!(
+            code,
+            "\nstatic GOTO: [[Option<usize>; {}]; {}] = [",
+            nonterminals.len(),
+            self.states.len()
+        )
+        .unwrap();
+        for state in &self.states {
+            let row: Vec<_> = nonterminals
+                .iter()
+                .map(|n| match state.goto.get(n) {
+                    Some(dest) => format!("Some({})", dest),
+                    None => "None".to_string(),
+                })
+                .collect();
+            writeln!(code, "    [{}],", row.join(", ")).unwrap();
+        }
+        writeln!(code, "];").unwrap();
+
+        // Nonterminal and body length of each production.
+        writeln!(
+            code,
+            "\nstatic PRODUCTIONS: [(usize, usize); {}] = [",
+            productions.len()
+        )
+        .unwrap();
+        for (lhs, rhs) in &productions {
+            writeln!(code, "    ({}, {}),", nonterminal_idx(lhs), rhs.body.len()).unwrap();
+        }
+        writeln!(code, "];").unwrap();
+
+        code.push_str(DRIVER);
+        code
+    }
+}
+
+/// Driver loop of the generated parser.
+const DRIVER: &str = r#"
+pub fn parse<V, I, F>(input: I, mut reduce: F) -> Result<V, Option<usize>>
+where
+    I: IntoIterator<Item = (usize, V)>,
+    F: FnMut(usize, Vec<V>) -> V,
+{
+    let mut input = input.into_iter().peekable();
+
+    let mut states = vec![INITIAL];
+    let mut values: Vec<V> = Vec::new();
+
+    loop {
+        let state = *states.last().unwrap();
+        let terminal = match input.peek() {
+            Some(&(terminal, _)) => terminal,
+            None => ENDMARKER,
+        };
+
+        match ACTION[state][terminal] {
+            Action::Shift(dest) => {
+                states.push(dest);
+                values.push(input.next().unwrap().1);
+            }
+            Action::Reduce(production) => {
+                let (lhs, len) = PRODUCTIONS[production];
+                states.truncate(states.len() - len);
+                let children = values.split_off(values.len() - len);
+
+                let top = *states.last().unwrap();
+                states.push(GOTO[top][lhs].unwrap());
+                values.push(reduce(production, children));
+            }
+            Action::Accept => return Ok(values.pop().unwrap()),
+            Action::Error => return Err(input.next().map(|(terminal, _)| terminal)),
+        }
+    }
+}
+"#;
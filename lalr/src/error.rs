@@ -4,6 +4,10 @@ pub enum Error {
     NoStartRule,
     #[error("nonterminal in right-hand side does not exist")]
     InvalidNonterminal,
+    #[error("nonterminal already has productions")]
+    NonterminalExists,
+    #[error("invalid grammar text: {0}")]
+    InvalidText(String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -1,25 +1,38 @@
 use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
 
 use crate::error::{Error, Result};
 
 #[derive(Debug, Clone)]
 pub struct Grammar<T, N, A> {
     pub rules: BTreeMap<N, Vec<Rhs<T, N, A>>>,
+    /// The starting nonterminal. Parse tables are constructed from the first production of this
+    /// nonterminal, which is taken to be the augmenting production `S' -> S` and is accepted
+    /// rather than reduced; see [`Grammar::augment`].
     pub start: N,
 }
 
 pub type GrammarNoop<T, N> = Grammar<T, N, ()>;
 
+/// A grammar whose terminals and nonterminals are strings, for quick experiments without defining
+/// symbol types. See [`Grammar::from_productions`].
+pub type StringGrammar = GrammarNoop<String, String>;
+
 #[derive(Debug, Clone)]
 pub struct Rhs<T, N, A> {
     pub body: Vec<Symbol<T, N>>,
+    /// Data associated with the production. To compute semantic values with
+    /// [`LR1Parser::evaluate`](crate::parser::LR1Parser::evaluate), this is the semantic action,
+    /// an `Fn(Vec<V>) -> V` that computes the value of the nonterminal from the values of the
+    /// symbols in the body.
     pub assoc: A,
 }
 
 comparators!(Rhs(T, N, A), (T, N), (body));
 
 impl<T, N, A> Rhs<T, N, A> {
+    /// Create a production with the given body and associated data, such as a semantic action.
     #[inline]
     pub fn new(body: Vec<Symbol<T, N>>, assoc: A) -> Self {
         Self { body, assoc }
@@ -27,6 +40,7 @@ impl<T, N, A> Rhs<T, N, A> {
 }
 
 impl<T, N> Rhs<T, N, ()> {
+    /// Create a production with the given body and no associated data.
     #[inline]
     pub fn noop(body: Vec<Symbol<T, N>>) -> Self {
         Self { body, assoc: () }
@@ -39,6 +53,71 @@ pub enum Symbol<T, N> {
     Nonterminal(N),
 }
 
+impl<T, N> fmt::Display for Symbol<T, N>
+where
+    T: fmt::Display,
+    N: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Symbol::Terminal(t) => t.fmt(f),
+            Symbol::Nonterminal(n) => n.fmt(f),
+        }
+    }
+}
+
+/// A terminal that may follow a point of a derivation: either a terminal of the grammar, or the
+/// endmarker `$`, which stands for the end of the input and follows the starting nonterminal.
+///
+/// The endmarker is not a symbol of any production. Where lookaheads are given as `Option<&T>`,
+/// such as in [`LR1State::set_action`](crate::lr1::LR1State::set_action) and in conflicts,
+/// [`None`] is the endmarker; this type converts from and into that representation.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Lookahead<'g, T> {
+    Terminal(&'g T),
+    EndMarker,
+}
+
+impl<'g, T> Lookahead<'g, T> {
+    /// Return the terminal, or [`None`] for the endmarker.
+    #[inline]
+    pub fn terminal(self) -> Option<&'g T> {
+        match self {
+            Lookahead::Terminal(t) => Some(t),
+            Lookahead::EndMarker => None,
+        }
+    }
+
+    #[inline]
+    pub fn is_endmarker(&self) -> bool {
+        matches!(self, Lookahead::EndMarker)
+    }
+}
+
+impl<'g, T> From<Option<&'g T>> for Lookahead<'g, T> {
+    #[inline]
+    fn from(t: Option<&'g T>) -> Self {
+        match t {
+            Some(t) => Lookahead::Terminal(t),
+            None => Lookahead::EndMarker,
+        }
+    }
+}
+
+impl<'g, T> fmt::Display for Lookahead<'g, T>
+where
+    T: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Lookahead::Terminal(t) => t.fmt(f),
+            Lookahead::EndMarker => write!(f, "$"),
+        }
+    }
+}
+
 /// Map of FIRST sets for the nonterminals in a grammar. The boolean flag indicates whether or not
 /// the FIRST set contains ε.
 pub type FirstSets<'g, T, N> = BTreeMap<&'g N, (BTreeSet<&'g T>, bool)>;
@@ -58,7 +137,8 @@ where
     /// nonterminals that have no rules of their own.
     #[inline]
     pub fn new(start: N, rules: BTreeMap<N, Vec<Rhs<T, N, A>>>) -> Result<Self> {
-        // Check that all nonterminals used in rule bodies have their own rules.
+        // Check that all nonterminals used in rule bodies have their own rules, so that table
+        // construction never looks up a nonterminal without an entry.
         // Vectors of Rhs may be empty to indicate A -> e.
         if !rules.iter().any(|(n, _)| *n == start) {
             Err(Error::NoStartRule)
@@ -78,21 +158,254 @@ where
     }
 }
 
+/// Builder for a [`Grammar`] that accumulates productions one at a time.
+///
+/// ```
+/// use lalr::{GrammarBuilder, Rhs, Symbol::{Nonterminal as NT, Terminal as TT}};
+///
+/// // S -> x S | y
+/// let grammar = GrammarBuilder::new()
+///     .rule("S", Rhs::noop(vec![TT("x"), NT("S")]))
+///     .rule("S", Rhs::noop(vec![TT("y")]))
+///     .build("S")
+///     .unwrap();
+/// assert_eq!(2, grammar.rules["S"].len());
+/// ```
+#[derive(Debug, Clone)]
+pub struct GrammarBuilder<T, N, A> {
+    rules: BTreeMap<N, Vec<Rhs<T, N, A>>>,
+}
+
+impl<T, N, A> Default for GrammarBuilder<T, N, A>
+where
+    N: Ord,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, N, A> GrammarBuilder<T, N, A>
+where
+    N: Ord,
+{
+    /// Create a builder with no productions.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            rules: BTreeMap::new(),
+        }
+    }
+
+    /// Add a production for `lhs`. The productions of each nonterminal keep the order in which
+    /// they are added.
+    #[inline]
+    pub fn rule(mut self, lhs: N, rhs: Rhs<T, N, A>) -> Self {
+        self.rules.entry(lhs).or_default().push(rhs);
+        self
+    }
+}
+
+impl<T, N, A> GrammarBuilder<T, N, A>
+where
+    T: PartialEq,
+    N: Ord,
+{
+    /// Create the grammar with the given starting nonterminal from the added productions.
+    ///
+    /// Returns [`Err`] under the same conditions as [`Grammar::new`].
+    #[inline]
+    pub fn build(self, start: N) -> Result<Grammar<T, N, A>> {
+        Grammar::new(start, self.rules)
+    }
+}
+
+impl StringGrammar {
+    /// Create a grammar from string literals. Each production is given as its nonterminal and
+    /// its body, in which each symbol is tagged as a terminal or a nonterminal. The productions of
+    /// each nonterminal keep their order, and an empty body is an ε-production.
+    ///
+    /// Returns [`Err`] under the same conditions as [`Grammar::new`].
+    ///
+    /// ```
+    /// use lalr::{StringGrammar, Symbol::{Nonterminal as NT, Terminal as TT}};
+    ///
+    /// // E -> S
+    /// // S -> C C
+    /// // C -> x C | y
+    /// let grammar = StringGrammar::from_productions(
+    ///     "E",
+    ///     &[
+    ///         ("E", &[NT("S")]),
+    ///         ("S", &[NT("C"), NT("C")]),
+    ///         ("C", &[TT("x"), NT("C")]),
+    ///         ("C", &[TT("y")]),
+    ///     ],
+    /// )
+    /// .unwrap();
+    /// assert_eq!(2, grammar.rules["C"].len());
+    ///
+    /// let table = grammar.slr1_table(&|_, _, _| 0).unwrap();
+    /// assert_eq!(7, table.states.len());
+    /// ```
+    #[inline]
+    pub fn from_productions(
+        start: &str,
+        productions: &[(&str, &[Symbol<&str, &str>])],
+    ) -> Result<Self> {
+        let mut builder = GrammarBuilder::new();
+        for (lhs, body) in productions {
+            let body = body
+                .iter()
+                .map(|sy| match sy {
+                    Symbol::Terminal(t) => Symbol::Terminal(t.to_string()),
+                    Symbol::Nonterminal(n) => Symbol::Nonterminal(n.to_string()),
+                })
+                .collect();
+            builder = builder.rule(lhs.to_string(), Rhs::noop(body));
+        }
+
+        builder.build(start.to_string())
+    }
+}
+
+impl<T, N, A> Grammar<T, N, A>
+where
+    T: Clone + PartialEq,
+    N: Clone + Ord,
+    A: Clone,
+{
+    /// Create the augmented grammar, which has a new starting nonterminal `start` with the single
+    /// production `start -> S`, where `S` is the current starting nonterminal. Because parse
+    /// tables are constructed from the first production of the starting nonterminal, a grammar
+    /// whose starting nonterminal has several productions, or appears in a production body, must
+    /// be augmented first.
+    ///
+    /// `start` must be a nonterminal that does not appear in the grammar, such as an extra
+    /// variant of the nonterminal type. The production is accepted rather than reduced when
+    /// parsing, so `assoc` is never used as a semantic action.
+    ///
+    /// Returns [`Err`] if `start` already has productions.
+    #[inline]
+    pub fn augment(&self, start: N, assoc: A) -> Result<Self> {
+        if self.rules.contains_key(&start) {
+            return Err(Error::NonterminalExists);
+        }
+
+        let mut rules = self.rules.clone();
+        let body = vec![Symbol::Nonterminal(self.start.clone())];
+        rules.insert(start.clone(), vec![Rhs::new(body, assoc)]);
+
+        Self::new(start, rules)
+    }
+
+    /// Create a copy of the grammar with a different starting nonterminal, e.g. to analyze the
+    /// language of an inner nonterminal. All analyses, such as FOLLOW sets and reachability, are
+    /// rooted at the new start. As with any grammar, `start` must be augmented (see
+    /// [`Grammar::augment`]) before constructing parse tables unless its first production is the
+    /// one to accept.
+    ///
+    /// Returns [`Err`] if `start` has no productions.
+    #[inline]
+    pub fn with_start(&self, start: N) -> Result<Self> {
+        Self::new(start, self.rules.clone())
+    }
+}
+
 impl<T, N, A> Grammar<T, N, A>
 where
     T: Ord,
     N: Ord,
 {
+    /// Return the productions of a nonterminal, or an empty slice if it has no rules.
+    #[inline]
+    pub fn productions_for(&self, n: &N) -> &[Rhs<T, N, A>] {
+        self.rules.get(n).map_or(&[], Vec::as_slice)
+    }
+
+    /// Return the set of terminals that appear in the bodies of the productions.
+    #[inline]
+    pub fn terminals(&self) -> BTreeSet<&T> {
+        self.symbols()
+            .filter_map(|sy| match sy {
+                Symbol::Terminal(t) => Some(t),
+                Symbol::Nonterminal(_) => None,
+            })
+            .collect()
+    }
+
+    /// Return the set of nonterminals that have rules or appear in the bodies of the productions,
+    /// including those that are never referenced.
+    #[inline]
+    pub fn nonterminals(&self) -> BTreeSet<&N> {
+        let referenced = self.symbols().filter_map(|sy| match sy {
+            Symbol::Terminal(_) => None,
+            Symbol::Nonterminal(n) => Some(n),
+        });
+        self.rules.keys().chain(referenced).collect()
+    }
+
+    /// Return the set of nonterminals with rules that cannot be derived from the starting
+    /// nonterminal. Their productions are never used by a parser for the grammar.
+    #[inline]
+    pub fn unreachable_nonterminals(&self) -> BTreeSet<&N> {
+        let mut reachable = BTreeSet::new();
+        reachable.insert(&self.start);
+
+        let mut stack = vec![&self.start];
+        while let Some(n) = stack.pop() {
+            let referenced = self
+                .productions_for(n)
+                .iter()
+                .flat_map(|rhs| &rhs.body)
+                .filter_map(|sy| match sy {
+                    Symbol::Terminal(_) => None,
+                    Symbol::Nonterminal(n) => Some(n),
+                });
+            for n in referenced {
+                if reachable.insert(n) {
+                    stack.push(n);
+                }
+            }
+        }
+
+        self.rules
+            .keys()
+            .filter(|n| !reachable.contains(n))
+            .collect()
+    }
+
+    /// Determine if the starting nonterminal derives the empty string, i.e. if a parser for the
+    /// grammar accepts empty input.
+    #[inline]
+    pub fn accepts_empty(&self) -> bool {
+        self.first_sets()[&self.start].1
+    }
+
+    /// Iterate through the symbols in the bodies of all productions.
+    #[inline]
+    fn symbols(&self) -> impl Iterator<Item = &Symbol<T, N>> {
+        self.rules.values().flatten().flat_map(|rhs| &rhs.body)
+    }
+
+    /// Compute the FOLLOW set of a nonterminal, including the endmarker if it may follow the
+    /// nonterminal.
+    #[inline]
+    pub fn follow(&self, n: &N) -> BTreeSet<Lookahead<'_, T>> {
+        self.analyze().follow(n)
+    }
+
     /// Compute FOLLOW sets for the nonterminals in the grammar.
     ///
     /// Partly from [`goffrie/lalr`](https://github.com/goffrie/lalr/blob/master/src/lib.rs).
     #[inline]
     pub fn follow_sets<'g>(
         &'g self,
-        first_sets: Option<&'g FirstSets<'g, T, N>>,
+        first_sets: Option<&FirstSets<'g, T, N>>,
     ) -> FollowSets<'g, T, N> {
         // Compute the FIRST sets if they're not given.
-        let first_sets: Cow<'g, _> = match first_sets {
+        let first_sets: Cow<'_, _> = match first_sets {
             Some(sets) => Cow::Borrowed(sets),
             None => {
                 let sets = self.first_sets();
@@ -191,9 +504,8 @@ where
             for (lhs, rhs_set) in &self.rules {
                 let mut first = map.get(lhs).unwrap().clone();
                 for rhs in rhs_set {
-                    if rhs.body.is_empty() {
-                        first.1 = true
-                    }
+                    // Whether or not every symbol seen so far derives ε; A -> ε trivially does.
+                    let mut nullable = true;
 
                     // For A -> X1 X2 X3 X4 ..., add FIRST(X1) to FIRST(A).
                     // Add FIRST(X2) if FIRST(X1) contains ε, and so on.
@@ -204,6 +516,7 @@ where
                                 if first.0.insert(t) {
                                     changed = true;
                                 }
+                                nullable = false;
                                 break 'inner;
                             }
                             Symbol::Nonterminal(ref n) => {
@@ -215,11 +528,18 @@ where
                                 }
 
                                 if !n_first.1 {
+                                    nullable = false;
                                     break 'inner;
                                 }
                             }
                         }
                     }
+
+                    // If every symbol in the body derives ε, add ε to FIRST(A).
+                    if nullable && !first.1 {
+                        first.1 = true;
+                        changed = true;
+                    }
                 }
                 map.insert(lhs, first);
             }
@@ -227,12 +547,92 @@ where
 
         map
     }
+
+    /// Compute FIRST(βa) for the sequence of symbols β followed by the terminal a, which is the
+    /// endmarker if `lookahead` is [`None`]. The lookahead is included in the set only if every
+    /// symbol in β derives ε.
+    #[inline]
+    pub fn first_of<'g>(
+        &'g self,
+        symbols: &'g [Symbol<T, N>],
+        lookahead: Option<&'g T>,
+        first_sets: &FirstSets<'g, T, N>,
+    ) -> BTreeSet<Option<&'g T>> {
+        let mut first_set = BTreeSet::new();
+
+        // Add to FIRST set until the current symbol's FIRST set is not nullable.
+        for sy in symbols {
+            match sy {
+                // FIRST(t) where t is a terminal is never nullable, add to set and stop.
+                Symbol::Terminal(t) => {
+                    first_set.insert(Some(t));
+                    return first_set;
+                }
+                // Add the terminals of FIRST(n) of the nonterminal n.
+                Symbol::Nonterminal(n) => {
+                    let (sy_first, sy_nullable) = &first_sets[n];
+                    first_set.extend(sy_first.iter().copied().map(Some));
+                    if !sy_nullable {
+                        return first_set;
+                    }
+                }
+            }
+        }
+
+        // All of β was nullable, so consider the lookahead a.
+        first_set.insert(lookahead);
+        first_set
+    }
+
+    /// Determine if the grammar is LL(1): for each nonterminal A, the alternatives of A have
+    /// disjoint FIRST sets, at most one of them derives ε, and if one does, the FIRST sets of the
+    /// others are disjoint from FOLLOW(A).
+    #[inline]
+    pub fn is_ll1(&self) -> bool {
+        let first_sets = self.first_sets();
+        let follow_sets = self.follow_sets(Some(&first_sets));
+
+        self.rules.iter().all(|(lhs, rhs_set)| {
+            let (follow, follow_endmarker) = &follow_sets[lhs];
+
+            // Lookaheads that select some alternative so far, with None as the endmarker.
+            let mut selected = BTreeSet::new();
+            let mut any_nullable = false;
+
+            for rhs in rhs_set {
+                // FIRST of the body, which contains None if the body is nullable.
+                let mut first = self.first_of(&rhs.body, None, &first_sets);
+                let nullable = first.remove(&None);
+
+                let mut lookaheads: Vec<_> = first.into_iter().collect();
+                if nullable {
+                    if any_nullable {
+                        return false;
+                    }
+                    any_nullable = true;
+
+                    // A nullable alternative is also selected by the terminals in FOLLOW(A).
+                    lookaheads.extend(follow.iter().copied().map(Some));
+                    if *follow_endmarker {
+                        lookaheads.push(None);
+                    }
+                }
+
+                if !lookaheads.into_iter().all(|t| selected.insert(t)) {
+                    return false;
+                }
+            }
+
+            true
+        })
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::{
+        lr1::LR1Action,
         Grammar, Rhs,
         Symbol::{Nonterminal as NT, Terminal as TT},
     };
@@ -262,6 +662,28 @@ mod test {
         assert_eq!(expected, follow_sets);
     }
 
+    #[test]
+    fn test_follow_endmarker() {
+        let GrammarUtil { grammar, .. } = create_grammar();
+
+        // The endmarker follows the starting nonterminal.
+        let start_follow = grammar.follow(&grammar.start);
+        assert!(start_follow.contains(&Lookahead::EndMarker));
+        assert_eq!(Some(&Lookahead::EndMarker), start_follow.iter().last());
+
+        let follow = grammar.follow(&F);
+        let expected: BTreeSet<_> = [Plus, Times, RightParen]
+            .iter()
+            .map(Lookahead::Terminal)
+            .chain(Some(Lookahead::EndMarker))
+            .collect();
+        assert_eq!(expected, follow);
+
+        assert_eq!(None, Lookahead::<Terminal>::EndMarker.terminal());
+        assert_eq!(Lookahead::EndMarker, Lookahead::<Terminal>::from(None));
+        assert_eq!(Some(&Plus), Lookahead::from(Some(&Plus)).terminal());
+    }
+
     #[test]
     fn test_first_sets() {
         let GrammarUtil { grammar, .. } = create_grammar();
@@ -279,8 +701,256 @@ mod test {
         assert_eq!(expected, first_sets);
     }
 
+    #[test]
+    fn test_first_sets_nullable_body() {
+        let mut rules = BTreeMap::new();
+
+        // D -> E U
+        // E -> ε
+        // U -> * F
+        //    | ε
+        // F -> id
+        rules.insert(D, vec![Rhs::noop(vec![NT(E), NT(U)])]);
+        rules.insert(E, vec![Rhs::noop(vec![])]);
+        rules.insert(
+            U,
+            vec![Rhs::noop(vec![TT(Times), NT(F)]), Rhs::noop(vec![])],
+        );
+        rules.insert(F, vec![Rhs::noop(vec![TT(Id)])]);
+
+        let grammar = Grammar::new(D, rules).unwrap();
+        let first_sets = grammar.first_sets();
+
+        // D is nullable because both E and U are.
+        assert_eq!(([Times].iter().collect(), true), first_sets[&D]);
+        assert_eq!((BTreeSet::new(), true), first_sets[&E]);
+
+        let follow_sets = grammar.follow_sets(Some(&first_sets));
+        assert_eq!(([Times].iter().collect(), true), follow_sets[&E]);
+    }
+
+    #[test]
+    fn test_new_missing_production() {
+        let mut rules = BTreeMap::new();
+
+        // D -> T E
+        // T -> id
+        // (E has no productions)
+        rules.insert(D, vec![Rhs::noop(vec![NT(T), NT(E)])]);
+        rules.insert(T, vec![Rhs::noop(vec![TT(Id)])]);
+
+        let grammar = Grammar::new(D, rules);
+        assert!(matches!(grammar, Err(Error::InvalidNonterminal)));
+    }
+
+    #[test]
+    fn test_new_no_start_rule() {
+        let mut rules = BTreeMap::new();
+
+        // T -> id
+        rules.insert(T, vec![Rhs::noop(vec![TT(Id)])]);
+
+        let grammar = Grammar::new(D, rules);
+        assert!(matches!(grammar, Err(Error::NoStartRule)));
+    }
+
+    #[test]
+    fn test_builder() {
+        let GrammarUtil { grammar: expected } = create_grammar();
+
+        let grammar = GrammarBuilder::new()
+            .rule(D, Rhs::noop(vec![NT(T), NT(E)]))
+            .rule(E, Rhs::noop(vec![TT(Plus), NT(T), NT(E)]))
+            .rule(E, Rhs::noop(vec![]))
+            .rule(T, Rhs::noop(vec![NT(F), NT(U)]))
+            .rule(U, Rhs::noop(vec![TT(Times), NT(F), NT(U)]))
+            .rule(U, Rhs::noop(vec![]))
+            .rule(F, Rhs::noop(vec![TT(LeftParen), NT(D), TT(RightParen)]))
+            .rule(F, Rhs::noop(vec![TT(Id)]))
+            .build(D)
+            .unwrap();
+
+        assert_eq!(expected.start, grammar.start);
+        assert_eq!(expected.rules, grammar.rules);
+    }
+
+    #[test]
+    fn test_builder_missing_production() {
+        // D -> T E
+        // T -> id
+        // (E has no productions)
+        let grammar = GrammarBuilder::new()
+            .rule(D, Rhs::noop(vec![NT(T), NT(E)]))
+            .rule(T, Rhs::noop(vec![TT(Id)]))
+            .build(D);
+        assert!(matches!(grammar, Err(Error::InvalidNonterminal)));
+    }
+
+    #[test]
+    fn test_augment() {
+        let GrammarUtil { grammar, .. } = create_grammar();
+        let augmented = grammar.augment(Start, ()).unwrap();
+
+        // Exactly one production, Start -> D, is added.
+        let count = |g: &Grammar<_, _, _>| g.rules.values().map(Vec::len).sum::<usize>();
+        assert_eq!(count(&grammar) + 1, count(&augmented));
+        assert_eq!(Start, augmented.start);
+        assert_eq!(vec![Rhs::noop(vec![NT(D)])], augmented.rules[&Start]);
+
+        // The accept action is on the endmarker after D, completing Start -> D.
+        let table = augmented.lr1_table(&|_, _, _| 0).unwrap();
+        let accept_state = &table.states[table.states[table.initial].goto[&D]];
+        assert!(matches!(accept_state.endmarker, Some(LR1Action::Accept)));
+
+        let result = augmented.augment(D, ());
+        assert!(matches!(result, Err(Error::NonterminalExists)));
+    }
+
+    #[test]
+    fn test_with_start() {
+        let GrammarUtil { grammar, .. } = create_grammar();
+        let inner = grammar.with_start(F).unwrap();
+        assert_eq!(F, inner.start);
+        assert_eq!(grammar.rules, inner.rules);
+
+        // Only F is followed by the endmarker; D is only followed by the `)` that closes it.
+        let follow_sets = inner.follow_sets(None);
+        assert_eq!(([RightParen].iter().collect(), false), follow_sets[&D]);
+        assert_eq!(([RightParen].iter().collect(), false), follow_sets[&E]);
+        let plus_right_paren = ([Plus, RightParen].iter().collect(), false);
+        assert_eq!(plus_right_paren, follow_sets[&T]);
+        assert_eq!(plus_right_paren, follow_sets[&U]);
+        let f_follow = ([Plus, Times, RightParen].iter().collect(), true);
+        assert_eq!(f_follow, follow_sets[&F]);
+
+        // Augmenting and table construction are rooted at the new start.
+        let augmented = inner.augment(Start, ()).unwrap();
+        assert_eq!(vec![Rhs::noop(vec![NT(F)])], augmented.rules[&Start]);
+        assert!(augmented.lr1_table(&|_, _, _| 0).is_ok());
+
+        let result = grammar.with_start(Start);
+        assert!(matches!(result, Err(Error::NoStartRule)));
+    }
+
+    #[test]
+    fn test_productions_for() {
+        let GrammarUtil { grammar, .. } = create_grammar();
+        assert_eq!(grammar.rules[&E].as_slice(), grammar.productions_for(&E));
+
+        // Start has no rules in the unaugmented grammar.
+        assert!(grammar.productions_for(&Start).is_empty());
+
+        let grammar: Grammar<char, char, ()> =
+            Grammar::from_text("%start A\nA -> x B\nB ->").unwrap();
+        assert!(grammar.productions_for(&'B').is_empty());
+        assert!(grammar.productions_for(&'C').is_empty());
+    }
+
+    #[test]
+    fn test_symbols() {
+        let GrammarUtil { grammar, .. } = create_grammar();
+
+        let terminals: BTreeSet<_> = [Plus, Times, LeftParen, RightParen, Id].iter().collect();
+        assert_eq!(terminals, grammar.terminals());
+
+        let nonterminals: BTreeSet<_> = [D, E, T, U, F].iter().collect();
+        assert_eq!(nonterminals, grammar.nonterminals());
+
+        // Start is never referenced, but has a production.
+        let augmented = grammar.augment(Start, ()).unwrap();
+        assert!(augmented.nonterminals().contains(&Start));
+        assert_eq!(terminals, augmented.terminals());
+    }
+
+    #[test]
+    fn test_unreachable_nonterminals() {
+        let GrammarUtil { grammar, .. } = create_grammar();
+        assert!(grammar.unreachable_nonterminals().is_empty());
+
+        // The old starting nonterminal is reachable from the new one.
+        let augmented = grammar.augment(Start, ()).unwrap();
+        assert!(augmented.unreachable_nonterminals().is_empty());
+
+        // Only the new starting nonterminal is unreachable from the old one.
+        let mut rules = augmented.rules;
+        rules.remove(&Start);
+        rules.insert(Start, vec![Rhs::noop(vec![TT(Id)])]);
+        let grammar = Grammar::new(D, rules).unwrap();
+        let unreachable: BTreeSet<_> = [Start].iter().collect();
+        assert_eq!(unreachable, grammar.unreachable_nonterminals());
+    }
+
+    #[test]
+    fn test_accepts_empty() {
+        let GrammarUtil { grammar, .. } = create_grammar();
+        assert!(!grammar.accepts_empty());
+
+        // E -> + T E | ε
+        let grammar = Grammar::new(E, grammar.rules).unwrap();
+        assert!(grammar.accepts_empty());
+        assert!(grammar.augment(Start, ()).unwrap().accepts_empty());
+    }
+
+    #[test]
+    fn test_first_of() {
+        let GrammarUtil { grammar, .. } = create_grammar();
+        let first_sets = grammar.first_sets();
+        let first_of = |symbols, lookahead| grammar.first_of(symbols, lookahead, &first_sets);
+
+        // E and U are nullable, so FIRST(E U a) includes the lookahead a.
+        let nullable = [NT(E), NT(U)];
+        let expected: BTreeSet<_> = vec![Some(&Plus), Some(&Times), Some(&Id)]
+            .into_iter()
+            .collect();
+        assert_eq!(expected, first_of(&nullable, Some(&Id)));
+
+        let expected: BTreeSet<_> = vec![None, Some(&Plus), Some(&Times)].into_iter().collect();
+        assert_eq!(expected, first_of(&nullable, None));
+
+        // FIRST(E F a) stops at F, which is not nullable.
+        let not_nullable = [NT(E), NT(F), TT(Times)];
+        let expected: BTreeSet<_> = vec![Some(&Plus), Some(&LeftParen), Some(&Id)]
+            .into_iter()
+            .collect();
+        assert_eq!(expected, first_of(&not_nullable, Some(&RightParen)));
+
+        // FIRST(a) for an empty sequence is the lookahead alone.
+        let expected: BTreeSet<_> = vec![Some(&Id)].into_iter().collect();
+        assert_eq!(expected, first_of(&[], Some(&Id)));
+    }
+
+    #[test]
+    fn test_is_ll1() {
+        let GrammarUtil { grammar, .. } = create_grammar();
+        assert!(grammar.is_ll1());
+
+        // Left recursion: D -> D + T | T
+        let mut left_recursive = grammar.clone();
+        left_recursive.rules.insert(
+            D,
+            vec![
+                Rhs::noop(vec![NT(D), TT(Plus), NT(T)]),
+                Rhs::noop(vec![NT(T)]),
+            ],
+        );
+        assert!(!left_recursive.is_ll1());
+
+        // Two nullable alternatives: U -> * F U | ε | ε
+        let mut nullable = grammar.clone();
+        nullable.rules.get_mut(&U).unwrap().push(Rhs::noop(vec![]));
+        assert!(!nullable.is_ll1());
+
+        // D -> T E +, so that + is in both FIRST(+ T E) and FOLLOW(E)
+        let mut follow = grammar;
+        follow
+            .rules
+            .insert(D, vec![Rhs::noop(vec![NT(T), NT(E), TT(Plus)])]);
+        assert!(!follow.is_ll1());
+    }
+
     #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
     enum Nonterminal {
+        Start,
         D,
         E,
         T,
@@ -29,10 +29,18 @@ mod comparator {
     }
 }
 
+#[macro_use]
+mod macros;
+
+pub mod analysis;
+mod codegen;
 pub mod error;
 pub mod grammar;
 pub mod lr0;
 pub mod lr1;
+pub mod parser;
+mod text;
 
+pub use analysis::GrammarAnalysis;
 pub use error::{Error, Result};
 pub use grammar::*;
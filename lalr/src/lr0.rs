@@ -1,6 +1,7 @@
 use crate::{Grammar, Rhs, Symbol};
 
 use std::collections::{btree_set, BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
 use std::iter::FromIterator;
 
 use itertools::Itertools;
@@ -47,7 +48,7 @@ where
         let mut initial_set = LR0ItemSet::new();
         initial_set.insert(LR0Item {
             lhs: &self.start,
-            rhs: &self.rules.get(&self.start).unwrap()[0],
+            rhs: &self.productions_for(&self.start)[0],
             pos: 0,
         });
         self.lr0_closure(&mut initial_set);
@@ -110,17 +111,18 @@ where
         LR0Automaton { states, start: 0 }
     }
 
-    /// Compute the closure of items for the given item set.
-    ///
-    /// TODO: Find better, non-recursive way to write this?
+    /// Compute the closure of items for the given item set. Each item is expanded only once, so
+    /// the closure is finite even if the grammar is left-recursive, directly or through other
+    /// nonterminals.
     #[inline]
     pub fn lr0_closure<'g>(&'g self, set: &mut LR0ItemSet<'g, T, N, A>)
     where
         N: Ord,
         LR0Item<'g, T, N, A>: Ord,
     {
-        let mut added = LR0ItemSet::new();
-        for item in set.iter() {
+        // Items whose closures have not yet been added to the set.
+        let mut pending: Vec<_> = set.iter().cloned().collect();
+        while let Some(item) = pending.pop() {
             // Add each item B -> .y for each item A -> a.Bb
             let next_symbol = match item.next_symbol() {
                 Some(sy) => match sy {
@@ -130,26 +132,19 @@ where
                 None => continue,
             };
 
-            // Shouldn't panic as long as Grammar created with Grammar::new?
-            for production in self.rules.get(next_symbol).unwrap() {
+            for production in self.productions_for(next_symbol) {
                 let new_item = LR0Item {
                     lhs: next_symbol,
-                    rhs: &production,
+                    rhs: production,
                     pos: 0,
                 };
-                if new_item != *item {
-                    added.insert(new_item);
+                // Only new items need their closures computed, so that left-recursive
+                // productions are not expanded forever.
+                if set.insert(new_item.clone()) {
+                    pending.push(new_item);
                 }
             }
         }
-
-        if !added.is_empty() {
-            // Compute closure of items to be added to original set.
-            self.lr0_closure(&mut added);
-
-            // Post-order insertion of new items.
-            set.append(&mut added);
-        }
     }
 
     /// Compute the GOTO(I, X) where I is a set of items and X is a grammar symbol, returning the
@@ -214,6 +209,28 @@ impl<'g, T: 'g, N: 'g, A: 'g> LR0Item<'g, T, N, A> {
     }
 }
 
+impl<'g, T, N, A> fmt::Display for LR0Item<'g, T, N, A>
+where
+    T: fmt::Display,
+    N: fmt::Display,
+{
+    /// Write the production of the item with a dot at its position, e.g. `A -> a . B c`.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ->", self.lhs)?;
+        for (i, sy) in self.rhs.body.iter().enumerate() {
+            if i == self.pos {
+                write!(f, " .")?;
+            }
+            write!(f, " {}", sy)?;
+        }
+        if self.pos == self.rhs.body.len() {
+            write!(f, " .")?;
+        }
+        Ok(())
+    }
+}
+
 impl<'g, T: 'g, N: 'g, A: 'g> Clone for LR0Item<'g, T, N, A> {
     #[inline]
     fn clone(&self) -> Self {
@@ -342,6 +359,61 @@ mod test {
         assert_eq!(*initial_state.goto.get(&F).unwrap(), 3);
     }
 
+    #[test]
+    fn test_item_display() {
+        let rhs: Rhs<&str, &str, ()> = Rhs::noop(vec![TT("a"), NT("B"), TT("c")]);
+        let item = |pos| LR0Item {
+            lhs: &"A",
+            rhs: &rhs,
+            pos,
+        };
+
+        assert_eq!("A -> . a B c", item(0).to_string());
+        assert_eq!("A -> a . B c", item(1).to_string());
+        assert_eq!("A -> a B c .", item(3).to_string());
+
+        let empty: Rhs<&str, &str, ()> = Rhs::noop(vec![]);
+        let item = LR0Item {
+            lhs: &"A",
+            rhs: &empty,
+            pos: 0,
+        };
+        assert_eq!("A -> .", item.to_string());
+    }
+
+    #[test]
+    fn test_lr0_closure_left_recursion() {
+        // S -> A
+        // A -> B x | y
+        // B -> A z
+        let grammar: Grammar<&str, &str, ()> = Grammar::new(
+            "S",
+            rules! {
+                "S" => [NT("A")],
+                "A" => [NT("B"), TT("x")] | [TT("y")],
+                "B" => [NT("A"), TT("z")],
+            },
+        )
+        .unwrap();
+
+        // A and B are expanded once each, though each leads back to the other.
+        let mut set = LR0ItemSet::new();
+        set.insert(LR0Item {
+            lhs: &"S",
+            rhs: &grammar.rules["S"][0],
+            pos: 0,
+        });
+        grammar.lr0_closure(&mut set);
+
+        let items: Vec<_> = set.iter().map(|item| item.to_string()).collect();
+        assert_eq!(4, items.len());
+        for item in &["S -> . A", "A -> . B x", "A -> . y", "B -> . A z"] {
+            assert!(items.contains(&item.to_string()), "{} missing", item);
+        }
+
+        assert!(grammar.slr1_table(&|_, _, _| 0).is_ok());
+    }
+
     #[test]
     fn test_lr0_automaton() {
         let GrammarUtil { grammar, .. } = create_grammar();
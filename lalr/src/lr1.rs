@@ -1,17 +1,70 @@
+use crate::analysis::GrammarAnalysis;
 use crate::grammar::{FirstSets, Grammar, Rhs, Symbol};
+use crate::lr0::LR0Item;
 
 use std::cmp;
 use std::collections::{btree_set, BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
 use std::iter::FromIterator;
+use std::ptr;
 
 use itertools::Itertools;
 
+/// Cache of FIRST(β) for suffixes β of production bodies, keyed by the production and the position
+/// at which the suffix begins. The set contains [`None`] if β is nullable.
+type SuffixFirstSets<'g, T, N, A> = BTreeMap<(*const Rhs<T, N, A>, usize), BTreeSet<Option<&'g T>>>;
+
 #[derive(Debug)]
 pub struct LR1Table<'g, T: 'g, N: 'g, A: 'g> {
     pub states: Vec<LR1State<'g, T, N, A>>,
     pub initial: usize,
 }
 
+impl<'g, T, N, A> LR1Table<'g, T, N, A>
+where
+    T: Ord,
+    N: Ord,
+{
+    /// Return warnings for the unreachable nonterminals of `grammar`, which must be the grammar the
+    /// table was constructed from, and for the productions of reachable nonterminals that are
+    /// never reduced. The production of the starting nonterminal is accepted rather than reduced,
+    /// so it is never reported.
+    #[inline]
+    pub fn warnings(&self, grammar: &'g Grammar<T, N, A>) -> Vec<LR1Warning<'g, T, N, A>> {
+        let unreachable = grammar.unreachable_nonterminals();
+
+        let reduced: Vec<_> = self
+            .states
+            .iter()
+            .flat_map(|state| state.actions.values().chain(&state.endmarker))
+            .filter_map(|action| match action {
+                LR1Action::Reduce(_, rhs) => Some(*rhs),
+                _ => None,
+            })
+            .collect();
+        let accepted = grammar.productions_for(&grammar.start).first();
+
+        let mut warnings: Vec<_> = unreachable
+            .iter()
+            .map(|n| LR1Warning::UnreachableNonterminal(*n))
+            .collect();
+
+        let unused = grammar
+            .rules
+            .iter()
+            .filter(|(lhs, _)| !unreachable.contains(lhs))
+            .flat_map(|(lhs, rhs_set)| rhs_set.iter().map(move |rhs| (lhs, rhs)))
+            .filter(|(_, rhs)| {
+                !matches!(accepted, Some(accepted) if ptr::eq(*rhs, accepted))
+                    && !reduced.iter().any(|reduced| ptr::eq(*rhs, *reduced))
+            })
+            .map(|(lhs, rhs)| LR1Warning::UnusedProduction(lhs, rhs));
+        warnings.extend(unused);
+
+        warnings
+    }
+}
+
 /// State in an LR(1) automaton.
 #[derive(Debug)]
 pub struct LR1State<'g, T: 'g, N: 'g, A: 'g> {
@@ -45,14 +98,79 @@ pub enum LR1Conflict<'g, T: 'g, N: 'g, A: 'g> {
         shift: (Option<&'g T>, usize),
         /// Reduce rule involved in the conflict.
         reduce: (&'g N, &'g Rhs<T, N, A>),
+        /// Index of the state in which the conflict occurred, if known.
+        state: Option<usize>,
+        /// Items of the state in which the conflict occurred, without lookaheads, if known.
+        items: Option<Vec<LR0Item<'g, T, N, A>>>,
     },
     /// Reduce-reduce conflict
     ReduceReduce {
         r1: (&'g N, &'g Rhs<T, N, A>),
         r2: (&'g N, &'g Rhs<T, N, A>),
+        /// Index of the state in which the conflict occurred, if known.
+        state: Option<usize>,
+        /// Items of the state in which the conflict occurred, without lookaheads, if known.
+        items: Option<Vec<LR0Item<'g, T, N, A>>>,
     },
 }
 
+impl<'g, T: 'g, N: 'g, A: 'g> LR1Conflict<'g, T, N, A> {
+    /// Return the index of the state in which the conflict occurred, or [`None`] if the conflict
+    /// was raised by [`LR1State::set_action`] without it.
+    #[inline]
+    pub fn state(&self) -> Option<usize> {
+        match self {
+            LR1Conflict::ShiftReduce { state, .. } | LR1Conflict::ReduceReduce { state, .. } => {
+                *state
+            }
+        }
+    }
+
+    /// Return the items of the state in which the conflict occurred, without lookaheads, or
+    /// [`None`] if the conflict was raised by [`LR1State::set_action`] without them.
+    #[inline]
+    pub fn items(&self) -> Option<&[LR0Item<'g, T, N, A>]> {
+        match self {
+            LR1Conflict::ShiftReduce { items, .. } | LR1Conflict::ReduceReduce { items, .. } => {
+                items.as_deref()
+            }
+        }
+    }
+}
+
+/// A likely mistake in a grammar, found by inspecting an LR(1) parse table constructed from it.
+#[derive(Debug, Clone)]
+pub enum LR1Warning<'g, T: 'g, N: 'g, A: 'g> {
+    /// A nonterminal that cannot be derived from the starting nonterminal, so none of its
+    /// productions are ever used.
+    UnreachableNonterminal(&'g N),
+    /// A production of a reachable nonterminal that is never reduced, e.g. because all of its
+    /// reduce actions were discarded in conflicts.
+    UnusedProduction(&'g N, &'g Rhs<T, N, A>),
+}
+
+/// How a parse table construction handles shift-reduce conflicts.
+///
+/// Some shift-reduce conflicts are expected, such as that of the dangling `else`, where shifting
+/// attaches the `else` to the nearest `if`. Like yacc, which shifts by default, a table may be
+/// constructed with such conflicts resolved, and the resolved conflicts reported for review.
+/// Reduce-reduce conflicts that priorities do not resolve are always errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Fail on the first shift-reduce conflict.
+    Error,
+    /// Resolve shift-reduce conflicts by shifting.
+    PreferShift,
+    /// Resolve shift-reduce conflicts by reducing.
+    PreferReduce,
+}
+
+/// An LR(1) parse table along with all conflicts encountered while constructing it.
+pub type LR1TableConflicts<'g, T, N, A> = (LR1Table<'g, T, N, A>, Vec<LR1Conflict<'g, T, N, A>>);
+
+/// The index and items of a state, recorded in the conflicts raised in it.
+type StateContext<'s, 'g, T, N, A> = Option<(usize, &'s [LR0Item<'g, T, N, A>])>;
+
 #[derive(Debug)]
 enum LR1ConflictResolution<'g, T: 'g, N: 'g, A: 'g> {
     Conflict(LR1Conflict<'g, T, N, A>),
@@ -64,7 +182,9 @@ impl<'g, T: 'g, N: 'g, A: 'g> LR1State<'g, T, N, A> {
     /// Insert an action for a symbol, returning an [`LR1Conflict`] error some action already
     /// exists for that symbol.
     ///
-    /// If `sy` is [`None`], it is interpreted as the endmarker terminal.
+    /// If `sy` is [`None`], it is interpreted as the endmarker terminal. The index and items of
+    /// this state are unknown, so the conflict records neither; use
+    /// [`set_action_in`](Self::set_action_in) to record them.
     #[inline]
     pub fn set_action<F>(
         &mut self,
@@ -76,51 +196,91 @@ impl<'g, T: 'g, N: 'g, A: 'g> LR1State<'g, T, N, A> {
         T: Ord,
         N: Ord,
         F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        self.insert_action(None, sy, action, priority_of)
+    }
+
+    /// Insert an action for a symbol, as [`set_action`](Self::set_action) does, where `state` and
+    /// `items` are the index and items of this state, which are recorded in any conflict.
+    #[inline]
+    pub fn set_action_in<F>(
+        &mut self,
+        state: usize,
+        items: &[LR0Item<'g, T, N, A>],
+        sy: Option<&'g T>,
+        action: LR1Action<'g, T, N, A>,
+        priority_of: &F,
+    ) -> Result<(), LR1Conflict<'g, T, N, A>>
+    where
+        T: Ord,
+        N: Ord,
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        self.insert_action(Some((state, items)), sy, action, priority_of)
+    }
+
+    /// Insert an action for a symbol, recording `context`, the index and items of this state, in
+    /// any conflict.
+    #[inline]
+    fn insert_action<F>(
+        &mut self,
+        context: StateContext<'_, 'g, T, N, A>,
+        sy: Option<&'g T>,
+        action: LR1Action<'g, T, N, A>,
+        priority_of: &F,
+    ) -> Result<(), LR1Conflict<'g, T, N, A>>
+    where
+        T: Ord,
+        N: Ord,
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        // Check for an existing action; if there is one, there is a conflict. The endmarker has
+        // its own slot, but is otherwise handled like any terminal.
+        let resolution = match self.action(sy) {
+            Some(existing) => Self::determine_conflict(context, existing, &action, sy, priority_of),
+            None => LR1ConflictResolution::Override,
+        };
+
+        match resolution {
+            LR1ConflictResolution::Conflict(conflict) => Err(conflict),
+            LR1ConflictResolution::Override => {
+                self.replace_action(sy, action);
+                Ok(())
+            }
+            LR1ConflictResolution::Keep => Ok(()),
+        }
+    }
+
+    /// Return the action for a lookahead, which is the endmarker terminal if [`None`] (see
+    /// [`Lookahead`](crate::Lookahead)).
+    #[inline]
+    pub fn action(&self, sy: Option<&T>) -> Option<&LR1Action<'g, T, N, A>>
+    where
+        T: Ord,
+    {
+        match sy {
+            Some(sy) => self.actions.get(sy),
+            None => self.endmarker.as_ref(),
+        }
+    }
+
+    /// Set the action for a lookahead, replacing any existing action.
+    #[inline]
+    fn replace_action(&mut self, sy: Option<&'g T>, action: LR1Action<'g, T, N, A>)
+    where
+        T: Ord,
     {
         match sy {
             Some(sy) => {
-                // Check for existing action; if there is one, there is a conflict.
-                // If no existing, set the action.
-                match self.actions.get(sy) {
-                    Some(existing) => {
-                        // Only reduce-reduce and shift-reduce should occur.
-                        match Self::determine_conflict(existing, &action, Some(sy), priority_of) {
-                            LR1ConflictResolution::Conflict(conflict) => Err(conflict),
-                            LR1ConflictResolution::Override => {
-                                self.actions.insert(sy, action);
-                                Ok(())
-                            }
-                            LR1ConflictResolution::Keep => Ok(()),
-                        }
-                    }
-                    None => {
-                        self.actions.insert(sy, action);
-                        Ok(())
-                    }
-                }
+                self.actions.insert(sy, action);
             }
-            // sy is endmarker terminal.
-            None => match &self.endmarker {
-                Some(existing) => {
-                    match Self::determine_conflict(&existing, &action, None, priority_of) {
-                        LR1ConflictResolution::Conflict(conflict) => Err(conflict),
-                        LR1ConflictResolution::Override => {
-                            self.endmarker = Some(action);
-                            Ok(())
-                        }
-                        LR1ConflictResolution::Keep => Ok(()),
-                    }
-                }
-                None => {
-                    self.endmarker = Some(action);
-                    Ok(())
-                }
-            },
+            None => self.endmarker = Some(action),
         }
     }
 
     #[inline]
     fn determine_conflict<F>(
+        context: StateContext<'_, 'g, T, N, A>,
         a1: &LR1Action<'g, T, N, A>,
         a2: &LR1Action<'g, T, N, A>,
         sy: Option<&'g T>,
@@ -132,6 +292,8 @@ impl<'g, T: 'g, N: 'g, A: 'g> LR1State<'g, T, N, A> {
         F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
     {
         use LR1ConflictResolution::*;
+        let state = context.map(|(state, _)| state);
+        let items = || context.map(|(_, items)| items.to_vec());
         // TODO: check for same action; don't error on those
         match *a1 {
             LR1Action::Reduce(n1, rhs1) => match *a2 {
@@ -148,6 +310,8 @@ impl<'g, T: 'g, N: 'g, A: 'g> LR1State<'g, T, N, A> {
                             cmp::Ordering::Equal => Conflict(LR1Conflict::ReduceReduce {
                                 r1: (n1, rhs1),
                                 r2: (n2, rhs2),
+                                state,
+                                items: items(),
                             }),
                         }
                     }
@@ -155,6 +319,8 @@ impl<'g, T: 'g, N: 'g, A: 'g> LR1State<'g, T, N, A> {
                 LR1Action::Shift(dest2) => Conflict(LR1Conflict::ShiftReduce {
                     shift: (sy, dest2),
                     reduce: (n1, rhs1),
+                    state,
+                    items: items(),
                 }),
                 _ => unreachable!(),
             },
@@ -162,6 +328,8 @@ impl<'g, T: 'g, N: 'g, A: 'g> LR1State<'g, T, N, A> {
                 LR1Action::Reduce(n2, rhs2) => Conflict(LR1Conflict::ShiftReduce {
                     shift: (sy, dest1),
                     reduce: (n2, rhs2),
+                    state,
+                    items: items(),
                 }),
                 _ => unreachable!(),
             },
@@ -183,6 +351,16 @@ pub struct LR1Item<'g, T: 'g, N: 'g, A: 'g> {
 comparators!(LR1Item('g, T, N, A), (T, N), (lhs, rhs, pos, lookahead));
 
 impl<'g, T: 'g, N: 'g, A: 'g> LR1Item<'g, T, N, A> {
+    /// Return the item without its lookahead.
+    #[inline]
+    pub fn core(&self) -> LR0Item<'g, T, N, A> {
+        LR0Item {
+            lhs: self.lhs,
+            rhs: self.rhs,
+            pos: self.pos,
+        }
+    }
+
     /// Retrieves B for A -> a.Bb, or None if A -> a.
     #[inline]
     pub fn next_symbol(&self) -> Option<&'g Symbol<T, N>> {
@@ -190,6 +368,22 @@ impl<'g, T: 'g, N: 'g, A: 'g> LR1Item<'g, T, N, A> {
     }
 }
 
+impl<'g, T, N, A> fmt::Display for LR1Item<'g, T, N, A>
+where
+    T: fmt::Display,
+    N: fmt::Display,
+{
+    /// Write the production of the item with a dot at its position, followed by its lookahead,
+    /// e.g. `A -> a . B c , d`. The endmarker lookahead is written as `$`.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.lookahead {
+            Some(t) => write!(f, "{} , {}", self.core(), t),
+            None => write!(f, "{} , $", self.core()),
+        }
+    }
+}
+
 impl<'g, T: 'g, N: 'g, A: 'g> Clone for LR1Item<'g, T, N, A> {
     #[inline]
     fn clone(&self) -> Self {
@@ -358,6 +552,8 @@ where
     T: Ord,
     N: Ord,
 {
+    /// Construct an LALR(1) parse table for the grammar by merging the states of its LR(1)
+    /// automaton that have the same cores.
     #[inline]
     pub fn lalr1_table_by_lr1<'g, F>(
         &'g self,
@@ -366,109 +562,25 @@ where
     where
         F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
     {
-        // Construct C = the collection of sets of LR(1) items.
-        let lr1_automaton = self.lr1_automaton();
-        let lr1_states = &lr1_automaton.states;
-
-        // For each core present among the set of LR(1) items, find all sets having that core, and
-        // replace these sets by their union.
-
-        // Vector of LR(1) states and the set of indexes to the original LR(1) item sets in
-        // lr1_states.
-        let mut states: Vec<(LR1State<'g, T, N, A>, BTreeSet<usize>)> = Vec::new();
-        // Mapping of item core sets to indexes in the LR(1) state vector.
-        let mut union_map: BTreeMap<LR1ItemCoreSet<'g, T, N, A>, usize> = BTreeMap::new();
-        // Mapping of original LR(1) item set indexes (in lr1_states) to new LR(1) merged states
-        // indexes (in states).
-        let mut state_mapping = BTreeMap::new();
-
-        // Group indexes of duplicates (by item cores).
-        for (i, state) in lr1_states.iter().enumerate() {
-            let items = &state.items;
-
-            // Check for item core set in map.
-            let item_cores: LR1ItemCoreSet<'g, T, N, A> = items.clone().into();
-            match union_map.get(&item_cores) {
-                // If already exists, then
-                Some(state_idx) => {
-                    let (_, indexes) = states.get_mut(*state_idx).unwrap();
-                    indexes.insert(i);
-                    state_mapping.insert(i, *state_idx);
-                }
-                None => {
-                    let new_state = LR1State {
-                        actions: BTreeMap::new(),
-                        endmarker: None,
-                        goto: BTreeMap::new(),
-                    };
-                    let mut indexes = BTreeSet::new();
-                    indexes.insert(i);
-
-                    states.push((new_state, indexes));
-
-                    let state_idx = states.len() - 1;
-                    union_map.insert(item_cores, state_idx);
-                    state_mapping.insert(i, state_idx);
-                }
-            };
-        }
-
-        // item_cores : the LR(1) item core sets
-        // state_idx  : index of corresponding new LR(1) state in `states`
-        // state      : LR(1) state in `states` at index `state_idx`
-        // indexes    : vector of indexes to original LR(1) item sets in `lr1_states`
-        for (state, indexes) in states.iter_mut() {
-            // Collect the associated automaton states.
-            let associated_states: Vec<_> = indexes
-                .iter()
-                .map(|idx| lr1_states.get(*idx).unwrap())
-                .collect();
-
-            // Union the item sets for the current core/new LR(1) state.
-            let item_union: BTreeSet<_> = associated_states
-                .iter()
-                .flat_map(|assoc| assoc.items.clone())
-                .collect();
-
-            let transitions: BTreeMap<_, _> = associated_states
-                .iter()
-                .flat_map(|assoc| &assoc.transitions)
-                .collect();
-
-            for (sy, dest) in transitions {
-                let new_dest = state_mapping.get(dest).unwrap();
-                match *sy {
-                    Symbol::Terminal(ref t) => {
-                        state.set_action(Some(t), LR1Action::Shift(*new_dest), priority_of)?;
-                    }
-                    Symbol::Nonterminal(ref n) => {
-                        state.goto.insert(n, *new_dest);
-                    }
-                }
-            }
-
-            for item in item_union {
-                if item.pos == item.rhs.body.len() {
-                    if *item.lhs != self.start {
-                        state.set_action(
-                            item.lookahead,
-                            LR1Action::Reduce(item.lhs, item.rhs),
-                            priority_of,
-                        )?;
-                    } else if item.lookahead.is_none() {
-                        state.set_action(None, LR1Action::Accept, priority_of)?;
-                    }
-                }
-            }
-        }
+        self.analyze().lalr1_table_by_lr1(priority_of)
+    }
 
-        Ok(LR1Table {
-            states: states.into_iter().map(|x| x.0).collect(),
-            // TODO: Not sure if this is actually correct
-            initial: *state_mapping.get(&lr1_automaton.start).unwrap(),
-        })
+    /// Like [`Grammar::lalr1_table_by_lr1`], but construction continues past conflicts and all of
+    /// them are returned along with the table. A conflicting action is discarded in favour of the
+    /// action already set; since shift actions are set before reduce actions, shift-reduce
+    /// conflicts are resolved as shifts.
+    #[inline]
+    pub fn lalr1_table_by_lr1_verbose<'g, F>(
+        &'g self,
+        priority_of: &F,
+    ) -> LR1TableConflicts<'g, T, N, A>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        self.analyze().lalr1_table_by_lr1_verbose(priority_of)
     }
 
+    /// Construct a canonical LR(1) parse table for the grammar.
     #[inline]
     pub fn lr1_table<'g, F>(
         &'g self,
@@ -477,70 +589,41 @@ where
     where
         F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
     {
-        let lr1_automaton = self.lr1_automaton();
-
-        let mut states = Vec::new();
-
-        for automaton_state in lr1_automaton.states {
-            let mut lr1_state = LR1State {
-                actions: BTreeMap::new(),
-                endmarker: None,
-                goto: BTreeMap::new(),
-            };
-
-            for (sy, dest) in automaton_state.transitions {
-                match *sy {
-                    // If [A -> α·aβ, b] is in I_i and GOTO(I_i, a) = I_j and a is a terminal, then
-                    // set ACTION[i, a] to "shift j".
-                    Symbol::Terminal(ref t) => {
-                        lr1_state.set_action(Some(t), LR1Action::Shift(dest), priority_of)?;
-                    }
-                    // If GOTO(I_i, A) = I_j, then GOTO[i, A] = j.
-                    Symbol::Nonterminal(ref n) => {
-                        lr1_state.goto.insert(n, dest);
-                    }
-                }
-            }
-
-            for item in automaton_state.items {
-                // If [A -> α·, a] is in I_i, A != S', then set ACTION[i, a] to "reduce A ->
-                // α".
-                if item.pos == item.rhs.body.len() {
-                    if *item.lhs != self.start {
-                        lr1_state.set_action(
-                            item.lookahead,
-                            LR1Action::Reduce(item.lhs, item.rhs),
-                            priority_of,
-                        )?;
-                    } else if item.lookahead.is_none() {
-                        // If [S' -> S·, $] is in I_i, then set ACTION[i, $] to "accept".
-                        lr1_state.set_action(None, LR1Action::Accept, priority_of)?;
-                    }
-                }
-            }
-
-            states.push(lr1_state);
-        }
+        self.analyze().lr1_table(priority_of)
+    }
 
-        Ok(LR1Table {
-            states,
-            initial: lr1_automaton.start,
-        })
+    /// Like [`Grammar::lr1_table`], but all conflicts are returned along with the table. Conflicts
+    /// are resolved as in [`Grammar::lalr1_table_by_lr1_verbose`].
+    #[inline]
+    pub fn lr1_table_verbose<'g, F>(&'g self, priority_of: &F) -> LR1TableConflicts<'g, T, N, A>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        self.analyze().lr1_table_verbose(priority_of)
     }
 
     #[inline]
     pub fn lr1_automaton(&self) -> LR1Automaton<'_, T, N, A> {
+        self.lr1_automaton_from(&self.first_sets())
+    }
+
+    /// Compute the LR(1) automaton from the FIRST sets of the grammar.
+    #[inline]
+    pub(crate) fn lr1_automaton_from<'g>(
+        &'g self,
+        first_sets: &FirstSets<'g, T, N>,
+    ) -> LR1Automaton<'g, T, N, A> {
         // Initialize item set to closure of {[S' -> S]}.
         let mut initial_set = LR1ItemSet::new();
         initial_set.insert(LR1Item {
             lhs: &self.start,
-            rhs: &self.rules.get(&self.start).unwrap()[0],
+            rhs: &self.productions_for(&self.start)[0],
             pos: 0,
             lookahead: None,
         });
 
-        let first_sets = self.first_sets();
-        self.lr1_closure(&mut initial_set, &first_sets);
+        let mut suffix_firsts = BTreeMap::new();
+        self.lr1_closure_cached(&mut initial_set, first_sets, &mut suffix_firsts);
 
         let initial_state = LR1AutomatonState {
             items: initial_set.clone(),
@@ -559,7 +642,8 @@ where
             let symbols = state.items.iter().flat_map(|item| &item.rhs.body).dedup();
             for sy in symbols {
                 // Compute GOTO(I, X)
-                let goto_closure = self.lr1_goto(&state.items, &sy, &first_sets);
+                let goto_closure =
+                    self.lr1_goto_cached(&state.items, sy, first_sets, &mut suffix_firsts);
                 if goto_closure.is_empty() {
                     continue;
                 }
@@ -596,6 +680,17 @@ where
         set: &LR1ItemSet<'g, T, N, A>,
         x: &'g Symbol<T, N>,
         first_sets: &FirstSets<'g, T, N>,
+    ) -> LR1ItemSet<'g, T, N, A> {
+        self.lr1_goto_cached(set, x, first_sets, &mut BTreeMap::new())
+    }
+
+    #[inline]
+    fn lr1_goto_cached<'g>(
+        &'g self,
+        set: &LR1ItemSet<'g, T, N, A>,
+        x: &'g Symbol<T, N>,
+        first_sets: &FirstSets<'g, T, N>,
+        suffix_firsts: &mut SuffixFirstSets<'g, T, N, A>,
     ) -> LR1ItemSet<'g, T, N, A> {
         let mut new_set = LR1ItemSet::new();
 
@@ -616,7 +711,7 @@ where
             });
         }
 
-        self.lr1_closure(&mut new_set, first_sets);
+        self.lr1_closure_cached(&mut new_set, first_sets, suffix_firsts);
         new_set
     }
 
@@ -627,233 +722,1090 @@ where
         set: &mut LR1ItemSet<'g, T, N, A>,
         first_sets: &FirstSets<'g, T, N>,
     ) {
-        let mut changed = true;
-        while changed {
-            changed = false;
-            let mut added = BTreeSet::new();
-            // For each item [A -> α·Bβ, a] in I where B is a nonterminal.
-            for item in set.iter() {
-                // Extract B.
-                let b = match item.next_symbol() {
-                    Some(sy) => match sy {
-                        Symbol::Nonterminal(ref n) => n,
-                        Symbol::Terminal(_) => continue,
-                    },
-                    None => continue,
-                };
-
-                // For each production B -> γ in G'.
-                let b_productions = self.rules.get(b).unwrap();
-
-                if !b_productions.is_empty() {
-                    // Compute FIRST(βa).
-                    let first_beta_a = {
-                        // Extract β (all symbols after B).
-                        let a = item.lookahead;
-                        let beta = &item.rhs.body[(item.pos + 1)..];
-
-                        let mut first_set = BTreeSet::new();
-                        let mut nullable = true;
-                        // Add to FIRST set until the current symbol's FIRST set is not nullable.
-                        for sy in beta {
-                            if !nullable {
-                                break;
-                            }
-
-                            match sy {
-                                // FIRST(t) where t is a terminal is never nullable, add to set and
-                                // stop.
-                                Symbol::Terminal(ref t) => {
-                                    first_set.insert(t);
-                                    nullable = false;
-                                }
-                                Symbol::Nonterminal(ref n) => {
-                                    // Get FIRST(n) of the nonterminal n and add its terminals to
-                                    // the total FIRST set.
-                                    let (sy_first, sy_nullable) = first_sets.get(n).unwrap();
-                                    first_set.extend(sy_first);
-                                    if !sy_nullable {
-                                        nullable = false;
-                                    }
-                                }
-                            }
-                        }
-
-                        let mut first_set: BTreeSet<_> = first_set.into_iter().map(Some).collect();
-
-                        // If all of β was nullable, consider the terminal a.
-                        if nullable {
-                            first_set.insert(a);
-                        }
+        self.lr1_closure_cached(set, first_sets, &mut BTreeMap::new())
+    }
 
-                        first_set
+    /// Compute the LR(1) closure set, reusing the FIRST sets of body suffixes computed for earlier
+    /// items, including those of earlier closures.
+    #[inline]
+    fn lr1_closure_cached<'g>(
+        &'g self,
+        set: &mut LR1ItemSet<'g, T, N, A>,
+        first_sets: &FirstSets<'g, T, N>,
+        suffix_firsts: &mut SuffixFirstSets<'g, T, N, A>,
+    ) {
+        // Only items that have not been expanded yet need to be considered.
+        let mut pending: Vec<_> = set.iter().cloned().collect();
+
+        // For each item [A -> α·Bβ, a] in I where B is a nonterminal.
+        while let Some(item) = pending.pop() {
+            // Extract B.
+            let b = match item.next_symbol() {
+                Some(Symbol::Nonterminal(n)) => n,
+                _ => continue,
+            };
+
+            // Compute FIRST(β), where β is all symbols after B. FIRST(βa) is the same, with a in
+            // place of ε.
+            let first_beta = suffix_firsts
+                .entry((item.rhs, item.pos + 1))
+                .or_insert_with(|| {
+                    self.first_of(&item.rhs.body[(item.pos + 1)..], None, first_sets)
+                });
+
+            // For each production B -> γ in G', and each terminal b in FIRST(βa), add
+            // [B -> ·γ, b] to set I.
+            for rhs in self.productions_for(b) {
+                for bt in first_beta.iter() {
+                    let new_item = LR1Item {
+                        lhs: b,
+                        rhs,
+                        pos: 0,
+                        lookahead: bt.or(item.lookahead),
+                    };
+
+                    if set.insert(new_item.clone()) {
+                        pending.push(new_item);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Construct an SLR(1) parse table for the grammar.
+    #[inline]
+    pub fn slr1_table<'g, F>(
+        &'g self,
+        priority_of: &F,
+    ) -> Result<LR1Table<'g, T, N, A>, LR1Conflict<'g, T, N, A>>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        self.analyze().slr1_table(priority_of)
+    }
+
+    /// Like [`Grammar::slr1_table`], but all conflicts are returned along with the table.
+    /// Conflicts are resolved as in [`Grammar::lalr1_table_by_lr1_verbose`].
+    #[inline]
+    pub fn slr1_table_verbose<'g, F>(&'g self, priority_of: &F) -> LR1TableConflicts<'g, T, N, A>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        self.analyze().slr1_table_verbose(priority_of)
+    }
+
+    /// Like [`Grammar::slr1_table`], but shift-reduce conflicts are resolved according to
+    /// `policy` rather than failing, unless it is [`ConflictPolicy::Error`]. The resolved
+    /// conflicts are returned along with the table, for review.
+    #[inline]
+    pub fn slr1_table_with_policy<'g, F>(
+        &'g self,
+        priority_of: &F,
+        policy: ConflictPolicy,
+    ) -> Result<LR1TableConflicts<'g, T, N, A>, LR1Conflict<'g, T, N, A>>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        self.analyze().slr1_table_with_policy(priority_of, policy)
+    }
+
+    /// Determine if the grammar is SLR(1), i.e. if [`Grammar::slr1_table`] constructs a table
+    /// without conflicts when no priorities are given.
+    #[inline]
+    pub fn is_slr1(&self) -> bool {
+        self.slr1_table(&|_, _, _| 0).is_ok()
+    }
+
+    /// Determine if the grammar is LALR(1), i.e. if [`Grammar::lalr1_table_by_lr1`] constructs a
+    /// table without conflicts when no priorities are given.
+    #[inline]
+    pub fn is_lalr1(&self) -> bool {
+        self.lalr1_table_by_lr1(&|_, _, _| 0).is_ok()
+    }
+
+    /// Determine if the grammar is LR(1), i.e. if [`Grammar::lr1_table`] constructs a table
+    /// without conflicts when no priorities are given.
+    #[inline]
+    pub fn is_lr1(&self) -> bool {
+        self.lr1_table(&|_, _, _| 0).is_ok()
+    }
+}
+
+/// Table constructions from the analyses of a grammar, which are shared between the tables
+/// constructed from the same analysis. Each is equivalent to the construction of the same name on
+/// [`Grammar`].
+impl<'g, T, N, A> GrammarAnalysis<'g, T, N, A>
+where
+    T: Ord,
+    N: Ord,
+{
+    /// See [`Grammar::lalr1_table_by_lr1`].
+    #[inline]
+    pub fn lalr1_table_by_lr1<F>(
+        &self,
+        priority_of: &F,
+    ) -> Result<LR1Table<'g, T, N, A>, LR1Conflict<'g, T, N, A>>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        self.lalr1_table_by_lr1_with(priority_of, &mut Err)
+    }
+
+    /// See [`Grammar::lalr1_table_by_lr1_verbose`].
+    #[inline]
+    pub fn lalr1_table_by_lr1_verbose<F>(&self, priority_of: &F) -> LR1TableConflicts<'g, T, N, A>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        let mut conflicts = Vec::new();
+        let table = self.lalr1_table_by_lr1_with(priority_of, &mut |conflict| {
+            conflicts.push(conflict);
+            Ok(())
+        });
+
+        match table {
+            Ok(table) => (table, with_reduce_conflicts(conflicts, priority_of)),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Construct the table, calling `on_conflict` for each conflict encountered. Construction
+    /// stops if `on_conflict` returns [`Err`].
+    #[inline]
+    fn lalr1_table_by_lr1_with<F, C>(
+        &self,
+        priority_of: &F,
+        on_conflict: &mut C,
+    ) -> Result<LR1Table<'g, T, N, A>, LR1Conflict<'g, T, N, A>>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+        C: FnMut(LR1Conflict<'g, T, N, A>) -> Result<(), LR1Conflict<'g, T, N, A>>,
+    {
+        // Construct C = the collection of sets of LR(1) items.
+        let lr1_automaton = self.lr1_automaton();
+        let grammar = self.grammar();
+        let lr1_states = &lr1_automaton.states;
+
+        // For each core present among the set of LR(1) items, find all sets having that core, and
+        // replace these sets by their union.
+
+        // Vector of LR(1) states and the set of indexes to the original LR(1) item sets in
+        // lr1_states.
+        let mut states: Vec<(LR1State<'g, T, N, A>, BTreeSet<usize>)> = Vec::new();
+        // Mapping of item core sets to indexes in the LR(1) state vector.
+        let mut union_map: BTreeMap<LR1ItemCoreSet<'g, T, N, A>, usize> = BTreeMap::new();
+        // Mapping of original LR(1) item set indexes (in lr1_states) to new LR(1) merged states
+        // indexes (in states).
+        let mut state_mapping = BTreeMap::new();
+
+        // Group indexes of duplicates (by item cores).
+        for (i, state) in lr1_states.iter().enumerate() {
+            let items = &state.items;
+
+            // Check for item core set in map.
+            let item_cores: LR1ItemCoreSet<'g, T, N, A> = items.clone().into();
+            match union_map.get(&item_cores) {
+                // If already exists, then
+                Some(state_idx) => {
+                    let (_, indexes) = states.get_mut(*state_idx).unwrap();
+                    indexes.insert(i);
+                    state_mapping.insert(i, *state_idx);
+                }
+                None => {
+                    let new_state = LR1State {
+                        actions: BTreeMap::new(),
+                        endmarker: None,
+                        goto: BTreeMap::new(),
                     };
+                    let mut indexes = BTreeSet::new();
+                    indexes.insert(i);
+
+                    states.push((new_state, indexes));
+
+                    let state_idx = states.len() - 1;
+                    union_map.insert(item_cores, state_idx);
+                    state_mapping.insert(i, state_idx);
+                }
+            };
+        }
+
+        // item_cores : the LR(1) item core sets
+        // state_idx  : index of corresponding new LR(1) state in `states`
+        // state      : LR(1) state in `states` at index `state_idx`
+        // indexes    : vector of indexes to original LR(1) item sets in `lr1_states`
+        for (i, (state, indexes)) in states.iter_mut().enumerate() {
+            // Collect the associated automaton states.
+            let associated_states: Vec<_> = indexes
+                .iter()
+                .map(|idx| lr1_states.get(*idx).unwrap())
+                .collect();
+
+            // Union the item sets for the current core/new LR(1) state.
+            let item_union: BTreeSet<_> = associated_states
+                .iter()
+                .flat_map(|assoc| assoc.items.clone())
+                .collect();
+            let cores: Vec<_> = item_union.iter().map(LR1Item::core).dedup().collect();
+
+            let transitions: BTreeMap<_, _> = associated_states
+                .iter()
+                .flat_map(|assoc| &assoc.transitions)
+                .collect();
+
+            for (sy, dest) in transitions {
+                let new_dest = state_mapping.get(dest).unwrap();
+                match *sy {
+                    Symbol::Terminal(ref t) => {
+                        state
+                            .set_action_in(
+                                i,
+                                &cores,
+                                Some(t),
+                                LR1Action::Shift(*new_dest),
+                                priority_of,
+                            )
+                            .or_else(&mut *on_conflict)?;
+                    }
+                    Symbol::Nonterminal(ref n) => {
+                        state.goto.insert(n, *new_dest);
+                    }
+                }
+            }
+
+            for item in item_union {
+                if item.pos == item.rhs.body.len() {
+                    if *item.lhs != grammar.start {
+                        state
+                            .set_action_in(
+                                i,
+                                &cores,
+                                item.lookahead,
+                                LR1Action::Reduce(item.lhs, item.rhs),
+                                priority_of,
+                            )
+                            .or_else(&mut *on_conflict)?;
+                    } else if item.lookahead.is_none() {
+                        state
+                            .set_action_in(i, &cores, None, LR1Action::Accept, priority_of)
+                            .or_else(&mut *on_conflict)?;
+                    }
+                }
+            }
+        }
+
+        Ok(LR1Table {
+            states: states.into_iter().map(|x| x.0).collect(),
+            // TODO: Not sure if this is actually correct
+            initial: *state_mapping.get(&lr1_automaton.start).unwrap(),
+        })
+    }
+
+    /// See [`Grammar::lr1_table`].
+    #[inline]
+    pub fn lr1_table<F>(
+        &self,
+        priority_of: &F,
+    ) -> Result<LR1Table<'g, T, N, A>, LR1Conflict<'g, T, N, A>>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        self.lr1_table_with(priority_of, &mut Err)
+    }
+
+    /// See [`Grammar::lr1_table_verbose`].
+    #[inline]
+    pub fn lr1_table_verbose<F>(&self, priority_of: &F) -> LR1TableConflicts<'g, T, N, A>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        let mut conflicts = Vec::new();
+        let table = self.lr1_table_with(priority_of, &mut |conflict| {
+            conflicts.push(conflict);
+            Ok(())
+        });
+
+        match table {
+            Ok(table) => (table, with_reduce_conflicts(conflicts, priority_of)),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn lr1_table_with<F, C>(
+        &self,
+        priority_of: &F,
+        on_conflict: &mut C,
+    ) -> Result<LR1Table<'g, T, N, A>, LR1Conflict<'g, T, N, A>>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+        C: FnMut(LR1Conflict<'g, T, N, A>) -> Result<(), LR1Conflict<'g, T, N, A>>,
+    {
+        let lr1_automaton = self.lr1_automaton();
+        let grammar = self.grammar();
+
+        let mut states = Vec::new();
+
+        for (i, automaton_state) in lr1_automaton.states.iter().enumerate() {
+            let mut lr1_state = LR1State {
+                actions: BTreeMap::new(),
+                endmarker: None,
+                goto: BTreeMap::new(),
+            };
+            let cores: Vec<_> = automaton_state
+                .items
+                .iter()
+                .map(LR1Item::core)
+                .dedup()
+                .collect();
+
+            for (sy, &dest) in &automaton_state.transitions {
+                match **sy {
+                    // If [A -> α·aβ, b] is in I_i and GOTO(I_i, a) = I_j and a is a terminal, then
+                    // set ACTION[i, a] to "shift j".
+                    Symbol::Terminal(ref t) => {
+                        lr1_state
+                            .set_action_in(i, &cores, Some(t), LR1Action::Shift(dest), priority_of)
+                            .or_else(&mut *on_conflict)?;
+                    }
+                    // If GOTO(I_i, A) = I_j, then GOTO[i, A] = j.
+                    Symbol::Nonterminal(ref n) => {
+                        lr1_state.goto.insert(n, dest);
+                    }
+                }
+            }
+
+            for item in automaton_state.items.iter() {
+                // If [A -> α·, a] is in I_i, A != S', then set ACTION[i, a] to "reduce A ->
+                // α".
+                if item.pos == item.rhs.body.len() {
+                    if *item.lhs != grammar.start {
+                        lr1_state
+                            .set_action_in(
+                                i,
+                                &cores,
+                                item.lookahead,
+                                LR1Action::Reduce(item.lhs, item.rhs),
+                                priority_of,
+                            )
+                            .or_else(&mut *on_conflict)?;
+                    } else if item.lookahead.is_none() {
+                        // If [S' -> S·, $] is in I_i, then set ACTION[i, $] to "accept".
+                        lr1_state
+                            .set_action_in(i, &cores, None, LR1Action::Accept, priority_of)
+                            .or_else(&mut *on_conflict)?;
+                    }
+                }
+            }
+
+            states.push(lr1_state);
+        }
+
+        Ok(LR1Table {
+            states,
+            initial: lr1_automaton.start,
+        })
+    }
+
+    /// See [`Grammar::slr1_table`].
+    #[inline]
+    pub fn slr1_table<F>(
+        &self,
+        priority_of: &F,
+    ) -> Result<LR1Table<'g, T, N, A>, LR1Conflict<'g, T, N, A>>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        self.slr1_table_with(priority_of, &mut Err)
+    }
+
+    /// See [`Grammar::slr1_table_verbose`].
+    #[inline]
+    pub fn slr1_table_verbose<F>(&self, priority_of: &F) -> LR1TableConflicts<'g, T, N, A>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        let mut conflicts = Vec::new();
+        let table = self.slr1_table_with(priority_of, &mut |conflict| {
+            conflicts.push(conflict);
+            Ok(())
+        });
+
+        match table {
+            Ok(table) => (table, with_reduce_conflicts(conflicts, priority_of)),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// See [`Grammar::slr1_table_with_policy`].
+    #[inline]
+    pub fn slr1_table_with_policy<F>(
+        &self,
+        priority_of: &F,
+        policy: ConflictPolicy,
+    ) -> Result<LR1TableConflicts<'g, T, N, A>, LR1Conflict<'g, T, N, A>>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    {
+        let mut resolved = Vec::new();
+        let mut table = self.slr1_table_with(priority_of, &mut |conflict| match conflict {
+            LR1Conflict::ShiftReduce { .. } if policy != ConflictPolicy::Error => {
+                resolved.push(conflict);
+                Ok(())
+            }
+            _ => Err(conflict),
+        })?;
+
+        // Reduce-reduce conflicts between the reductions are resolved by priority or fail as
+        // usual.
+        let reductions = compare_reductions(&resolved, priority_of, &mut Err)?;
+
+        // The shift is set first and kept on a conflict, so only a preferred reduction needs to
+        // replace it.
+        if policy == ConflictPolicy::PreferReduce {
+            for ((state, sy), (lhs, rhs)) in reductions {
+                table.states[state].replace_action(sy, LR1Action::Reduce(lhs, rhs));
+            }
+        }
+
+        Ok((table, resolved))
+    }
+
+    #[inline]
+    fn slr1_table_with<F, C>(
+        &self,
+        priority_of: &F,
+        on_conflict: &mut C,
+    ) -> Result<LR1Table<'g, T, N, A>, LR1Conflict<'g, T, N, A>>
+    where
+        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+        C: FnMut(LR1Conflict<'g, T, N, A>) -> Result<(), LR1Conflict<'g, T, N, A>>,
+    {
+        let lr0_automaton = self.lr0_automaton();
+        let follow_sets = self.follow_sets();
+        let grammar = self.grammar();
+
+        // New states in the LR(1) table.
+        let mut states = Vec::new();
+
+        for (i, lr0_state) in lr0_automaton.states.iter().enumerate() {
+            let mut lr1_state = LR1State {
+                actions: BTreeMap::new(),
+                endmarker: None,
+                goto: BTreeMap::new(),
+            };
+            let cores: Vec<_> = lr0_state.items.iter().cloned().collect();
+
+            for (sy, &dest) in &lr0_state.transitions {
+                match **sy {
+                    // If [A -> α.aβ] is in I_i and GOTO(I_i, a) = I_j and a is a terminal, then
+                    // set ACTION[i, a] to "shift j".
+                    Symbol::Terminal(ref t) => {
+                        lr1_state
+                            .set_action_in(i, &cores, Some(t), LR1Action::Shift(dest), priority_of)
+                            .or_else(&mut *on_conflict)?;
+                    }
+                    // If GOTO(I_i, A) = I_j for nonterminal A, then GOTO[i, A] = j.
+                    Symbol::Nonterminal(ref n) => {
+                        lr1_state.goto.insert(n, dest);
+                    }
+                }
+            }
+
+            for item in &cores {
+                // If [A -> α.] is in I_i, then set ACTION[i, a] to "reduce A -> α" for all a in
+                // FOLLOW(A), unless A is S'.
+                if item.pos == item.rhs.body.len() {
+                    if *item.lhs != grammar.start {
+                        let (follow_set, endmarker) = follow_sets.get(item.lhs).unwrap();
+                        for sy in follow_set {
+                            lr1_state
+                                .set_action_in(
+                                    i,
+                                    &cores,
+                                    Some(sy),
+                                    LR1Action::Reduce(item.lhs, item.rhs),
+                                    priority_of,
+                                )
+                                .or_else(&mut *on_conflict)?;
+                        }
+
+                        if *endmarker {
+                            lr1_state
+                                .set_action_in(
+                                    i,
+                                    &cores,
+                                    None,
+                                    LR1Action::Reduce(item.lhs, item.rhs),
+                                    priority_of,
+                                )
+                                .or_else(&mut *on_conflict)?;
+                        }
+                    } else {
+                        // If [S' -> S.] is in I_i, then set ACTION[i, $] to "accept".
+                        lr1_state
+                            .set_action_in(i, &cores, None, LR1Action::Accept, priority_of)
+                            .or_else(&mut *on_conflict)?;
+                    }
+                }
+            }
+
+            states.push(lr1_state);
+        }
+
+        Ok(LR1Table {
+            states,
+            // The initial state of the parser is the one constructed from the set of items
+            // containing [S' -> .S].
+            initial: lr0_automaton.start,
+        })
+    }
+}
+
+/// The preferred reduction for each state and lookahead, by state index.
+type Reductions<'g, T, N, A> = BTreeMap<(usize, Option<&'g T>), (&'g N, &'g Rhs<T, N, A>)>;
+
+/// Compare the reductions of shift-reduce conflicts in the same state and on the same lookahead
+/// with each other. They also conflict with each other, but are never compared during
+/// construction because the shift is kept. Reduce-reduce conflicts between them are passed to
+/// `on_conflict`, and the existing reduction is kept unless it returns [`Err`]. Returns the
+/// preferred reduction for each state and lookahead.
+#[inline]
+fn compare_reductions<'g, T, N, A, F, C>(
+    conflicts: &[LR1Conflict<'g, T, N, A>],
+    priority_of: &F,
+    on_conflict: &mut C,
+) -> Result<Reductions<'g, T, N, A>, LR1Conflict<'g, T, N, A>>
+where
+    T: Ord,
+    N: Ord,
+    F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+    C: FnMut(LR1Conflict<'g, T, N, A>) -> Result<(), LR1Conflict<'g, T, N, A>>,
+{
+    let mut reductions = BTreeMap::new();
+    for conflict in conflicts {
+        if let LR1Conflict::ShiftReduce {
+            shift: (sy, _),
+            reduce: (lhs, rhs),
+            state: Some(state),
+            items: Some(ref items),
+        } = *conflict
+        {
+            let reduce = match reductions.get(&(state, sy)) {
+                Some(&(lhs1, rhs1)) => match LR1State::determine_conflict(
+                    Some((state, items)),
+                    &LR1Action::Reduce(lhs1, rhs1),
+                    &LR1Action::Reduce(lhs, rhs),
+                    sy,
+                    priority_of,
+                ) {
+                    LR1ConflictResolution::Conflict(conflict) => {
+                        on_conflict(conflict)?;
+                        (lhs1, rhs1)
+                    }
+                    LR1ConflictResolution::Override => (lhs, rhs),
+                    LR1ConflictResolution::Keep => (lhs1, rhs1),
+                },
+                None => (lhs, rhs),
+            };
+            reductions.insert((state, sy), reduce);
+        }
+    }
+    Ok(reductions)
+}
+
+/// Add the reduce-reduce conflicts between reductions that conflict with the same shift (see
+/// [`compare_reductions`]) to the conflicts found during construction.
+#[inline]
+fn with_reduce_conflicts<'g, T, N, A, F>(
+    mut conflicts: Vec<LR1Conflict<'g, T, N, A>>,
+    priority_of: &F,
+) -> Vec<LR1Conflict<'g, T, N, A>>
+where
+    T: Ord,
+    N: Ord,
+    F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
+{
+    let mut reduce_conflicts = Vec::new();
+    let reductions = compare_reductions(&conflicts, priority_of, &mut |conflict| {
+        reduce_conflicts.push(conflict);
+        Ok(())
+    });
+
+    match reductions {
+        Ok(_) => {
+            conflicts.append(&mut reduce_conflicts);
+            conflicts
+        }
+        Err(_) => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod test_grammar_4_55 {
+    use crate::{
+        Grammar, Rhs,
+        Symbol::{Nonterminal as NT, Terminal as TT},
+    };
+
+    use std::collections::BTreeMap;
+
+    use Nonterminal::*;
+    use Terminal::*;
+
+    #[test]
+    fn test_lalr1_table_by_lr1() {
+        let grammar = create_grammar();
+        let table = grammar.lalr1_table_by_lr1(&|_, _, _| 0).unwrap();
+
+        assert_eq!(7, table.states.len());
+    }
+
+    #[test]
+    fn test_lr1_table() {
+        let grammar = create_grammar();
+
+        let table = grammar.lr1_table(&|_, _, _| 0).unwrap();
+
+        assert_eq!(10, table.states.len());
+    }
+
+    #[test]
+    fn test_analysis() {
+        let grammar = create_grammar();
+        let analysis = grammar.analyze();
+        let priority_of = |_: &_, _: &_, _: Option<&_>| 0;
+
+        // Tables constructed from one analysis are those constructed from fresh ones.
+        let slr1 = analysis.slr1_table(&priority_of).unwrap();
+        let lalr1 = analysis.lalr1_table_by_lr1(&priority_of).unwrap();
+        let lr1 = analysis.lr1_table(&priority_of).unwrap();
+        assert_eq!(
+            format!("{:?}", grammar.slr1_table(&priority_of).unwrap()),
+            format!("{:?}", slr1)
+        );
+        assert_eq!(
+            format!("{:?}", grammar.lalr1_table_by_lr1(&priority_of).unwrap()),
+            format!("{:?}", lalr1)
+        );
+        assert_eq!(
+            format!("{:?}", grammar.lr1_table(&priority_of).unwrap()),
+            format!("{:?}", lr1)
+        );
+
+        assert_eq!(grammar.first_sets(), *analysis.first_sets());
+        assert_eq!(grammar.follow_sets(None), *analysis.follow_sets());
+        assert_eq!(
+            grammar.lr0_automaton().states.len(),
+            analysis.lr0_automaton().states.len()
+        );
+    }
+
+    fn create_grammar() -> Grammar<Terminal, Nonterminal, ()> {
+        let mut rules = BTreeMap::new();
+
+        // E -> S
+        let start_rhs = Rhs::noop(vec![NT(S)]);
+        rules.insert(E, vec![start_rhs]);
+
+        // S -> C C
+        let cc = Rhs::noop(vec![NT(C), NT(C)]);
+        rules.insert(S, vec![cc]);
+
+        // C -> x C
+        //    | y
+        let x_c = Rhs::noop(vec![TT(X), NT(C)]);
+        let y = Rhs::noop(vec![TT(Y)]);
+        rules.insert(C, vec![x_c, y]);
+
+        Grammar::new(E, rules).unwrap()
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Nonterminal {
+        E,
+        S,
+        C,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Terminal {
+        X,
+        Y,
+    }
+}
+
+#[cfg(test)]
+mod test_grammar_4_49 {
+    use super::*;
+    use crate::{
+        Grammar, Rhs,
+        Symbol::{Nonterminal as NT, Terminal as TT},
+    };
+
+    use std::collections::BTreeMap;
+
+    use Nonterminal::*;
+    use Terminal::*;
+
+    #[test]
+    fn test_lr1_closure() {
+        let mut rules = BTreeMap::new();
+
+        // E -> S
+        let start_rhs = Rhs::noop(vec![NT(S)]);
+        rules.insert(E, vec![start_rhs.clone()]);
+
+        // S -> L = R
+        //    | R
+        let l_eq_r = Rhs::noop(vec![NT(L), TT(Equ), NT(R)]);
+        let r = Rhs::noop(vec![NT(R)]);
+        rules.insert(S, vec![l_eq_r, r]);
+
+        // L -> * R
+        //    | id
+        let deref_r = Rhs::noop(vec![TT(Deref), NT(R)]);
+        let id = Rhs::noop(vec![TT(Id)]);
+        rules.insert(L, vec![deref_r, id]);
+
+        // R -> L
+        let l = Rhs::noop(vec![NT(L)]);
+        rules.insert(R, vec![l]);
+
+        let grammar = Grammar::new(E, rules).unwrap();
+
+        // Compute CLOSURE({[E -> ·S, #]})
+        let mut initial_set = LR1ItemSet::new();
+        initial_set.insert(LR1Item {
+            lhs: &E,
+            rhs: &start_rhs,
+            pos: 0,
+            lookahead: None,
+        });
+
+        grammar.lr1_closure(&mut initial_set, &grammar.first_sets());
+
+        assert_eq!(8, initial_set.items.len());
+    }
+
+    #[test]
+    fn test_item_display() {
+        // L -> * R
+        let deref_r: Rhs<&str, &str, ()> = Rhs::noop(vec![TT("*"), NT("R")]);
+        let item = |pos, lookahead| LR1Item {
+            lhs: &"L",
+            rhs: &deref_r,
+            pos,
+            lookahead,
+        };
+
+        assert_eq!("L -> * . R , =", item(1, Some(&"=")).to_string());
+        assert_eq!("L -> * R . , $", item(2, None).to_string());
+    }
+
+    #[test]
+    fn test_grammar_class() {
+        let grammar = create_grammar();
+
+        // = is in FOLLOW(R), so the SLR(1) table has a shift-reduce conflict on = after L.
+        assert!(!grammar.is_slr1());
+        assert!(grammar.is_lalr1());
+        assert!(grammar.is_lr1());
+        assert!(!grammar.is_ll1());
+    }
+
+    #[test]
+    fn test_conflict_state() {
+        let grammar = create_grammar();
+        let conflict = grammar.slr1_table(&|_, _, _| 0).unwrap_err();
+
+        // The conflict is between shifting = and reducing R -> L, in the state reached by L.
+        assert!(matches!(
+            conflict,
+            LR1Conflict::ShiftReduce {
+                shift: (Some(&Equ), _),
+                reduce: (&R, _),
+                ..
+            }
+        ));
+        assert_eq!(Some(2), conflict.state());
+
+        // S -> L · = R
+        // R -> L ·
+        let items: Vec<_> = conflict
+            .items()
+            .unwrap()
+            .iter()
+            .map(|item| (item.lhs, item.pos))
+            .collect();
+        assert_eq!(vec![(&S, 1), (&R, 1)], items);
+
+        let lr0_automaton = grammar.lr0_automaton();
+        let lr0_items: Vec<_> = lr0_automaton.states[2].items.iter().collect();
+        assert_eq!(
+            lr0_items,
+            conflict.items().unwrap().iter().collect::<Vec<_>>()
+        );
+
+        // A conflict raised without the index and items of its state records neither.
+        let (shift, reduce) = match conflict {
+            LR1Conflict::ShiftReduce { shift, reduce, .. } => (shift, reduce),
+            _ => unreachable!(),
+        };
+        let mut state = LR1State {
+            actions: BTreeMap::new(),
+            endmarker: None,
+            goto: BTreeMap::new(),
+        };
+        let priority_of = |_: &_, _: &_, _: Option<&_>| 0;
+        state
+            .set_action(shift.0, LR1Action::Shift(shift.1), &priority_of)
+            .unwrap();
+        let conflict = state
+            .set_action(shift.0, LR1Action::Reduce(reduce.0, reduce.1), &priority_of)
+            .unwrap_err();
+        assert_eq!(None, conflict.state());
+        assert_eq!(None, conflict.items());
+    }
+
+    #[test]
+    fn test_macros() {
+        // The productions defined by the macros are those built by hand.
+        let mut rules = BTreeMap::new();
+        rules.insert(E, vec![Rhs::noop(vec![NT(S)])]);
+        let l_eq_r = Rhs::noop(vec![NT(L), TT(Equ), NT(R)]);
+        rules.insert(S, vec![l_eq_r, Rhs::noop(vec![NT(R)])]);
+        let deref_r = Rhs::noop(vec![TT(Deref), NT(R)]);
+        rules.insert(L, vec![deref_r, Rhs::noop(vec![TT(Id)])]);
+        rules.insert(R, vec![Rhs::noop(vec![NT(L)])]);
+
+        assert_eq!(rules, create_grammar().rules);
+
+        // Productions with associated data.
+        let with_assoc: Rhs<Terminal, Nonterminal, u8> = rhs!(NT(L), TT(Equ), NT(R) => 1);
+        assert_eq!(1, with_assoc.assoc);
+        assert_eq!(rules[&S][0].body, with_assoc.body);
+    }
+
+    fn create_grammar() -> Grammar<Terminal, Nonterminal, ()> {
+        // E -> S
+        // S -> L = R
+        //    | R
+        // L -> * R
+        //    | id
+        // R -> L
+        let rules = rules! {
+            E => [NT(S)],
+            S => [NT(L), TT(Equ), NT(R)] | [NT(R)],
+            L => [TT(Deref), NT(R)] | [TT(Id)],
+            R => [NT(L)],
+        };
+
+        Grammar::new(E, rules).unwrap()
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Nonterminal {
+        E,
+        S,
+        L,
+        R,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Terminal {
+        Equ,
+        Deref,
+        Id,
+    }
+}
+
+#[cfg(test)]
+mod test_epsilon {
+    use super::*;
+    use crate::{
+        Grammar, Rhs,
+        Symbol::{Nonterminal as NT, Terminal as TT},
+    };
+
+    use std::collections::BTreeMap;
 
-                    for rhs in b_productions {
-                        // For each terminal b in FIRST(βa), add [B -> ·γ, b] to set I.
-                        for bt in &first_beta_a {
-                            added.insert(LR1Item {
-                                lhs: b,
-                                rhs,
-                                pos: 0,
-                                lookahead: *bt,
-                            });
-                        }
-                    }
-                }
-            }
+    use Nonterminal::*;
 
-            if !added.is_empty() {
-                for item in added.into_iter() {
-                    if set.insert(item) {
-                        changed = true;
-                    }
-                }
-            }
-        }
+    #[test]
+    fn test_slr1_table() {
+        let grammar = create_grammar();
+        let table = grammar.slr1_table(&|_, _, _| 0).unwrap();
+
+        // [A -> ·] is in the initial state, so reduce by A -> ε on FOLLOW(A) = {b}.
+        let initial_state = &table.states[table.initial];
+        assert!(matches!(
+            initial_state.actions.get(&Terminal::B),
+            Some(LR1Action::Reduce(&A, rhs)) if rhs.body.is_empty()
+        ));
+        assert!(initial_state.endmarker.is_none());
+        assert!(initial_state.goto.contains_key(&A));
     }
 
-    /// Construct an SLR(1) parse table for the grammar.
-    #[inline]
-    pub fn slr1_table<'g, F>(
-        &'g self,
-        priority_of: &F,
-    ) -> Result<LR1Table<'g, T, N, A>, LR1Conflict<'g, T, N, A>>
-    where
-        F: Fn(&N, &Rhs<T, N, A>, Option<&T>) -> i32,
-    {
-        let lr0_automaton = self.lr0_automaton();
-        let follow_sets = self.follow_sets(None);
+    #[test]
+    fn test_lr1_table() {
+        let grammar = create_grammar();
+        let table = grammar.lr1_table(&|_, _, _| 0).unwrap();
 
-        // New states in the LR(1) table.
-        let mut states = Vec::new();
+        let initial_state = &table.states[table.initial];
+        assert!(matches!(
+            initial_state.actions.get(&Terminal::B),
+            Some(LR1Action::Reduce(&A, rhs)) if rhs.body.is_empty()
+        ));
+    }
 
-        for lr0_state in lr0_automaton.states {
-            let mut lr1_state = LR1State {
-                actions: BTreeMap::new(),
-                endmarker: None,
-                goto: BTreeMap::new(),
-            };
+    #[test]
+    fn test_lr1_closure() {
+        let grammar = create_grammar();
+        let first_sets = grammar.first_sets();
 
-            for (sy, dest) in lr0_state.transitions {
-                match *sy {
-                    // If [A -> α.aβ] is in I_i and GOTO(I_i, a) = I_j and a is a terminal, then
-                    // set ACTION[i, a] to "shift j".
-                    Symbol::Terminal(ref t) => {
-                        lr1_state.set_action(Some(t), LR1Action::Shift(dest), priority_of)?;
-                    }
-                    // If GOTO(I_i, A) = I_j for nonterminal A, then GOTO[i, A] = j.
-                    Symbol::Nonterminal(ref n) => {
-                        lr1_state.goto.insert(n, dest);
-                    }
-                }
-            }
+        // Compute CLOSURE({[E -> ·S, $]})
+        let mut initial_set = LR1ItemSet::new();
+        initial_set.insert(LR1Item {
+            lhs: &E,
+            rhs: &grammar.rules[&E][0],
+            pos: 0,
+            lookahead: None,
+        });
+        grammar.lr1_closure(&mut initial_set, &first_sets);
 
-            for item in lr0_state.items {
-                // If [A -> α.] is in I_i, then set ACTION[i, a] to "reduce A -> α" for all a in
-                // FOLLOW(A), unless A is S'.
-                if item.pos == item.rhs.body.len() {
-                    if *item.lhs != self.start {
-                        let (follow_set, endmarker) = follow_sets.get(item.lhs).unwrap();
-                        for sy in follow_set {
-                            lr1_state.set_action(
-                                Some(sy),
-                                LR1Action::Reduce(item.lhs, item.rhs),
-                                priority_of,
-                            )?;
-                        }
+        // [E -> ·S, $], [S -> ·A B, $], [A -> ·, b]
+        assert_eq!(3, initial_set.len());
+        assert!(initial_set.iter().any(|item| *item.lhs == A
+            && item.pos == item.rhs.body.len()
+            && item.lookahead == Some(&Terminal::B)));
+    }
 
-                        if *endmarker {
-                            lr1_state.set_action(
-                                None,
-                                LR1Action::Reduce(item.lhs, item.rhs),
-                                priority_of,
-                            )?;
-                        }
-                    } else {
-                        // If [S' -> S.] is in I_i, then set ACTION[i, $] to "accept".
-                        lr1_state.set_action(None, LR1Action::Accept, priority_of)?;
-                    }
-                }
-            }
+    fn create_grammar() -> Grammar<Terminal, Nonterminal, ()> {
+        let mut rules = BTreeMap::new();
 
-            states.push(lr1_state);
-        }
+        // E -> S
+        rules.insert(E, vec![Rhs::noop(vec![NT(S)])]);
 
-        Ok(LR1Table {
-            states,
-            // The initial state of the parser is the one constructed from the set of items
-            // containing [S' -> .S].
-            initial: lr0_automaton.start,
-        })
+        // S -> A B
+        rules.insert(S, vec![Rhs::noop(vec![NT(A), NT(B)])]);
+
+        // A -> ε
+        rules.insert(A, vec![Rhs::noop(vec![])]);
+
+        // B -> b
+        rules.insert(B, vec![Rhs::noop(vec![TT(Terminal::B)])]);
+
+        Grammar::new(E, rules).unwrap()
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Nonterminal {
+        E,
+        S,
+        A,
+        B,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Terminal {
+        B,
     }
 }
 
 #[cfg(test)]
-mod test_grammar_4_55 {
+mod test_ambiguous {
+    use super::*;
     use crate::{
+        parser::{LR1Parser, ParseTree},
         Grammar, Rhs,
         Symbol::{Nonterminal as NT, Terminal as TT},
     };
 
-    use std::collections::BTreeMap;
-
     use Nonterminal::*;
     use Terminal::*;
 
     #[test]
-    fn test_lalr1_table_by_lr1() {
+    fn test_slr1_table_verbose() {
         let grammar = create_grammar();
-        let table = grammar.lalr1_table_by_lr1(&|_, _, _| 0).unwrap();
+        assert!(grammar.slr1_table(&|_, _, _| 0).is_err());
 
-        assert_eq!(7, table.states.len());
+        let (table, conflicts) = grammar.slr1_table_verbose(&|_, _, _| 0);
+        assert_conflicts(&table, &conflicts);
     }
 
     #[test]
-    fn test_lr1_table() {
+    fn test_lr1_table_verbose() {
         let grammar = create_grammar();
+        assert!(grammar.lr1_table(&|_, _, _| 0).is_err());
 
-        let table = grammar.lr1_table(&|_, _, _| 0).unwrap();
+        let (table, conflicts) = grammar.lr1_table_verbose(&|_, _, _| 0);
+        assert_conflicts(&table, &conflicts);
+    }
 
-        assert_eq!(10, table.states.len());
+    #[test]
+    fn test_lalr1_table_by_lr1_verbose() {
+        let grammar = create_grammar();
+        assert!(grammar.lalr1_table_by_lr1(&|_, _, _| 0).is_err());
+
+        let (table, conflicts) = grammar.lalr1_table_by_lr1_verbose(&|_, _, _| 0);
+        assert_conflicts(&table, &conflicts);
+    }
+
+    /// Both E -> E + E· and E -> E * E· conflict with shifting + and *, and every conflict is
+    /// resolved as a shift, so that both operators are right-associative with equal precedence.
+    fn assert_conflicts(
+        table: &LR1Table<'_, Terminal, Nonterminal, ()>,
+        conflicts: &[LR1Conflict<'_, Terminal, Nonterminal, ()>],
+    ) {
+        assert_eq!(4, conflicts.len());
+        assert!(conflicts.iter().all(|conflict| match conflict {
+            LR1Conflict::ShiftReduce { shift, .. } => matches!(shift.0, Some(Plus) | Some(Times)),
+            LR1Conflict::ReduceReduce { .. } => false,
+        }));
+
+        // id * id + id
+        let parser = LR1Parser::new(table);
+        let tree = parser.parse(vec![Id, Times, Id, Plus, Id].into_iter());
+
+        let id = || ParseTree::Node(&E, vec![ParseTree::Leaf(Id)]);
+        let sum = ParseTree::Node(&E, vec![id(), ParseTree::Leaf(Plus), id()]);
+        let expected = ParseTree::Node(&E, vec![id(), ParseTree::Leaf(Times), sum]);
+        assert_eq!(Ok(expected), tree);
     }
 
     fn create_grammar() -> Grammar<Terminal, Nonterminal, ()> {
         let mut rules = BTreeMap::new();
 
-        // E -> S
-        let start_rhs = Rhs::noop(vec![NT(S)]);
-        rules.insert(E, vec![start_rhs]);
-
-        // S -> C C
-        let cc = Rhs::noop(vec![NT(C), NT(C)]);
-        rules.insert(S, vec![cc]);
+        // S -> E
+        rules.insert(S, vec![Rhs::noop(vec![NT(E)])]);
 
-        // C -> x C
-        //    | y
-        let x_c = Rhs::noop(vec![TT(X), NT(C)]);
-        let y = Rhs::noop(vec![TT(Y)]);
-        rules.insert(C, vec![x_c, y]);
+        // E -> E + E
+        //    | E * E
+        //    | id
+        let sum = Rhs::noop(vec![NT(E), TT(Plus), NT(E)]);
+        let product = Rhs::noop(vec![NT(E), TT(Times), NT(E)]);
+        let id = Rhs::noop(vec![TT(Id)]);
+        rules.insert(E, vec![sum, product, id]);
 
-        Grammar::new(E, rules).unwrap()
+        Grammar::new(S, rules).unwrap()
     }
 
     #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
     enum Nonterminal {
-        E,
         S,
-        C,
+        E,
     }
 
     #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
     enum Terminal {
-        X,
-        Y,
+        Plus,
+        Times,
+        Id,
     }
 }
 
 #[cfg(test)]
-mod test_grammar_4_49 {
+mod test_warnings {
     use super::*;
     use crate::{
         Grammar, Rhs,
@@ -866,57 +1818,319 @@ mod test_grammar_4_49 {
     use Terminal::*;
 
     #[test]
-    fn test_lr1_closure() {
+    fn test_unreachable_nonterminal() {
         let mut rules = BTreeMap::new();
 
         // E -> S
-        let start_rhs = Rhs::noop(vec![NT(S)]);
-        rules.insert(E, vec![start_rhs.clone()]);
+        rules.insert(E, vec![Rhs::noop(vec![NT(S)])]);
 
-        // S -> L = R
-        //    | R
-        let l_eq_r = Rhs::noop(vec![NT(L), TT(Equ), NT(R)]);
-        let r = Rhs::noop(vec![NT(R)]);
-        rules.insert(S, vec![l_eq_r, r]);
+        // S -> x S
+        //    | y
+        let x_s = Rhs::noop(vec![TT(X), NT(S)]);
+        let y = Rhs::noop(vec![TT(Y)]);
+        rules.insert(S, vec![x_s, y]);
 
-        // L -> * R
-        //    | id
-        let deref_r = Rhs::noop(vec![TT(Deref), NT(R)]);
-        let id = Rhs::noop(vec![TT(Id)]);
-        rules.insert(L, vec![deref_r, id]);
+        // Dead -> d
+        rules.insert(Dead, vec![Rhs::noop(vec![TT(D)])]);
 
-        // R -> L
-        let l = Rhs::noop(vec![NT(L)]);
-        rules.insert(R, vec![l]);
+        let grammar = Grammar::new(E, rules).unwrap();
+        let table = grammar.slr1_table(&|_, _, _| 0).unwrap();
+
+        let warnings = table.warnings(&grammar);
+        assert_eq!(1, warnings.len());
+        assert!(matches!(
+            warnings[0],
+            LR1Warning::UnreachableNonterminal(&Dead)
+        ));
+    }
+
+    #[test]
+    fn test_unused_production() {
+        let mut rules = BTreeMap::new();
+
+        // E -> S
+        rules.insert(E, vec![Rhs::noop(vec![NT(S)])]);
+
+        // S -> x
+        //    | T
+        let x = Rhs::noop(vec![TT(X)]);
+        let t = Rhs::noop(vec![NT(T)]);
+        rules.insert(S, vec![x, t]);
+
+        // T -> x
+        rules.insert(T, vec![Rhs::noop(vec![TT(X)])]);
 
         let grammar = Grammar::new(E, rules).unwrap();
+        let (table, conflicts) = grammar.lr1_table_verbose(&|_, _, _| 0);
+        assert_eq!(1, conflicts.len());
+
+        // One of the x productions loses the reduce-reduce conflict.
+        let warnings = table.warnings(&grammar);
+        assert_eq!(1, warnings.len());
+        match warnings[0] {
+            LR1Warning::UnusedProduction(_, rhs) => assert_eq!(vec![TT(X)], rhs.body),
+            _ => panic!("expected an unused production"),
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Nonterminal {
+        E,
+        S,
+        T,
+        Dead,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Terminal {
+        X,
+        Y,
+        D,
+    }
+}
+
+#[cfg(test)]
+mod test_long_bodies {
+    use super::*;
+    use crate::{GrammarBuilder, StringGrammar};
+
+    /// Number of optional symbols in the body of S.
+    const LEN: usize = 60;
+
+    #[test]
+    fn test_lr1_closure() {
+        let grammar = create_grammar();
+        let start_rhs = &grammar.rules["E"][0];
 
         // Compute CLOSURE({[E -> ·S, #]})
         let mut initial_set = LR1ItemSet::new();
         initial_set.insert(LR1Item {
-            lhs: &E,
-            rhs: &start_rhs,
+            lhs: &grammar.start,
+            rhs: start_rhs,
             pos: 0,
             lookahead: None,
         });
-
         grammar.lr1_closure(&mut initial_set, &grammar.first_sets());
 
-        assert_eq!(8, initial_set.items.len());
+        // [E -> ·S, #], [S -> ·A0 ... x, #], and both productions of A0 for each lookahead in
+        // FIRST(A1 ... x) = {a1, ..., x}.
+        assert_eq!(2 + 2 * LEN, initial_set.len());
+    }
+
+    #[test]
+    fn test_lr1_table() {
+        let grammar = create_grammar();
+        let table = grammar.lr1_table(&|_, _, _| 0).unwrap();
+
+        // S -> A0 ... x is reduced only after all of the optional symbols.
+        assert!(table.states.len() > 2 * LEN);
+        assert!(grammar.is_lalr1());
+    }
+
+    /// E -> S
+    /// S -> A0 A1 ... x
+    /// Ai -> ai | ε
+    fn create_grammar() -> StringGrammar {
+        let nt = |i| Symbol::Nonterminal(format!("A{}", i));
+        let tt = |s: String| Symbol::Terminal(s);
+
+        let mut body: Vec<_> = (0..LEN).map(nt).collect();
+        body.push(tt("x".to_string()));
+
+        let mut builder = GrammarBuilder::new()
+            .rule(
+                "E".to_string(),
+                Rhs::noop(vec![Symbol::Nonterminal("S".to_string())]),
+            )
+            .rule("S".to_string(), Rhs::noop(body));
+        for i in 0..LEN {
+            builder = builder
+                .rule(format!("A{}", i), Rhs::noop(vec![tt(format!("a{}", i))]))
+                .rule(format!("A{}", i), Rhs::noop(vec![]));
+        }
+
+        builder.build("E".to_string()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test_dangling_else {
+    use super::*;
+    use crate::{
+        parser::{LR1Parser, ParseTree},
+        Grammar,
+        Symbol::{Nonterminal as NT, Terminal as TT},
+    };
+
+    use Nonterminal::*;
+    use Terminal::*;
+
+    #[test]
+    fn test_prefer_shift() {
+        let grammar = create_grammar();
+        assert!(grammar.slr1_table(&|_, _, _| 0).is_err());
+        assert!(grammar
+            .slr1_table_with_policy(&|_, _, _| 0, ConflictPolicy::Error)
+            .is_err());
+
+        let (table, conflicts) = grammar
+            .slr1_table_with_policy(&|_, _, _| 0, ConflictPolicy::PreferShift)
+            .unwrap();
+
+        // The only conflict is between shifting else and reducing the if without it.
+        assert_eq!(1, conflicts.len());
+        assert!(matches!(
+            conflicts[0],
+            LR1Conflict::ShiftReduce {
+                shift: (Some(&Else), _),
+                reduce: (&S, rhs),
+                ..
+            } if rhs.body.len() == 4
+        ));
+
+        // The else is attached to the nearest if.
+        let parser = LR1Parser::new(&table);
+        let input = vec![If, Cond, Then, If, Cond, Then, Other, Else, Other];
+        let tree = parser.parse(input.into_iter()).unwrap();
+        match tree {
+            ParseTree::Node(&S, children) => {
+                assert_eq!(4, children.len());
+                assert!(matches!(&children[3], ParseTree::Node(&S, inner) if inner.len() == 6));
+            }
+            _ => panic!("expected S"),
+        }
+    }
+
+    #[test]
+    fn test_prefer_reduce() {
+        let grammar = create_grammar();
+        let (table, conflicts) = grammar
+            .slr1_table_with_policy(&|_, _, _| 0, ConflictPolicy::PreferReduce)
+            .unwrap();
+        assert_eq!(1, conflicts.len());
+
+        // The inner if is reduced before the else is seen, so the else can never be shifted.
+        let parser = LR1Parser::new(&table);
+        let input = vec![If, Cond, Then, Other, Else, Other];
+        assert!(parser.parse(input.into_iter()).is_err());
+        let input = vec![If, Cond, Then, Other];
+        assert!(parser.parse(input.into_iter()).is_ok());
+    }
+
+    #[test]
+    fn test_reduce_reduce_behind_shift() {
+        // E -> S
+        // S -> A other | B other | cond other
+        // A -> cond
+        // B -> cond
+        //
+        // After cond, reducing either A or B conflicts with shifting other, and with each other.
+        let rules = rules! {
+            E => [NT(S)],
+            S => [NT(A), TT(Other)] | [NT(B), TT(Other)] | [TT(Cond), TT(Other)],
+            A => [TT(Cond)],
+            B => [TT(Cond)],
+        };
+        let grammar = Grammar::new(E, rules).unwrap();
+
+        for &policy in &[ConflictPolicy::PreferShift, ConflictPolicy::PreferReduce] {
+            let result = grammar.slr1_table_with_policy(&|_, _, _| 0, policy);
+            assert!(matches!(result, Err(LR1Conflict::ReduceReduce { .. })));
+        }
+
+        // With a priority between the reductions, the preferred reduction is the higher one.
+        let priority_of = |lhs: &Nonterminal, _: &_, _: Option<&_>| match lhs {
+            A => 1,
+            _ => 0,
+        };
+        let (table, conflicts) = grammar
+            .slr1_table_with_policy(&priority_of, ConflictPolicy::PreferReduce)
+            .unwrap();
+        assert_eq!(2, conflicts.len());
+
+        let parser = LR1Parser::new(&table);
+        let tree = parser.parse(vec![Cond, Other].into_iter()).unwrap();
+        match tree {
+            ParseTree::Node(&S, children) => {
+                assert!(matches!(&children[0], ParseTree::Node(&A, _)));
+            }
+            _ => panic!("expected S"),
+        }
+    }
+
+    #[test]
+    fn test_verbose_reduce_reduce_behind_shift() {
+        // The grammar of test_reduce_reduce_behind_shift, where reducing either A or B after cond
+        // conflicts with shifting other, and with each other.
+        let rules = rules! {
+            E => [NT(S)],
+            S => [NT(A), TT(Other)] | [NT(B), TT(Other)] | [TT(Cond), TT(Other)],
+            A => [TT(Cond)],
+            B => [TT(Cond)],
+        };
+        let grammar = Grammar::new(E, rules).unwrap();
+
+        let priority_of = |_: &_, _: &_, _: Option<&_>| 0;
+        for (_, conflicts) in &[
+            grammar.slr1_table_verbose(&priority_of),
+            grammar.lr1_table_verbose(&priority_of),
+            grammar.lalr1_table_by_lr1_verbose(&priority_of),
+        ] {
+            let shift_reduce = conflicts
+                .iter()
+                .filter(|conflict| matches!(conflict, LR1Conflict::ShiftReduce { .. }))
+                .count();
+            assert_eq!(2, shift_reduce);
+
+            let reduce_reduce: Vec<_> = conflicts
+                .iter()
+                .filter_map(|conflict| match conflict {
+                    LR1Conflict::ReduceReduce {
+                        r1: (n1, _),
+                        r2: (n2, _),
+                        ..
+                    } => Some((*n1, *n2)),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(1, reduce_reduce.len());
+            let (n1, n2) = reduce_reduce[0];
+            assert!((n1, n2) == (&A, &B) || (n1, n2) == (&B, &A));
+        }
+    }
+
+    fn create_grammar() -> Grammar<Terminal, Nonterminal, ()> {
+        // E -> S
+        // S -> if C then S
+        //    | if C then S else S
+        //    | other
+        // C -> cond
+        let rules = rules! {
+            E => [NT(S)],
+            S => [TT(If), NT(C), TT(Then), NT(S)]
+                | [TT(If), NT(C), TT(Then), NT(S), TT(Else), NT(S)]
+                | [TT(Other)],
+            C => [TT(Cond)],
+        };
+
+        Grammar::new(E, rules).unwrap()
     }
 
     #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
     enum Nonterminal {
         E,
         S,
-        L,
-        R,
+        C,
+        A,
+        B,
     }
 
     #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
     enum Terminal {
-        Equ,
-        Deref,
-        Id,
+        If,
+        Then,
+        Else,
+        Other,
+        Cond,
     }
 }
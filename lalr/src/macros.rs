@@ -0,0 +1,43 @@
+/// Create a production from the symbols of its body, e.g. `rhs!(NT(L), TT(Equ), NT(R))`, where
+/// `NT` and `TT` are the [`Symbol`](crate::Symbol) variants imported under shorter names. The
+/// production has no associated data unless it is given after `=>`, as in
+/// `rhs!(NT(E), TT(Plus), NT(T) => add)`.
+#[macro_export]
+macro_rules! rhs {
+    ($($symbol:expr),* $(,)?) => {
+        $crate::Rhs::noop(vec![$($symbol),*])
+    };
+    ($($symbol:expr),* => $assoc:expr) => {
+        $crate::Rhs::new(vec![$($symbol),*], $assoc)
+    };
+}
+
+/// Create the map of productions of a grammar, for [`Grammar::new`](crate::Grammar::new), from
+/// the bodies of the productions of each nonterminal, separated by `|`:
+///
+/// ```
+/// # use lalr::{rules, Grammar, Symbol::{Nonterminal as NT, Terminal as TT}};
+/// let rules = rules! {
+///     "S" => [NT("E")],
+///     "E" => [NT("E"), TT("+"), NT("T")] | [NT("T")],
+///     "T" => [TT("id")],
+/// };
+/// let grammar = Grammar::new("S", rules).unwrap();
+/// # assert_eq!(2, grammar.rules[&"E"].len());
+/// ```
+///
+/// The productions have no associated data. A nonterminal may be given more than once, in which
+/// case its productions are appended in order.
+#[macro_export]
+macro_rules! rules {
+    ($($lhs:expr => $([$($symbol:expr),* $(,)?])|+),* $(,)?) => {{
+        let mut rules = ::std::collections::BTreeMap::new();
+        $(
+            rules
+                .entry($lhs)
+                .or_insert_with(::std::vec::Vec::new)
+                .extend(vec![$($crate::rhs!($($symbol),*)),+]);
+        )*
+        rules
+    }};
+}
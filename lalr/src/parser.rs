@@ -0,0 +1,491 @@
+use crate::grammar::Rhs;
+use crate::lr1::{LR1Action, LR1Table};
+
+use std::fmt;
+
+/// A token produced by a lexer that can be fed to an [`LR1Parser`]. Each token maps to the
+/// grammar terminal it is an instance of, so that tokens may carry payloads (e.g. the value of an
+/// integer literal) that the grammar terminals do not. Only the terminal is used to look up
+/// actions in the parse table; the token itself is kept in the parse tree or passed on to
+/// semantic actions.
+pub trait TokenSource<T> {
+    /// Return the grammar terminal that this token represents.
+    fn terminal(&self) -> &T;
+}
+
+/// A token that pairs the grammar terminal it is an instance of, its kind, with an attribute
+/// that the terminal does not carry, such as the value of an integer literal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<T, V> {
+    pub kind: T,
+    pub attribute: V,
+}
+
+impl<T, V> Token<T, V> {
+    /// Create a token of the given kind with an attribute.
+    #[inline]
+    pub fn new(kind: T, attribute: V) -> Self {
+        Self { kind, attribute }
+    }
+
+    /// Return the grammar terminal that this token is an instance of.
+    #[inline]
+    pub fn kind(&self) -> &T {
+        &self.kind
+    }
+
+    /// Discard the kind and return the attribute.
+    #[inline]
+    pub fn into_attribute(self) -> V {
+        self.attribute
+    }
+}
+
+impl<T, V> TokenSource<T> for Token<T, V> {
+    #[inline]
+    fn terminal(&self) -> &T {
+        self.kind()
+    }
+}
+
+/// A parse tree produced by an [`LR1Parser`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseTree<'g, N, K> {
+    /// A token that was shifted onto the stack.
+    Leaf(K),
+    /// A nonterminal that was reduced, with the subtrees for each symbol in the production body.
+    Node(&'g N, Vec<ParseTree<'g, N, K>>),
+}
+
+/// An error encountered when parsing a token stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError<K> {
+    /// The parse table has no action for the token in the current state.
+    UnexpectedToken(K),
+    /// The parse table has no action for the end of input in the current state.
+    UnexpectedEndmarker,
+    /// The input was accepted by a production of the starting nonterminal with an empty body,
+    /// which leaves no value to return. This only happens if the grammar is not augmented.
+    NoValue,
+}
+
+impl<K> fmt::Display for ParseError<K> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedToken(_) => write!(f, "unexpected token"),
+            Self::UnexpectedEndmarker => write!(f, "unexpected end of input"),
+            Self::NoValue => write!(f, "input accepted without a value"),
+        }
+    }
+}
+
+impl<K: fmt::Debug> std::error::Error for ParseError<K> {}
+
+/// A shift-reduce parser driven by an LR(1) parse table. Any of the canonical LR(1), LALR(1), or
+/// SLR(1) tables may be used.
+///
+/// The production of the starting nonterminal is accepted rather than reduced, and the value of
+/// the last symbol in its body is returned, so the grammar should be augmented (see
+/// [`Grammar::augment`](crate::Grammar::augment)). In particular, empty input is accepted if the
+/// original starting nonterminal derives the empty string (see
+/// [`Grammar::accepts_empty`](crate::Grammar::accepts_empty)). Otherwise, accepting by an empty
+/// production leaves no value, and [`ParseError::NoValue`] is returned.
+#[derive(Debug)]
+pub struct LR1Parser<'t, 'g, T, N, A> {
+    table: &'t LR1Table<'g, T, N, A>,
+}
+
+impl<'t, 'g, T, N, A> LR1Parser<'t, 'g, T, N, A>
+where
+    T: Ord,
+    N: Ord,
+{
+    /// Create a parser that uses the given parse table.
+    #[inline]
+    pub fn new(table: &'t LR1Table<'g, T, N, A>) -> Self {
+        Self { table }
+    }
+
+    /// Parse a stream of grammar terminals.
+    #[inline]
+    pub fn parse<I>(&self, input: I) -> Result<ParseTree<'g, N, T>, ParseError<T>>
+    where
+        I: Iterator<Item = T>,
+    {
+        self.parse_tokens(input.map(BareTerminal))
+            .map(ParseTree::into_terminals)
+            .map_err(|err| match err {
+                ParseError::UnexpectedToken(BareTerminal(t)) => ParseError::UnexpectedToken(t),
+                ParseError::UnexpectedEndmarker => ParseError::UnexpectedEndmarker,
+                ParseError::NoValue => ParseError::NoValue,
+            })
+    }
+
+    /// Parse the tokens produced by a lexer, mapping each token to its grammar terminal with
+    /// [`TokenSource::terminal`]. The tokens themselves are kept as the leaves of the parse tree.
+    #[inline]
+    pub fn parse_tokens<L>(
+        &self,
+        lexer: L,
+    ) -> Result<ParseTree<'g, N, L::Item>, ParseError<L::Item>>
+    where
+        L: Iterator,
+        L::Item: TokenSource<T>,
+    {
+        self.drive(lexer, ParseTree::Leaf, |lhs, _, children| {
+            ParseTree::Node(lhs, children)
+        })
+    }
+
+    /// Parse the tokens produced by a lexer, computing a semantic value for each symbol instead
+    /// of a parse tree. Each shifted token is converted to a value with `value_of`. On each
+    /// reduction, the action associated with the production (the `assoc` of its [`Rhs`]) is
+    /// called with the values of the symbols in the body, in order, and returns the value of the
+    /// nonterminal. The value of the starting nonterminal is returned.
+    #[inline]
+    pub fn evaluate<L, V, F>(&self, lexer: L, value_of: F) -> Result<V, ParseError<L::Item>>
+    where
+        L: Iterator,
+        L::Item: TokenSource<T>,
+        A: Fn(Vec<V>) -> V,
+        F: FnMut(L::Item) -> V,
+    {
+        self.drive(lexer, value_of, |_, rhs, values| (rhs.assoc)(values))
+    }
+
+    /// Run the shift-reduce loop, building a value of type `X` for each symbol with `shift` and
+    /// `reduce`.
+    #[inline]
+    fn drive<L, X, S, R>(
+        &self,
+        lexer: L,
+        mut shift: S,
+        mut reduce: R,
+    ) -> Result<X, ParseError<L::Item>>
+    where
+        L: Iterator,
+        L::Item: TokenSource<T>,
+        S: FnMut(L::Item) -> X,
+        R: FnMut(&'g N, &'g Rhs<T, N, A>, Vec<X>) -> X,
+    {
+        let mut lexer = lexer.peekable();
+
+        let mut states = vec![self.table.initial];
+        let mut values: Vec<X> = Vec::new();
+
+        loop {
+            let state = &self.table.states[*states.last().unwrap()];
+            let action = state.action(lexer.peek().map(|token| token.terminal()));
+
+            match action {
+                Some(LR1Action::Shift(dest)) => {
+                    states.push(*dest);
+                    values.push(shift(lexer.next().unwrap()));
+                }
+                Some(LR1Action::Reduce(lhs, rhs)) => {
+                    // Pop one state and value for each symbol in the production body.
+                    let len = rhs.body.len();
+                    states.truncate(states.len() - len);
+                    let children = values.split_off(values.len() - len);
+
+                    let top = &self.table.states[*states.last().unwrap()];
+                    states.push(top.goto[lhs]);
+                    values.push(reduce(lhs, rhs, children));
+                }
+                Some(LR1Action::Accept) => return values.pop().ok_or(ParseError::NoValue),
+                None => {
+                    return Err(match lexer.next() {
+                        Some(token) => ParseError::UnexpectedToken(token),
+                        None => ParseError::UnexpectedEndmarker,
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Wrapper to treat bare grammar terminals as tokens.
+struct BareTerminal<T>(T);
+
+impl<T> TokenSource<T> for BareTerminal<T> {
+    #[inline]
+    fn terminal(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<'g, N, T> ParseTree<'g, N, BareTerminal<T>> {
+    #[inline]
+    fn into_terminals(self) -> ParseTree<'g, N, T> {
+        match self {
+            Self::Leaf(BareTerminal(t)) => ParseTree::Leaf(t),
+            Self::Node(lhs, children) => ParseTree::Node(
+                lhs,
+                children.into_iter().map(Self::into_terminals).collect(),
+            ),
+        }
+    }
+}
+
+impl<'g, N, K> ParseTree<'g, N, K> {
+    /// Iterate through the tokens at the leaves of the tree, from left to right.
+    #[inline]
+    pub fn leaves(&self) -> Box<dyn Iterator<Item = &K> + '_> {
+        match self {
+            Self::Leaf(token) => Box::new(std::iter::once(token)),
+            Self::Node(_, children) => Box::new(children.iter().flat_map(Self::leaves)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LR1Parser, ParseError, ParseTree};
+    use crate::{
+        Grammar, Rhs,
+        Symbol::{Nonterminal as NT, Terminal as TT},
+    };
+
+    use std::collections::BTreeMap;
+
+    use Nonterminal::*;
+    use Terminal::*;
+
+    #[test]
+    fn test_parse() {
+        let grammar = create_grammar();
+        let table = grammar.lalr1_table_by_lr1(&|_, _, _| 0).unwrap();
+        let parser = LR1Parser::new(&table);
+
+        // x y y
+        let tree = parser.parse(vec![X, Y, Y].into_iter()).unwrap();
+        let expected = ParseTree::Node(
+            &S,
+            vec![
+                ParseTree::Node(
+                    &C,
+                    vec![
+                        ParseTree::Leaf(X),
+                        ParseTree::Node(&C, vec![ParseTree::Leaf(Y)]),
+                    ],
+                ),
+                ParseTree::Node(&C, vec![ParseTree::Leaf(Y)]),
+            ],
+        );
+        assert_eq!(expected, tree);
+        assert_eq!(vec![&X, &Y, &Y], tree.leaves().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let grammar = create_grammar();
+        let table = grammar.lr1_table(&|_, _, _| 0).unwrap();
+        let parser = LR1Parser::new(&table);
+
+        let result = parser.parse(vec![Y, X].into_iter());
+        assert_eq!(Err(ParseError::UnexpectedEndmarker), result);
+
+        let result = parser.parse(vec![Y, Y, Y].into_iter());
+        assert_eq!(Err(ParseError::UnexpectedToken(Y)), result);
+    }
+
+    fn create_grammar() -> Grammar<Terminal, Nonterminal, ()> {
+        let mut rules = BTreeMap::new();
+
+        // E -> S
+        rules.insert(E, vec![Rhs::noop(vec![NT(S)])]);
+
+        // S -> C C
+        rules.insert(S, vec![Rhs::noop(vec![NT(C), NT(C)])]);
+
+        // C -> x C
+        //    | y
+        let x_c = Rhs::noop(vec![TT(X), NT(C)]);
+        let y = Rhs::noop(vec![TT(Y)]);
+        rules.insert(C, vec![x_c, y]);
+
+        Grammar::new(E, rules).unwrap()
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Nonterminal {
+        E,
+        S,
+        C,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Terminal {
+        X,
+        Y,
+    }
+}
+
+#[cfg(test)]
+mod test_empty {
+    use super::{LR1Parser, ParseError, ParseTree, Token};
+    use crate::{
+        Grammar, Rhs,
+        Symbol::{Nonterminal as NT, Terminal as TT},
+    };
+
+    use std::collections::BTreeMap;
+
+    use Nonterminal::*;
+    use Terminal::*;
+
+    #[test]
+    fn test_parse_empty() {
+        let grammar = create_grammar();
+        assert!(grammar.accepts_empty());
+
+        let table = grammar.lalr1_table_by_lr1(&|_, _, _| 0).unwrap();
+        let parser = LR1Parser::new(&table);
+
+        // The empty production is reduced before accepting.
+        let tree = parser.parse(vec![].into_iter()).unwrap();
+        assert_eq!(ParseTree::Node(&S, vec![]), tree);
+
+        // a a
+        let tree = parser.parse(vec![A, A].into_iter()).unwrap();
+        let empty = ParseTree::Node(&S, vec![]);
+        let inner = ParseTree::Node(&S, vec![ParseTree::Leaf(A), empty]);
+        let expected = ParseTree::Node(&S, vec![ParseTree::Leaf(A), inner]);
+        assert_eq!(expected, tree);
+    }
+
+    #[test]
+    fn test_parse_empty_unaugmented() {
+        // Without augmenting, the empty input is accepted by S -> ε, which has no value.
+        let grammar = create_unaugmented_grammar(());
+        let table = grammar.lalr1_table_by_lr1(&|_, _, _| 0).unwrap();
+        let parser = LR1Parser::new(&table);
+        assert_eq!(Err(ParseError::NoValue), parser.parse(vec![].into_iter()));
+
+        fn unit(_: Vec<()>) {}
+        let grammar = create_unaugmented_grammar(unit as fn(Vec<()>));
+        let table = grammar.lalr1_table_by_lr1(&|_, _, _| 0).unwrap();
+        let parser = LR1Parser::new(&table);
+        let tokens: Vec<Token<Terminal, ()>> = vec![];
+        let value = parser.evaluate(tokens.into_iter(), |_| ());
+        assert_eq!(Err(ParseError::NoValue), value);
+    }
+
+    fn create_grammar() -> Grammar<Terminal, Nonterminal, ()> {
+        // E -> S
+        create_unaugmented_grammar(()).augment(E, ()).unwrap()
+    }
+
+    fn create_unaugmented_grammar<A: Clone>(assoc: A) -> Grammar<Terminal, Nonterminal, A> {
+        let mut rules = BTreeMap::new();
+
+        // S -> ε
+        //    | a S
+        let empty = Rhs::new(vec![], assoc.clone());
+        let a_s = Rhs::new(vec![TT(A), NT(S)], assoc);
+        rules.insert(S, vec![empty, a_s]);
+
+        Grammar::new(S, rules).unwrap()
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Nonterminal {
+        E,
+        S,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Terminal {
+        A,
+    }
+}
+
+#[cfg(test)]
+mod test_arithmetic {
+    use super::{LR1Parser, ParseError, Token};
+    use crate::{
+        Grammar, Rhs,
+        Symbol::{Nonterminal as NT, Terminal as TT},
+    };
+
+    use std::collections::BTreeMap;
+
+    use Nonterminal::*;
+    use Terminal::*;
+
+    /// Semantic action of each production.
+    type Action = fn(Vec<i64>) -> i64;
+
+    #[test]
+    fn test_evaluate() {
+        let grammar = create_grammar();
+        let table = grammar.lalr1_table_by_lr1(&|_, _, _| 0).unwrap();
+        let parser = LR1Parser::new(&table);
+
+        // 2 + 3 * 4
+        let tokens = vec![num(2), op(Plus), num(3), op(Times), num(4)];
+        let value = parser.evaluate(tokens.into_iter(), Token::into_attribute);
+        assert_eq!(Ok(14), value);
+
+        // 2 * 3 + 4
+        let tokens = vec![num(2), op(Times), num(3), op(Plus), num(4)];
+        let value = parser.evaluate(tokens.into_iter(), Token::into_attribute);
+        assert_eq!(Ok(10), value);
+
+        // 2 + * 3
+        let tokens = vec![num(2), op(Plus), op(Times), num(3)];
+        let value = parser.evaluate(tokens.into_iter(), Token::into_attribute);
+        assert_eq!(Err(ParseError::UnexpectedToken(op(Times))), value);
+    }
+
+    fn create_grammar() -> Grammar<Terminal, Nonterminal, Action> {
+        let first: Action = |v| v[0];
+        let add: Action = |v| v[0] + v[2];
+        let multiply: Action = |v| v[0] * v[2];
+
+        let mut rules = BTreeMap::new();
+
+        // S -> E
+        rules.insert(S, vec![Rhs::new(vec![NT(E)], first)]);
+
+        // E -> E + T
+        //    | T
+        let sum = Rhs::new(vec![NT(E), TT(Plus), NT(T)], add);
+        rules.insert(E, vec![sum, Rhs::new(vec![NT(T)], first)]);
+
+        // T -> T * F
+        //    | F
+        let product = Rhs::new(vec![NT(T), TT(Times), NT(F)], multiply);
+        rules.insert(T, vec![product, Rhs::new(vec![NT(F)], first)]);
+
+        // F -> num
+        rules.insert(F, vec![Rhs::new(vec![TT(Num)], first)]);
+
+        Grammar::new(S, rules).unwrap()
+    }
+
+    /// Numbers carry their value as the attribute; operators carry a dummy value.
+    fn num(n: i64) -> Token<Terminal, i64> {
+        Token::new(Num, n)
+    }
+
+    fn op(t: Terminal) -> Token<Terminal, i64> {
+        Token::new(t, 0)
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Nonterminal {
+        S,
+        E,
+        T,
+        F,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Terminal {
+        Num,
+        Plus,
+        Times,
+    }
+}
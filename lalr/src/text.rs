@@ -0,0 +1,259 @@
+//! A portable text format for grammars.
+//!
+//! The first line names the starting nonterminal, and each following rule lists the alternative
+//! productions of a nonterminal in order:
+//!
+//! ```text
+//! %start E
+//!
+//! C -> x C
+//!    | y
+//!
+//! E -> S
+//! ```
+//!
+//! Symbols are separated by whitespace, and an empty body is written as `%empty`. A symbol in a
+//! body is a nonterminal if it is the left-hand side of some rule, and a terminal otherwise, so
+//! symbol names must not contain whitespace or `|`, and terminals must not share a name with a
+//! nonterminal. Blank lines are ignored.
+
+use crate::error::{Error, Result};
+use crate::grammar::{Grammar, Rhs, Symbol};
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+
+const START_DIRECTIVE: &str = "%start";
+const EMPTY_BODY: &str = "%empty";
+
+impl<T, N, A> Grammar<T, N, A>
+where
+    T: Display,
+    N: Display,
+{
+    /// Write the grammar in the text format. The semantic actions of the productions are not
+    /// included.
+    #[inline]
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} {}\n", START_DIRECTIVE, self.start);
+
+        for (lhs, rhs_set) in &self.rules {
+            let lhs = lhs.to_string();
+            write!(text, "\n{} ->", lhs).unwrap();
+
+            for (i, rhs) in rhs_set.iter().enumerate() {
+                // Align the alternatives under the arrow.
+                if i > 0 {
+                    write!(text, "\n{:width$}|", "", width = lhs.chars().count() + 2).unwrap();
+                }
+
+                if rhs.body.is_empty() {
+                    write!(text, " {}", EMPTY_BODY).unwrap();
+                }
+
+                for sy in &rhs.body {
+                    write!(text, " {}", sy).unwrap();
+                }
+            }
+
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+impl<T, N> Grammar<T, N, ()>
+where
+    T: FromStr + PartialEq,
+    N: FromStr + Ord,
+{
+    /// Read a grammar written in the text format.
+    ///
+    /// Returns [`Err`] if the text is malformed or a symbol fails to parse, or if the grammar
+    /// itself is invalid (see [`Grammar::new`]).
+    #[inline]
+    pub fn from_text(text: &str) -> Result<Self> {
+        let mut start = None;
+        // Alternatives for each nonterminal, in order of appearance.
+        let mut rule_texts: Vec<(&str, Vec<&str>)> = Vec::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let err = |reason: &str| Error::InvalidText(format!("line {}: {}", i + 1, reason));
+
+            if line.is_empty() {
+                continue;
+            } else if let Some(rest) = line.strip_prefix(START_DIRECTIVE) {
+                if start.is_some() {
+                    return Err(err("duplicate start directive"));
+                }
+                start = Some(rest.trim());
+            } else if let Some(rest) = line.strip_prefix('|') {
+                match rule_texts.last_mut() {
+                    Some((_, alternatives)) => alternatives.extend(split_alternatives(rest)),
+                    None => return Err(err("alternative outside of a rule")),
+                }
+            } else {
+                let mut split = line.splitn(2, "->");
+                let lhs = split.next().unwrap().trim();
+                let rest = split.next().ok_or_else(|| err("expected `->`"))?;
+                if lhs.is_empty() || lhs.contains(char::is_whitespace) {
+                    return Err(err("expected a single nonterminal before `->`"));
+                }
+
+                // An empty rest is a rule with no productions.
+                rule_texts.push((lhs, split_alternatives(rest).collect()));
+            }
+        }
+
+        let start = start.ok_or_else(|| Error::InvalidText("missing start directive".into()))?;
+        let start = Self::parse_symbol(start)?;
+
+        let nonterminals: BTreeSet<_> = rule_texts.iter().map(|(lhs, _)| *lhs).collect();
+        let mut rules: BTreeMap<N, Vec<_>> = BTreeMap::new();
+        for (lhs, alternatives) in rule_texts {
+            let rhs_set = alternatives
+                .into_iter()
+                .map(|alt| {
+                    let body = alt
+                        .split_whitespace()
+                        .filter(|&s| s != EMPTY_BODY)
+                        .map(|s| {
+                            if nonterminals.contains(s) {
+                                Self::parse_symbol(s).map(Symbol::Nonterminal)
+                            } else {
+                                Self::parse_symbol(s).map(Symbol::Terminal)
+                            }
+                        })
+                        .collect::<Result<_>>()?;
+                    Ok(Rhs::noop(body))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            rules
+                .entry(Self::parse_symbol(lhs)?)
+                .or_default()
+                .extend(rhs_set);
+        }
+
+        Self::new(start, rules)
+    }
+
+    #[inline]
+    fn parse_symbol<S: FromStr>(s: &str) -> Result<S> {
+        s.parse()
+            .map_err(|_| Error::InvalidText(format!("invalid symbol `{}`", s)))
+    }
+}
+
+/// Split the alternatives on a line, skipping empty ones, since an empty body is written as
+/// `%empty`.
+#[inline]
+fn split_alternatives(text: &str) -> impl Iterator<Item = &str> {
+    text.split('|').filter(|alt| !alt.trim().is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        Error, Grammar, Rhs,
+        Symbol::{Nonterminal as NT, Terminal as TT},
+    };
+
+    use std::collections::BTreeMap;
+
+    const TEXT: &str = "%start E
+
+E -> S
+
+L -> * R
+   | id
+
+R -> L
+
+S -> L = R
+   | R
+";
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(TEXT, create_grammar().to_text());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let grammar = create_grammar();
+        let parsed: Grammar<String, String, ()> = Grammar::from_text(&grammar.to_text()).unwrap();
+
+        assert_eq!(grammar.start, parsed.start);
+        assert_eq!(grammar.rules, parsed.rules);
+        assert_eq!(TEXT, parsed.to_text());
+    }
+
+    #[test]
+    fn test_from_text_empty_body() {
+        let text = "%start A\nA -> x A | %empty\nB ->";
+        let grammar: Grammar<String, String, ()> = Grammar::from_text(text).unwrap();
+
+        let a = &grammar.rules["A"];
+        assert_eq!(vec![TT("x".into()), NT("A".into())], a[0].body);
+        assert!(a[1].body.is_empty());
+        assert!(grammar.rules["B"].is_empty());
+        assert_eq!(
+            "%start A\n\nA -> x A\n   | %empty\n\nB ->\n",
+            grammar.to_text()
+        );
+    }
+
+    #[test]
+    fn test_from_text_continuation() {
+        // A continuation line may hold several alternatives, and empty ones are skipped as on
+        // the first line.
+        let text = "%start A\nA -> a\n | b | c\n |\n | d |";
+        let grammar: Grammar<String, String, ()> = Grammar::from_text(text).unwrap();
+
+        let bodies: Vec<_> = grammar.rules["A"].iter().map(|rhs| &rhs.body).collect();
+        let expected: Vec<_> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|&t| vec![TT(t.to_string())])
+            .collect();
+        assert_eq!(expected.iter().collect::<Vec<_>>(), bodies);
+    }
+
+    #[test]
+    fn test_from_text_errors() {
+        let parse = |text| Grammar::<String, String, ()>::from_text(text);
+
+        assert!(matches!(parse("A -> x"), Err(Error::InvalidText(_))));
+        assert!(matches!(parse("%start A\nA x"), Err(Error::InvalidText(_))));
+        assert!(matches!(parse("%start A\n| x"), Err(Error::InvalidText(_))));
+        assert!(matches!(parse("%start B\nA -> x"), Err(Error::NoStartRule)));
+    }
+
+    fn create_grammar() -> Grammar<String, String, ()> {
+        let s = |s: &str| s.to_string();
+        let mut rules = BTreeMap::new();
+
+        // E -> S
+        rules.insert(s("E"), vec![Rhs::noop(vec![NT(s("S"))])]);
+
+        // S -> L = R
+        //    | R
+        let assign = Rhs::noop(vec![NT(s("L")), TT(s("=")), NT(s("R"))]);
+        let r = Rhs::noop(vec![NT(s("R"))]);
+        rules.insert(s("S"), vec![assign, r]);
+
+        // L -> * R
+        //    | id
+        let deref = Rhs::noop(vec![TT(s("*")), NT(s("R"))]);
+        let id = Rhs::noop(vec![TT(s("id"))]);
+        rules.insert(s("L"), vec![deref, id]);
+
+        // R -> L
+        rules.insert(s("R"), vec![Rhs::noop(vec![NT(s("L"))])]);
+
+        Grammar::new(s("E"), rules).unwrap()
+    }
+}
@@ -0,0 +1,105 @@
+// Parser generated by `lalr`. Do not edit.
+//
+// Terminals:
+//     0: Num
+//     1: Plus
+//     2: Star
+//     3: LParen
+//     4: RParen
+//
+// Productions:
+//     0: Start -> Expr
+//     1: Expr -> Expr Plus Term
+//     2: Expr -> Term
+//     3: Term -> Term Star Factor
+//     4: Term -> Factor
+//     5: Factor -> LParen Expr RParen
+//     6: Factor -> Num
+
+#[derive(Clone, Copy)]
+enum Action {
+    Error,
+    Shift(usize),
+    Reduce(usize),
+    Accept,
+}
+
+const INITIAL: usize = 0;
+const ENDMARKER: usize = 5;
+
+static ACTION: [[Action; 6]; 12] = [
+    [Action::Shift(4), Action::Error, Action::Error, Action::Shift(5), Action::Error, Action::Error],
+    [Action::Error, Action::Shift(6), Action::Error, Action::Error, Action::Error, Action::Accept],
+    [Action::Error, Action::Reduce(2), Action::Shift(7), Action::Error, Action::Reduce(2), Action::Reduce(2)],
+    [Action::Error, Action::Reduce(4), Action::Reduce(4), Action::Error, Action::Reduce(4), Action::Reduce(4)],
+    [Action::Error, Action::Reduce(6), Action::Reduce(6), Action::Error, Action::Reduce(6), Action::Reduce(6)],
+    [Action::Shift(4), Action::Error, Action::Error, Action::Shift(5), Action::Error, Action::Error],
+    [Action::Shift(4), Action::Error, Action::Error, Action::Shift(5), Action::Error, Action::Error],
+    [Action::Shift(4), Action::Error, Action::Error, Action::Shift(5), Action::Error, Action::Error],
+    [Action::Error, Action::Shift(6), Action::Error, Action::Error, Action::Shift(11), Action::Error],
+    [Action::Error, Action::Reduce(1), Action::Shift(7), Action::Error, Action::Reduce(1), Action::Reduce(1)],
+    [Action::Error, Action::Reduce(3), Action::Reduce(3), Action::Error, Action::Reduce(3), Action::Reduce(3)],
+    [Action::Error, Action::Reduce(5), Action::Reduce(5), Action::Error, Action::Reduce(5), Action::Reduce(5)],
+];
+
+static GOTO: [[Option<usize>; 4]; 12] = [
+    [None, Some(1), Some(2), Some(3)],
+    [None, None, None, None],
+    [None, None, None, None],
+    [None, None, None, None],
+    [None, None, None, None],
+    [None, Some(8), Some(2), Some(3)],
+    [None, None, Some(9), Some(3)],
+    [None, None, None, Some(10)],
+    [None, None, None, None],
+    [None, None, None, None],
+    [None, None, None, None],
+    [None, None, None, None],
+];
+
+static PRODUCTIONS: [(usize, usize); 7] = [
+    (0, 1),
+    (1, 3),
+    (1, 1),
+    (2, 3),
+    (2, 1),
+    (3, 3),
+    (3, 1),
+];
+
+pub fn parse<V, I, F>(input: I, mut reduce: F) -> Result<V, Option<usize>>
+where
+    I: IntoIterator<Item = (usize, V)>,
+    F: FnMut(usize, Vec<V>) -> V,
+{
+    let mut input = input.into_iter().peekable();
+
+    let mut states = vec![INITIAL];
+    let mut values: Vec<V> = Vec::new();
+
+    loop {
+        let state = *states.last().unwrap();
+        let terminal = match input.peek() {
+            Some(&(terminal, _)) => terminal,
+            None => ENDMARKER,
+        };
+
+        match ACTION[state][terminal] {
+            Action::Shift(dest) => {
+                states.push(dest);
+                values.push(input.next().unwrap().1);
+            }
+            Action::Reduce(production) => {
+                let (lhs, len) = PRODUCTIONS[production];
+                states.truncate(states.len() - len);
+                let children = values.split_off(values.len() - len);
+
+                let top = *states.last().unwrap();
+                states.push(GOTO[top][lhs].unwrap());
+                values.push(reduce(production, children));
+            }
+            Action::Accept => return Ok(values.pop().unwrap()),
+            Action::Error => return Err(input.next().map(|(terminal, _)| terminal)),
+        }
+    }
+}
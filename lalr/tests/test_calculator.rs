@@ -0,0 +1,173 @@
+use lalr::{
+    parser::{LR1Parser, ParseError, ParseTree, TokenSource},
+    Grammar, Rhs,
+    Symbol::{Nonterminal as NT, Terminal as TT},
+};
+use llex::lexer;
+
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Terminal {
+    Num,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LParen,
+    RParen,
+    Error,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Nonterminal {
+    Start,
+    Expr,
+    Term,
+    Factor,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Num(i64),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LParen,
+    RParen,
+    Error,
+}
+
+impl TokenSource<Terminal> for Token {
+    #[inline]
+    fn terminal(&self) -> &Terminal {
+        match self {
+            Token::Num(_) => &Terminal::Num,
+            Token::Plus => &Terminal::Plus,
+            Token::Minus => &Terminal::Minus,
+            Token::Star => &Terminal::Star,
+            Token::Slash => &Terminal::Slash,
+            Token::LParen => &Terminal::LParen,
+            Token::RParen => &Terminal::RParen,
+            Token::Error => &Terminal::Error,
+        }
+    }
+}
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> Token, Token::Error;
+
+    r"\s" => None,
+    r"[0-9]+" => Some(Token::Num(text.parse().unwrap())),
+    r"\+" => Some(Token::Plus),
+    r"-" => Some(Token::Minus),
+    r"\*" => Some(Token::Star),
+    r"/" => Some(Token::Slash),
+    r"\(" => Some(Token::LParen),
+    r"\)" => Some(Token::RParen),
+}
+
+fn grammar() -> Grammar<Terminal, Nonterminal, ()> {
+    use Nonterminal::*;
+
+    let mut rules = BTreeMap::new();
+
+    // Start -> Expr
+    rules.insert(Start, vec![Rhs::noop(vec![NT(Expr)])]);
+
+    // Expr -> Expr + Term
+    //       | Expr - Term
+    //       | Term
+    rules.insert(
+        Expr,
+        vec![
+            Rhs::noop(vec![NT(Expr), TT(Terminal::Plus), NT(Term)]),
+            Rhs::noop(vec![NT(Expr), TT(Terminal::Minus), NT(Term)]),
+            Rhs::noop(vec![NT(Term)]),
+        ],
+    );
+
+    // Term -> Term * Factor
+    //       | Term / Factor
+    //       | Factor
+    rules.insert(
+        Term,
+        vec![
+            Rhs::noop(vec![NT(Term), TT(Terminal::Star), NT(Factor)]),
+            Rhs::noop(vec![NT(Term), TT(Terminal::Slash), NT(Factor)]),
+            Rhs::noop(vec![NT(Factor)]),
+        ],
+    );
+
+    // Factor -> ( Expr )
+    //         | num
+    rules.insert(
+        Factor,
+        vec![
+            Rhs::noop(vec![TT(Terminal::LParen), NT(Expr), TT(Terminal::RParen)]),
+            Rhs::noop(vec![TT(Terminal::Num)]),
+        ],
+    );
+
+    Grammar::new(Start, rules).unwrap()
+}
+
+fn eval(tree: &ParseTree<'_, Nonterminal, Token>) -> i64 {
+    use ParseTree::*;
+
+    match tree {
+        Leaf(Token::Num(n)) => *n,
+        Node(_, children) => match children.as_slice() {
+            [a] => eval(a),
+            [Leaf(Token::LParen), a, Leaf(Token::RParen)] => eval(a),
+            [a, Leaf(op), b] => {
+                let (a, b) = (eval(a), eval(b));
+                match op {
+                    Token::Plus => a + b,
+                    Token::Minus => a - b,
+                    Token::Star => a * b,
+                    Token::Slash => a / b,
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        },
+        Leaf(_) => unreachable!(),
+    }
+}
+
+fn calculate(input: &str) -> Result<i64, ParseError<Token>> {
+    let grammar = grammar();
+    let table = grammar.lalr1_table_by_lr1(&|_, _, _| 0).unwrap();
+    let parser = LR1Parser::new(&table);
+
+    let lexer = Lexer::new();
+    let tokens = lexer.stream(input.chars()).map(|item| item.token);
+    parser.parse_tokens(tokens).map(|tree| eval(&tree))
+}
+
+#[test]
+fn test_calculate() {
+    assert_eq!(Ok(14), calculate("2 + 3 * 4"));
+    assert_eq!(Ok(20), calculate("(2 + 3) * 4"));
+    assert_eq!(Ok(3), calculate("10 - 4 - 3"));
+    assert_eq!(Ok(7), calculate("100 / 5 / 2 - 3"));
+    assert_eq!(Ok(42), calculate("42"));
+}
+
+#[test]
+fn test_calculate_errors() {
+    assert_eq!(Err(ParseError::UnexpectedEndmarker), calculate("2 +"));
+    assert_eq!(Err(ParseError::UnexpectedEndmarker), calculate(""));
+    assert_eq!(
+        Err(ParseError::UnexpectedToken(Token::RParen)),
+        calculate("(1 + 2))")
+    );
+    assert_eq!(
+        Err(ParseError::UnexpectedToken(Token::Error)),
+        calculate("1 + x")
+    );
+}
@@ -0,0 +1,110 @@
+use lalr::{
+    Grammar, Rhs,
+    Symbol::{Nonterminal as NT, Terminal as TT},
+};
+
+use std::collections::BTreeMap;
+
+/// Parser generated from the grammar below by `LR1Table::codegen`.
+mod calculator {
+    include!("generated/calculator.rs");
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Terminal {
+    Num,
+    Plus,
+    Star,
+    LParen,
+    RParen,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Nonterminal {
+    Start,
+    Expr,
+    Term,
+    Factor,
+}
+
+fn grammar() -> Grammar<Terminal, Nonterminal, ()> {
+    use Nonterminal::*;
+    use Terminal::*;
+
+    let mut rules = BTreeMap::new();
+
+    // Start -> Expr
+    rules.insert(Start, vec![Rhs::noop(vec![NT(Expr)])]);
+
+    // Expr -> Expr + Term
+    //       | Term
+    let add = Rhs::noop(vec![NT(Expr), TT(Plus), NT(Term)]);
+    rules.insert(Expr, vec![add, Rhs::noop(vec![NT(Term)])]);
+
+    // Term -> Term * Factor
+    //       | Factor
+    let multiply = Rhs::noop(vec![NT(Term), TT(Star), NT(Factor)]);
+    rules.insert(Term, vec![multiply, Rhs::noop(vec![NT(Factor)])]);
+
+    // Factor -> ( Expr )
+    //         | num
+    let paren = Rhs::noop(vec![TT(LParen), NT(Expr), TT(RParen)]);
+    rules.insert(Factor, vec![paren, Rhs::noop(vec![TT(Num)])]);
+
+    Grammar::new(Start, rules).unwrap()
+}
+
+/// Evaluate the input with the generated parser. Each token is a terminal and a value, which is
+/// only meaningful for numbers.
+fn calculate(tokens: &[(Terminal, i64)]) -> Result<i64, Option<usize>> {
+    let input = tokens.iter().map(|(t, v)| (t.clone() as usize, *v));
+    calculator::parse(input, |production, values| match production {
+        // Expr -> Expr + Term
+        1 => values[0] + values[2],
+        // Term -> Term * Factor
+        3 => values[0] * values[2],
+        // Factor -> ( Expr )
+        5 => values[1],
+        // Single-symbol productions pass the value through.
+        _ => values[0],
+    })
+}
+
+#[test]
+fn test_codegen_up_to_date() {
+    let grammar = grammar();
+    let table = grammar.lalr1_table_by_lr1(&|_, _, _| 0).unwrap();
+
+    let expected = include_str!("generated/calculator.rs");
+    assert_eq!(expected, table.codegen(&grammar));
+}
+
+#[test]
+fn test_generated_parser() {
+    use Terminal::*;
+
+    // 2 + 3 * 4
+    let tokens = [(Num, 2), (Plus, 0), (Num, 3), (Star, 0), (Num, 4)];
+    assert_eq!(Ok(14), calculate(&tokens));
+
+    // (2 + 3) * 4
+    let tokens = [
+        (LParen, 0),
+        (Num, 2),
+        (Plus, 0),
+        (Num, 3),
+        (RParen, 0),
+        (Star, 0),
+        (Num, 4),
+    ];
+    assert_eq!(Ok(20), calculate(&tokens));
+
+    // 2 +
+    assert_eq!(Err(None), calculate(&[(Num, 2), (Plus, 0)]));
+
+    // 2 )
+    assert_eq!(
+        Err(Some(RParen as usize)),
+        calculate(&[(Num, 2), (RParen, 0)])
+    );
+}
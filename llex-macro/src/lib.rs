@@ -1,22 +1,27 @@
 #![deny(rust_2018_idioms)]
 #![deny(future_incompatible)]
 
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap, HashSet};
+use std::iter;
 
 use proc_macro2::{Span, TokenStream};
-use quote::{format_ident, quote};
+use quote::{quote, quote_spanned, ToTokens};
 use regexp2::{
     automata::{
         dfa::{DFAFromNFA, Transition},
-        DFA, NFA,
+        nfa::Transition as NFATransition,
+        table::Table,
+        Alphabet, DFA, NFA,
     },
     class::{CharClass, CharRange},
     parser::{NFAParser, Parser},
 };
 use syn::{
-    parenthesized,
+    braced, parenthesized,
     parse::{Parse, ParseStream},
-    parse_macro_input, token, Expr, Ident, LitStr, Token, Type, Visibility,
+    parse_macro_input, parse_quote, token, Attribute, Expr, ExprPath, Ident, Lit, LitStr, Meta,
+    MetaNameValue, Token, Type, Variant, Visibility,
 };
 
 #[proc_macro]
@@ -35,65 +40,379 @@ fn lexer_(parsed: Lexer) -> Result<TokenStream, TokenStream> {
         fn_vis,
         fn_name,
         span_id,
+        pos_id,
+        groups_id,
         return_type,
+        token_enum,
         error_variant,
-        rules,
+        eof_variant,
+        error_type,
+        resync,
+        mut rules,
     } = parsed;
 
-    let (nfa, action_mapping) = parse_combined_nfa(&rules)?;
-    let DFAFromNFA { dfa, nfa_mapping }: DFAFromNFA<_> = nfa.into();
+    // The pattern and action of each rule as declared, before the token enum rewrites any actions.
+    let rule_list: Vec<_> = rules
+        .iter()
+        .map(|rule| {
+            let pattern = rule.pattern();
+            let action = rule.action.to_token_stream().to_string();
+            quote!((#pattern, #action))
+        })
+        .collect();
+
+    let token_enum = token_enum.map(|token_enum| {
+        token_enum.generate(
+            &error_variant,
+            eof_variant.as_ref(),
+            error_type.is_some(),
+            &mut rules,
+        )
+    });
 
-    let dfa_rebuilt = dfa_rebuilt(&dfa);
+    // With an error type, the lexer produces results: the error variant is an error, and the
+    // tokens of actions and the end-of-input token are wrapped in `Ok`.
+    let (item_type, error_item, eof_item): (Type, _, _) = match &error_type {
+        Some(error_type) => (
+            parse_quote!(std::result::Result<#return_type, #error_type>),
+            quote!(std::result::Result::Err(#error_variant)),
+            eof_variant
+                .as_ref()
+                .map(|eof_variant| quote!(std::result::Result::Ok(#eof_variant))),
+        ),
+        None => (
+            return_type.clone(),
+            error_variant.to_token_stream(),
+            eof_variant.as_ref().map(ToTokens::to_token_stream),
+        ),
+    };
 
-    let dfa_actions: Vec<_> = nfa_mapping
+    // Rules with the same pattern as another of higher precedence can never match, so they are
+    // left out of the NFA and reported.
+    let shadowed = shadowed_rules(&rules);
+    let shadowed_warnings: Vec<_> = shadowed
+        .iter()
+        .map(|&i| span_warning(rules[i].regexp.span(), SHADOWED_RULE_WARNING))
+        .collect();
+
+    let (nfa, action_mapping, nullable) = parse_combined_nfa(&rules, &shadowed)?;
+
+    // Rules that match the empty string produce a token without consuming any input, so the
+    // lexer would repeat it forever where nothing else matches. They are reported.
+    let nullable_warnings: Vec<_> = nullable
+        .iter()
+        .map(|&i| span_warning(rules[i].regexp.span(), EMPTY_MATCH_WARNING))
+        .collect();
+    // Partition the characters into the classes that the rules never distinguish between, so
+    // that the DFA has transitions on class numbers and the ranges of each class are emitted once.
+    let alphabet = Alphabet::of_nfa(&nfa);
+    let DFAFromNFA { dfa, nfa_mapping } = DFAFromNFA::with_alphabet(&nfa, &alphabet);
+
+    // Each final DFA state executes the action of the highest precedence rule it accepts.
+    let state_actions: HashMap<_, _> = nfa_mapping
         .iter()
         .filter_map(|(dfa_state, nfa_states)| {
             action_mapping
                 .iter()
                 .filter(|(nfa_state, _)| nfa_states.contains(nfa_state))
                 .min_by_key(|(_, (_, precedence))| precedence)
-                .map(|(_, (action, _))| (dfa_state, action))
+                .map(|(_, action)| (*dfa_state, *action))
         })
         .collect();
 
-    let action_fns: Vec<_> = dfa_actions
+    // Merge equivalent states, such as the final states of keywords, but keep apart states that
+    // execute different actions.
+    let (dfa, state_mapping) =
+        dfa.minimize_by(|state| state_actions.get(&state).map(|(_, precedence)| *precedence));
+    let dfa_actions: BTreeMap<_, _> = state_actions
         .iter()
-        .map(|(dfa_state, action)| {
-            let fn_name = format_ident!("action_{}", dfa_state);
-            quote! {
-                #[allow(unused)]
-                #[allow(clippy::unnecessary_wraps)]
-                #[inline]
-                fn #fn_name(#span_id: &str) -> std::option::Option<#return_type> {
-                    #action
+        .map(|(state, (action, _))| (state_mapping[*state], *action))
+        .collect();
+    // The rule, by declaration order, whose action each final DFA state executes.
+    let dfa_rules: BTreeMap<_, _> = state_actions
+        .iter()
+        .map(|(state, (_, (_, order)))| (state_mapping[*state], *order))
+        .collect();
+
+    // Number the final states first, so that the action of a final state is found by indexing a
+    // table with the state rather than by matching on it.
+    let (dfa, relabel) = finals_first(&dfa);
+    let dfa_actions: BTreeMap<_, _> = dfa_actions
+        .into_iter()
+        .map(|(state, action)| (relabel[state], action))
+        .collect();
+    let dfa_rules: BTreeMap<_, _> = dfa_rules
+        .into_iter()
+        .map(|(state, order)| (relabel[state], order))
+        .collect();
+
+    let dfa_rebuilt = dfa_rebuilt(&dfa, &alphabet);
+
+    // Emit the end-of-input token if one is given.
+    let eof_fn = eof_item.map(|eof_item| {
+        quote! {
+            #[inline]
+            fn eof(&self) -> std::option::Option<#item_type> {
+                std::option::Option::Some(#eof_item)
+            }
+        }
+    });
+
+    // Skip the characters after an offending one until the resync predicate holds, if given, so
+    // that a run of unmatched input produces a single error.
+    let resync_loop = resync.map(|resync| {
+        quote! {
+            while let std::option::Option::Some(&c) = input.peek() {
+                if (#resync)(c) {
+                    break;
                 }
+                chars.push(c);
+                input.next();
             }
-        })
-        .collect();
+        }
+    });
+
+    // Actions take the position of the match as a second argument if it is bound.
+    let pos_param = pos_id
+        .as_ref()
+        .map(|pos_id| quote!(, #pos_id: std::ops::Range<usize>));
+    let pos_ty = pos_id.as_ref().map(|_| quote!(, std::ops::Range<usize>));
+    let pos_arg = pos_id.as_ref().map(|_| quote!(, pos));
+    let error_pos = pos_id
+        .as_ref()
+        .map(|_| quote!(let pos = offset..offset + chars.len();));
+    let match_pos = pos_id
+        .as_ref()
+        .map(|_| quote!(let pos = offset + m.start..offset + m.end;));
+
+    // Actions take the substrings matched by the groups of their pattern as a third argument if it
+    // is bound. Groups are matched by a regex for each rule, which is only compiled if needed.
+    let groups_ty = groups_id
+        .as_ref()
+        .map(|_| quote!(, std::vec::Vec<std::option::Option<std::string::String>>));
+    let groups_field = groups_id
+        .as_ref()
+        .map(|_| quote!(captures: std::vec::Vec<::llex::regexp2::Regex>,));
+    let groups_init = groups_id.as_ref().map(|_| {
+        let patterns = rules.iter().map(|rule| rule.regexp.value());
+        quote! {
+            let captures = std::vec![
+                #( ::llex::regexp2::Regex::new(#patterns).unwrap() ),*
+            ];
+        }
+    });
+    let groups_ctor = groups_id.as_ref().map(|_| quote!(captures,));
+    let groups_fn = groups_id.as_ref().map(|_| {
+        quote! {
+            /// Return the substrings matched by the groups of the given rule in a match of it,
+            /// with [`None`] for groups that did not participate.
+            #[inline]
+            fn capture_groups(&self, rule: usize, span: &str) -> std::vec::Vec<std::option::Option<std::string::String>> {
+                // The rule matches the whole span, which is therefore its leftmost-longest match.
+                match self.captures[rule].captures(span) {
+                    std::option::Option::Some(caps) => caps
+                        .iter()
+                        .skip(1)
+                        .map(|m| m.map(|m| m.span.iter().collect()))
+                        .collect(),
+                    std::option::Option::None => std::vec::Vec::new(),
+                }
+            }
+        }
+    });
 
-    let action_match: Vec<_> = dfa_actions
+    // Rules with trailing context match their pattern and context together, and the context is
+    // split off of the match by a regex for each of them.
+    let context_rules: Vec<_> = rules
         .iter()
-        .map(|(dfa_state, _)| {
-            let fn_call = format_ident!("action_{}", dfa_state);
-            quote!(#dfa_state => #fn_call(&span))
+        .enumerate()
+        .filter_map(|(order, rule)| {
+            rule.context
+                .as_ref()
+                .map(|context| (order, &rule.regexp, context))
         })
         .collect();
+    let contexts_field = if context_rules.is_empty() {
+        None
+    } else {
+        Some(quote!(contexts: std::vec::Vec<(::llex::regexp2::Regex, ::llex::regexp2::Regex)>,))
+    };
+    let contexts_init = contexts_field.as_ref().map(|_| {
+        let pairs = context_rules.iter().map(|(_, regexp, context)| {
+            let (regexp, context) = (regexp.value(), context.value());
+            quote!((::llex::regexp2::Regex::new(#regexp).unwrap(), ::llex::regexp2::Regex::new(#context).unwrap()))
+        });
+        quote! {
+            let contexts = std::vec![ #( #pairs ),* ];
+        }
+    });
+    let contexts_ctor = contexts_field.as_ref().map(|_| quote!(contexts,));
+    let trailing_context_fn = contexts_field.as_ref().map(|_| {
+        let arms = dfa_rules.iter().filter_map(|(dfa_state, order)| {
+            context_rules
+                .iter()
+                .position(|(context_order, ..)| context_order == order)
+                .map(|i| quote!(#dfa_state => &self.contexts[#i]))
+        });
+        quote! {
+            #[inline]
+            fn trailing_context(&self, state: usize, span: &[char]) -> usize {
+                let (pattern, context) = match state {
+                    #( #arms, )*
+                    _ => return 0,
+                };
+
+                // Split the span after the longest prefix matching the pattern such that the rest
+                // matches the context.
+                (0..=span.len())
+                    .rev()
+                    .find(|&i| {
+                        let (head, tail) = span.split_at(i);
+                        pattern.is_full_match(&head.iter().collect::<std::string::String>())
+                            && context.is_full_match(&tail.iter().collect::<std::string::String>())
+                    })
+                    .map_or(0, |i| span.len() - i)
+            }
+        }
+    });
+
+    // The actions of the final states, in order. Non-capturing closures coerce to the function
+    // pointers of the table.
+    let pos_closure = pos_id.as_ref().map(|pos_id| quote!(, #pos_id));
+    let groups_closure = groups_id.as_ref().map(|groups_id| quote!(, #groups_id));
+    let action_count = dfa_actions.len();
+    let actions = dfa_actions.values().map(
+        |action| quote!(|#span_id #pos_closure #groups_closure| ::llex::Tokens::from(#action)),
+    );
+
+    // The rule, by declaration order, of each final state, to match the groups of its pattern
+    // and to report which rule matched.
+    let rules = dfa_rules.values();
+    let rules_const = quote!(const RULES: [usize; #action_count] = [ #( #rules ),* ];);
+    let rules_table = groups_id.as_ref().map(|_| &rules_const);
+    let groups_arg = groups_id
+        .as_ref()
+        .map(|_| quote!(, self.capture_groups(RULES[state], &span)));
 
     Ok(quote! {
+        #token_enum
+
+        #( #shadowed_warnings )*
+        #( #nullable_warnings )*
+
+        /// A lexer built from its rules once, by [`Self::new`]. Lexing only reads it, so it is
+        /// `Send` and `Sync` and may be shared, e.g. behind an `Arc`, to tokenize several inputs
+        /// concurrently.
         #[derive(Debug, Clone)]
         #struct_vis struct #struct_name {
             dfa: ::llex::stream::LexerDFA,
+            #groups_field
+            #contexts_field
         }
 
+        const _: fn() = || {
+            fn assert_send_sync<T: std::marker::Send + std::marker::Sync>() {}
+            assert_send_sync::<#struct_name>();
+        };
+
         impl #struct_name {
             #[inline]
             #struct_vis fn new() -> Self {
                 let dfa = #dfa_rebuilt;
-                Self { dfa }
+                #groups_init
+                #contexts_init
+                Self { dfa, #groups_ctor #contexts_ctor }
             }
 
+            #groups_fn
+
+            /// Return the DFA that matches the rules of the lexer. Each final state corresponds to
+            /// a rule, so the DFA accepts exactly the inputs that some rule matches in full.
             #[inline]
-            #fn_vis fn #fn_name<'a, I>(&self, input: I) -> ::llex::LexerStream<#return_type, &#struct_name, I>
+            #struct_vis fn dfa(&self) -> &::llex::stream::LexerDFA {
+                &self.dfa
+            }
+
+            /// Return the pattern and action of each rule, in declaration order. Patterns with
+            /// trailing context are written as `pattern/context`, and actions as the tokens of
+            /// their expressions.
+            #[inline]
+            #struct_vis fn rules() -> &'static [(&'static str, &'static str)] {
+                &[ #( #rule_list ),* ]
+            }
+
+            /// Match the input at byte index `pos` and return the tokens of the match along with
+            /// the byte index just past it, so that callers keeping the whole input need not copy
+            /// the rest of it. At the end of input, returns [`None`] and `pos` itself; the
+            /// end-of-input token, if any, is not produced.
+            ///
+            /// The characters before `pos` are counted for the positions bound by actions; use
+            /// [`Self::advance_with_rule`] to keep the count in a [`::llex::Cursor`] instead.
+            ///
+            /// Panics if `pos` is past the end of the input or not on a character boundary.
+            #[inline]
+            #struct_vis fn advance_at(&self, input: &str, pos: usize) -> (std::option::Option<::llex::Tokens<#item_type>>, usize) {
+                let (matched, next) = self.advance_with_rule(input, ::llex::Cursor::at(input, pos));
+                (matched.map(|(tokens, _)| tokens), next.byte)
+            }
+
+            /// Match the input at the cursor `pos` as [`Self::advance_at`] does, returning the
+            /// cursor past the match, and also return the index in [`Self::rules`] of the rule
+            /// that matched, e.g. to highlight tokens by rule. The index is [`None`] for the error
+            /// variant, which no rule produces.
+            ///
+            /// Panics if `pos.byte` is past the end of the input or not on a character boundary.
+            #[inline]
+            #struct_vis fn advance_with_rule(&self, input: &str, pos: ::llex::Cursor) -> (std::option::Option<(::llex::Tokens<#item_type>, std::option::Option<usize>)>, ::llex::Cursor) {
+                #rules_const
+
+                let tail = match input.get(pos.byte..) {
+                    std::option::Option::Some(tail) => tail,
+                    std::option::Option::None => panic!("byte index {} is not a character boundary of the input", pos.byte),
+                };
+                let offset = pos.char;
+                let mut rest = tail.chars().peekable();
+                let (tokens, m, rule) = match self.dfa.find_mut(&mut rest) {
+                    std::option::Option::Some((m, state)) => {
+                        let (tokens, m, _) = ::llex::merge::LexerRules::complete_match(self, state, m, offset);
+                        (tokens, m, std::option::Option::Some(RULES[state]))
+                    }
+                    // No rule matches: produce the error variant as when tokenizing.
+                    std::option::Option::None => match Self::no_match(&mut rest, offset) {
+                        std::option::Option::Some((tokens, m, _)) => (tokens, m, std::option::Option::None),
+                        std::option::Option::None => return (std::option::Option::None, pos),
+                    },
+                };
+
+                let len: usize = m.span.iter().map(|c| c.len_utf8()).sum();
+                let next = ::llex::Cursor::new(pos.byte + len, pos.char + m.span.len());
+                (std::option::Option::Some((tokens, rule)), next)
+            }
+
+            // Where no rule matches, consume the offending character (and any following ones up
+            // to the resync point) and produce the error variant for them.
+            #[inline]
+            fn no_match<I>(input: &mut std::iter::Peekable<I>, offset: usize) -> std::option::Option<(::llex::Tokens<#item_type>, ::llex::regexp2::automata::Match<char>, std::vec::Vec<char>)>
+            where
+                I: std::iter::Iterator<Item = char>,
+            {
+                #[allow(unused)]
+                #[inline]
+                fn error_action(#span_id: &str #pos_param) -> #item_type {
+                    #error_item
+                }
+
+                let mut chars = vec![input.next()?];
+                #resync_loop
+
+                let span: std::string::String = chars.iter().collect();
+                #error_pos
+                let m = ::llex::regexp2::automata::Match::new(0, chars.len(), chars);
+                std::option::Option::Some((::llex::Tokens::One(error_action(&span #pos_arg)), m, std::vec::Vec::new()))
+            }
+
+            #[inline]
+            #fn_vis fn #fn_name<'a, I>(&self, input: I) -> ::llex::LexerStream<#item_type, &#struct_name, I>
             where
                 I: std::iter::Iterator<Item = char>,
             {
@@ -101,50 +420,69 @@ fn lexer_(parsed: Lexer) -> Result<TokenStream, TokenStream> {
             }
         }
 
-        impl ::llex::stream::LexerDFAMatcher<#return_type> for #struct_name {
+        impl ::llex::stream::LexerDFAMatcher<#item_type> for #struct_name {
             #[inline]
-            fn tokenize<'a, I>(&self, input: &mut std::iter::Peekable<I>) -> std::option::Option<(#return_type, ::llex::regexp2::automata::Match<char>)>
+            fn tokenize<'a, I>(&self, input: &mut std::iter::Peekable<I>, offset: usize) -> std::option::Option<(::llex::Tokens<#item_type>, ::llex::regexp2::automata::Match<char>, std::vec::Vec<char>)>
             where
                 I: std::iter::Iterator<Item = char>,
             {
-                #(
-                    #action_fns
-                )*
-
                 // Step through DFA to the find the longest match.
                 let (m, final_state) = match self.dfa.find_mut(input) {
                     std::option::Option::Some(m) => m,
-                    std::option::Option::None => {
-                        input.next();
-                        return std::option::Option::Some((#error_variant, ::llex::regexp2::automata::Match::new(0, 0, vec![])));
-                    },
+                    std::option::Option::None => return Self::no_match(input, offset),
                 };
 
                 // Execute the action expression corresponding to the final state.
-                let span: std::string::String = m.span.iter().cloned().collect();
-                let token_op = match final_state {
-                    #( #action_match ),*,
-                    // Catch-all branch should never execute?
-                    _ => std::unreachable!(),
-                };
+                std::option::Option::Some(::llex::merge::LexerRules::complete_match(self, final_state, m, offset))
+            }
 
-                token_op.map(|t| (t, m))
+            #eof_fn
+        }
+
+        impl ::llex::merge::LexerRules<#item_type> for #struct_name {
+            #[inline]
+            fn dfa(&self) -> &::llex::stream::LexerDFA {
+                &self.dfa
             }
+
+            #[allow(unused_variables)]
+            #[inline]
+            fn act(&self, state: usize, m: &::llex::regexp2::automata::Match<char>, offset: usize) -> ::llex::Tokens<#item_type> {
+                type Action = fn(&str #pos_ty #groups_ty) -> ::llex::Tokens<#item_type>;
+                const ACTIONS: [Action; #action_count] = [ #( #actions ),* ];
+                #rules_table
+
+                let span: std::string::String = m.span.iter().cloned().collect();
+                #match_pos
+                ACTIONS[state](&span #pos_arg #groups_arg)
+            }
+
+            #trailing_context_fn
         }
 
-        impl ::llex::stream::LexerDFAMatcher<#return_type> for &#struct_name {
+        impl ::llex::stream::LexerDFAMatcher<#item_type> for &#struct_name {
             #[inline]
-            fn tokenize<I>(&self, input: &mut std::iter::Peekable<I>) -> std::option::Option<(#return_type, ::llex::regexp2::automata::Match<char>)>
+            fn tokenize<I>(&self, input: &mut std::iter::Peekable<I>, offset: usize) -> std::option::Option<(::llex::Tokens<#item_type>, ::llex::regexp2::automata::Match<char>, std::vec::Vec<char>)>
             where
                 I: std::iter::Iterator<Item = char>,
             {
-                (*self).tokenize(input)
+                (*self).tokenize(input, offset)
+            }
+
+            #[inline]
+            fn eof(&self) -> std::option::Option<#item_type> {
+                (*self).eof()
             }
         }
 
     })
 }
 
+mod kw {
+    syn::custom_keyword!(error);
+    syn::custom_keyword!(resync);
+}
+
 struct Lexer {
     struct_vis: Option<Visibility>,
     struct_name: Ident,
@@ -152,8 +490,22 @@ struct Lexer {
     fn_name: Ident,
 
     span_id: Ident,
+    /// Identifier bound to the range of character positions of the match, if any.
+    pos_id: Option<Ident>,
+    /// Identifier bound to the substrings matched by the groups of the pattern, if any. It may
+    /// only be given after the position identifier.
+    groups_id: Option<Ident>,
     return_type: Type,
+    token_enum: Option<TokenEnum>,
     error_variant: Expr,
+    /// Token emitted once at the end of input, if any.
+    eof_variant: Option<Expr>,
+    /// Type of the errors reported by actions, if any. The lexer then produces results of tokens
+    /// and errors, and the error variant is of this type.
+    error_type: Option<Type>,
+    /// Predicate on characters at which lexing resumes after an error, if any. Otherwise, only
+    /// the offending character is skipped.
+    resync: Option<Expr>,
 
     rules: Vec<Rule>,
 }
@@ -177,33 +529,88 @@ impl Parse for Lexer {
         let fn_name = input.parse()?;
         token!(;);
 
-        let span_id = {
+        let (span_id, pos_id, groups_id) = {
             let inner;
             parenthesized!(inner in input);
             let span_id = inner.parse()?;
+            // The position and groups identifiers are optional.
+            let pos_id = if inner.parse::<Token![,]>().is_ok() {
+                Some(inner.parse()?)
+            } else {
+                None
+            };
+            let groups_id = if pos_id.is_some() && inner.parse::<Token![,]>().is_ok() {
+                Some(inner.parse()?)
+            } else {
+                None
+            };
             if !inner.is_empty() {
                 return Err(inner.error("Unexpected token after token string identifier"));
             }
-            span_id
+            (span_id, pos_id, groups_id)
         };
 
         token!(->);
-        let return_type = input.parse()?;
+        // The return type is either an existing type, or an enum declaration to be generated.
+        let (return_type, token_enum) = {
+            let fork = input.fork();
+            fork.parse::<Visibility>()?;
+            if fork.peek(Token![enum]) {
+                let token_enum: TokenEnum = input.parse()?;
+                let name = &token_enum.name;
+                (parse_quote!(#name), Some(token_enum))
+            } else {
+                (input.parse()?, None)
+            }
+        };
         token!(,);
 
         let error_variant = input.parse()?;
+        // The end-of-input token is optional.
+        let eof_variant = if input.parse::<Token![,]>().is_ok() {
+            Some(input.parse()?)
+        } else {
+            None
+        };
         token!(;);
 
+        // The error type is optional.
+        let error_type = if input.peek(kw::error) {
+            input.parse::<kw::error>()?;
+            let error_type = input.parse()?;
+            token!(;);
+            Some(error_type)
+        } else {
+            None
+        };
+
+        // The resync predicate is optional.
+        let resync = if input.peek(kw::resync) {
+            input.parse::<kw::resync>()?;
+            let resync = input.parse()?;
+            token!(;);
+            Some(resync)
+        } else {
+            None
+        };
+
         let rules = {
             let mut rules = Vec::new();
             while !input.is_empty() {
+                let priority = Rule::parse_priority(input)?;
                 let regexp = input.parse()?;
+                // The trailing context is optional.
+                let context = if input.parse::<Token![/]>().is_ok() {
+                    Some(input.parse()?)
+                } else {
+                    None
+                };
                 input.parse::<Token![=>]>()?;
 
                 let optional_comma = input.peek(token::Brace);
 
                 let action = input.parse()?;
-                let rule = Rule::new(regexp, action);
+                let rule = Rule::new(regexp, context, action, priority);
 
                 match input.parse::<Token![,]>() {
                     Ok(_) => {}
@@ -225,8 +632,14 @@ impl Parse for Lexer {
             fn_vis,
             fn_name,
             span_id,
+            pos_id,
+            groups_id,
             return_type,
+            token_enum,
             error_variant,
+            eof_variant,
+            error_type,
+            resync,
             rules,
         })
     }
@@ -234,95 +647,400 @@ impl Parse for Lexer {
 
 struct Rule {
     regexp: LitStr,
+    /// Pattern that must follow a match of the rule, but is not part of it, if any.
+    context: Option<LitStr>,
     action: Expr,
+    /// Priority of the rule when several rules match the same longest input; higher priorities
+    /// win, and rules of equal priority fall back to declaration order.
+    priority: u32,
 }
 
 impl Rule {
     #[inline]
-    fn new(regexp: LitStr, action: Expr) -> Self {
-        Self { regexp, action }
+    fn new(regexp: LitStr, context: Option<LitStr>, action: Expr, priority: u32) -> Self {
+        Self {
+            regexp,
+            context,
+            action,
+            priority,
+        }
+    }
+
+    /// Return the pattern of the rule, followed by `/` and its trailing context if any.
+    #[inline]
+    fn pattern(&self) -> String {
+        match &self.context {
+            Some(context) => format!("{}/{}", self.regexp.value(), context.value()),
+            None => self.regexp.value(),
+        }
+    }
+
+    /// Parse the optional `#[priority = N]` attribute preceding a rule, defaulting to 0.
+    #[inline]
+    fn parse_priority(input: ParseStream<'_>) -> syn::Result<u32> {
+        let mut priority = 0;
+        for attr in input.call(Attribute::parse_outer)? {
+            if !attr.path.is_ident("priority") {
+                return Err(syn::Error::new_spanned(attr, "Unknown rule attribute"));
+            }
+
+            priority = match attr.parse_meta()? {
+                Meta::NameValue(MetaNameValue {
+                    lit: Lit::Int(lit), ..
+                }) => lit.base10_parse()?,
+                meta => {
+                    return Err(syn::Error::new_spanned(
+                        meta,
+                        "Expected `#[priority = N]` with a non-negative integer",
+                    ))
+                }
+            };
+        }
+
+        Ok(priority)
+    }
+}
+
+/// Declaration of a token enum to be generated by the macro. Variants may be given explicitly in
+/// braces, or declared inline by rules whose action is a bare `#name::#variant` path.
+struct TokenEnum {
+    vis: Visibility,
+    name: Ident,
+    variants: Vec<Variant>,
+}
+
+impl Parse for TokenEnum {
+    #[inline]
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        input.parse::<Token![enum]>()?;
+        let name = input.parse()?;
+
+        let variants = if input.peek(token::Brace) {
+            let inner;
+            braced!(inner in input);
+            inner
+                .parse_terminated::<_, Token![,]>(Variant::parse)?
+                .into_iter()
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            vis,
+            name,
+            variants,
+        })
+    }
+}
+
+impl TokenEnum {
+    /// Collect the unit variants declared inline by the error and end-of-input variants and rule
+    /// actions, rewrite those actions to return the variant, and generate the enum definition.
+    /// If the lexer has an error type, the rewritten actions return the variant in `Ok`.
+    #[inline]
+    fn generate(
+        mut self,
+        error_variant: &Expr,
+        eof_variant: Option<&Expr>,
+        fallible: bool,
+        rules: &mut [Rule],
+    ) -> TokenStream {
+        for expr in iter::once(error_variant).chain(eof_variant) {
+            if let Some(variant) = self.inline_variant(expr) {
+                self.declare(variant.clone());
+            }
+        }
+
+        for rule in rules.iter_mut() {
+            if let Some(variant) = self.inline_variant(&rule.action) {
+                self.declare(variant.clone());
+
+                let action = &rule.action;
+                rule.action = if fallible {
+                    parse_quote!(std::result::Result::Ok(std::option::Option::Some(#action)))
+                } else {
+                    parse_quote!(std::option::Option::Some(#action))
+                };
+            }
+        }
+
+        let Self {
+            vis,
+            name,
+            variants,
+        } = self;
+        quote! {
+            #[derive(Debug, Clone, PartialEq)]
+            #vis enum #name {
+                #( #variants ),*
+            }
+        }
+    }
+
+    /// Declare a unit variant if no variant with the same name exists.
+    #[inline]
+    fn declare(&mut self, ident: Ident) {
+        if !self.variants.iter().any(|v| v.ident == ident) {
+            self.variants.push(parse_quote!(#ident));
+        }
+    }
+
+    /// Retrieve X for an expression of the form `#name::X`.
+    #[inline]
+    fn inline_variant<'a>(&self, expr: &'a Expr) -> Option<&'a Ident> {
+        match expr {
+            Expr::Path(ExprPath {
+                qself: None, path, ..
+            }) if path.leading_colon.is_none()
+                && path.segments.len() == 2
+                && path.segments.iter().all(|seg| seg.arguments.is_empty())
+                && path.segments[0].ident == self.name =>
+            {
+                Some(&path.segments[1].ident)
+            }
+            _ => None,
+        }
     }
 }
 
 const INVALID_REGEXP_ERROR: &str = "invalid regular expression";
+const EMPTY_REGEXP_ERROR: &str = "regular expression can never match anything";
+const SHADOWED_RULE_WARNING: &str =
+    "rule is never used: an identical pattern has a rule of higher precedence";
+const EMPTY_MATCH_WARNING: &str =
+    "rule matches the empty string: its empty matches produce tokens without consuming input";
 
-// Parse the rules into a single NFA and a map of final states to action expressions.
+// Find the rules whose pattern is identical to that of a rule of higher precedence. Such rules
+// can never match, since the other rule always matches the same input.
+#[inline]
+fn shadowed_rules(rules: &[Rule]) -> BTreeSet<usize> {
+    let mut winners: HashMap<_, usize> = HashMap::new();
+    let mut shadowed = BTreeSet::new();
+    for (i, rule) in rules.iter().enumerate() {
+        let key = (
+            rule.regexp.value(),
+            rule.context.as_ref().map(LitStr::value),
+        );
+        match winners.entry(key) {
+            Entry::Occupied(mut entry) => {
+                // Higher priorities win, and then earlier rules.
+                let winner = *entry.get();
+                if rule.priority > rules[winner].priority {
+                    entry.insert(i);
+                    shadowed.insert(winner);
+                } else {
+                    shadowed.insert(i);
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(i);
+            }
+        }
+    }
+
+    shadowed
+}
+
+// Parse the rules into a single NFA and a map of final states to action expressions, each paired
+// with its precedence. Lower precedences win: rules are ordered by descending priority first, and
+// then by declaration order. Also return the rules that match the empty string.
 #[inline]
 #[allow(clippy::type_complexity)]
-fn parse_combined_nfa(
-    rules: &[Rule],
-) -> Result<(NFA<CharClass>, HashMap<usize, (&Expr, usize)>), TokenStream> {
+fn parse_combined_nfa<'r>(
+    rules: &'r [Rule],
+    shadowed: &BTreeSet<usize>,
+) -> Result<
+    (
+        NFA<CharClass>,
+        HashMap<usize, (&'r Expr, (Reverse<u32>, usize))>,
+        BTreeSet<usize>,
+    ),
+    TokenStream,
+> {
     let nfa_parser = NFAParser::new();
-    // Parse regular expression strings into NFAs.
+    let parse = |regexp: &LitStr, expr: &str| {
+        nfa_parser
+            .parse(expr)
+            .map_err(|e| span_error(regexp.span(), &format!("{}: {}", INVALID_REGEXP_ERROR, e)))
+    };
+
+    // Parse regular expression strings into NFAs, skipping shadowed rules. A rule with trailing
+    // context matches its pattern followed by the context, and matches the empty string if its
+    // pattern does, since the context is not consumed.
     let nfa_sub: Vec<_> = rules
         .iter()
+        .enumerate()
+        .filter(|(order, _)| !shadowed.contains(order))
         .map(
-            |Rule { regexp, action }| match nfa_parser.parse(&regexp.value()) {
-                // Throw errors if failed to parse.
-                Ok(op) => match op {
-                    Some(n) => Ok(Some((n, action))),
-                    // None returned means error.
-                    None => Err(span_error(regexp.span(), INVALID_REGEXP_ERROR)),
+            |(
+                order,
+                Rule {
+                    regexp,
+                    context,
+                    action,
+                    priority,
                 },
-                Err(e) => Err(span_error(
-                    regexp.span(),
-                    &format!("{}: {}", INVALID_REGEXP_ERROR, e),
-                )),
+            )| {
+                let pattern = parse(regexp, &regexp.value())?;
+                let nullable = matches_empty(&pattern);
+                let n = match context {
+                    Some(context) => {
+                        // Report errors in the pattern and context separately.
+                        parse(context, &context.value())?;
+                        parse(
+                            regexp,
+                            &format!("({})({})", regexp.value(), context.value()),
+                        )?
+                    }
+                    None => pattern,
+                };
+
+                if matches_nothing(&n) {
+                    Err(span_error(regexp.span(), EMPTY_REGEXP_ERROR))
+                } else {
+                    Ok((n, action, (Reverse(*priority), order), nullable))
+                }
             },
         )
         .collect::<Result<_, _>>()?;
 
-    let nfa_sub: Vec<_> = nfa_sub.into_iter().filter_map(|nfa| nfa).collect();
+    let nullable = nfa_sub
+        .iter()
+        .filter(|(.., nullable)| *nullable)
+        .map(|(_, _, (_, order), _)| *order)
+        .collect();
 
     // Combine NFAs into a single NFA.
     let mut action_mapping = HashMap::new();
     let mut nfa = NFA::new();
     let mut offset = nfa.total_states;
-    for (precedence, (sub, action)) in nfa_sub.iter().enumerate() {
+    for (sub, action, precedence, _) in nfa_sub.iter() {
         NFA::copy_into(&mut nfa, sub);
         nfa.add_epsilon_transition(nfa.initial_state, sub.initial_state + offset);
         // Map new, offsetted final states to their original action.
         for sub_final in sub.final_states.iter() {
             nfa.final_states.insert(*sub_final + offset);
-            action_mapping.insert(*sub_final + offset, (*action, precedence));
+            action_mapping.insert(*sub_final + offset, (*action, *precedence));
         }
 
         offset += sub.total_states;
     }
 
-    Ok((nfa, action_mapping))
+    Ok((nfa, action_mapping, nullable))
+}
+
+// Determine if the NFA accepts the empty string, i.e. if a final state is reachable from its
+// initial state by epsilon transitions alone.
+#[inline]
+fn matches_empty(nfa: &NFA<CharClass>) -> bool {
+    nfa.epsilon_closure(nfa.initial_state)
+        .iter()
+        .any(|state| nfa.is_final_state(state))
+}
+
+// Determine if no final state of the NFA is reachable from its initial state, taking only epsilon
+// transitions and transitions on non-empty character classes. A rule with such an NFA would only
+// compile into unreachable DFA states.
+#[inline]
+fn matches_nothing(nfa: &NFA<CharClass>) -> bool {
+    let mut visited = HashSet::new();
+    visited.insert(nfa.initial_state);
+
+    let mut stack = vec![nfa.initial_state];
+    while let Some(state) = stack.pop() {
+        if nfa.is_final_state(&state) {
+            return false;
+        }
+
+        let dests = nfa
+            .transitions_from(state)
+            .into_iter()
+            .filter(|(t, _)| match t {
+                NFATransition::Some(class) => !class.is_empty(),
+                NFATransition::Epsilon => true,
+            })
+            .flat_map(|(_, dest)| dest.iter());
+        for &dest in dests {
+            if visited.insert(dest) {
+                stack.push(dest);
+            }
+        }
+    }
+
+    true
+}
+
+// Relabel the states of the DFA so that its final states are labeled first, keeping the order of
+// the states otherwise. Returns the relabeled DFA and the new label of each state.
+#[inline]
+fn finals_first(dfa: &DFA<usize>) -> (DFA<usize>, Vec<usize>) {
+    let (finals, others): (Vec<_>, Vec<_>) =
+        (0..dfa.total_states).partition(|state| dfa.is_final_state(state));
+
+    let mut relabel = vec![0; dfa.total_states];
+    for (new, &old) in finals.iter().chain(&others).enumerate() {
+        relabel[old] = new;
+    }
+
+    let mut relabeled = DFA::new();
+    relabeled.initial_state = relabel[dfa.initial_state];
+    relabeled.total_states = dfa.total_states;
+    relabeled.final_states = (0..finals.len()).collect();
+    relabeled.transition = Table::from_entries(
+        dfa.transition
+            .into_iter()
+            .map(|(&src, tr, &dest)| (relabel[src], tr.clone(), relabel[dest])),
+    );
+
+    (relabeled, relabel)
 }
 
-fn dfa_rebuilt(dfa: &DFA<CharClass>) -> TokenStream {
+// Emit the DFA with transitions on the classes of the alphabet. The ranges of each class are
+// emitted once, and the classes on which a state moves to the same state are joined into one
+// transition.
+fn dfa_rebuilt(dfa: &DFA<usize>, alphabet: &Alphabet<CharClass>) -> TokenStream {
     let initial_state = dfa.initial_state;
     let total_states = dfa.total_states;
     let final_states: Vec<_> = dfa.final_states.iter().collect();
-    let transition_sets: Vec<_> = dfa
-        .transition
-        .clone()
-        .into_iter()
-        .map(|(src, Transition(tr), dest)| {
-            let ranges: Vec<_> = tr
-                .ranges
-                .iter()
-                .map(|CharRange { start, end }| quote!(::llex::regexp2::class::CharRange::new(#start, #end)))
-                .collect();
-            quote! {
-                dfa.transition.set(#src, ::llex::regexp2::automata::dfa::Transition(vec![ #( #ranges ),* ].into()), #dest);
-            }
-        })
-        .collect();
+
+    let classes = alphabet.classes().iter().map(|class| {
+        let ranges = class
+            .iter()
+            .map(|CharRange { start, end }| quote!((#start, #end)));
+        quote!(&[ #( #ranges ),* ])
+    });
+
+    let mut joined: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+    for (&src, &Transition(class), &dest) in &dfa.transition {
+        joined.entry((src, dest)).or_default().push(class);
+    }
+    let transition_entries = joined.into_iter().map(|((src, dest), mut ids)| {
+        ids.sort_unstable();
+        quote! {
+            (#src, ::llex::regexp2::automata::dfa::Transition(class(&[ #( #ids ),* ])), #dest)
+        }
+    });
 
     quote! {
         {
+            const CLASSES: &[&[(char, char)]] = &[ #( #classes ),* ];
+            let class = |ids: &[usize]| {
+                ::llex::regexp2::class::CharClass::from_ranges(
+                    ids.iter().flat_map(|&id| CLASSES[id].iter().copied()),
+                )
+            };
+
             let mut dfa = ::llex::regexp2::automata::DFA::new();
             dfa.initial_state = #initial_state;
             dfa.total_states = #total_states;
             dfa.final_states = std::collections::HashSet::new();
             dfa.final_states.extend(&[ #( #final_states ),* ]);
 
-            dfa.transition = ::llex::regexp2::automata::table::Table::new();
-            #( #transition_sets )*
+            dfa.transition = ::llex::regexp2::automata::table::Table::from_entries(vec![
+                #( #transition_entries ),*
+            ]);
 
             dfa
         }
@@ -332,3 +1050,109 @@ fn dfa_rebuilt(dfa: &DFA<CharClass>) -> TokenStream {
 fn span_error(span: Span, message: &str) -> TokenStream {
     syn::Error::new(span, message).to_compile_error()
 }
+
+// Emit a warning at the span. Proc macros cannot emit warnings directly on stable, so this uses a
+// deprecated item instead.
+fn span_warning(span: Span, message: &str) -> TokenStream {
+    quote_spanned! {span=>
+        const _: () = {
+            #[deprecated(note = #message)]
+            struct Warning;
+            let _ = Warning;
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        lexer_, Lexer, EMPTY_MATCH_WARNING, EMPTY_REGEXP_ERROR, INVALID_REGEXP_ERROR,
+        SHADOWED_RULE_WARNING,
+    };
+
+    // Expand a lexer with the given rules, which must be rejected, and return the error.
+    fn expand_err(rules: &str) -> String {
+        let src = format!(
+            "struct Lexer; fn stream; (text) -> Token, Token::Error; {}",
+            rules
+        );
+        let parsed: Lexer = syn::parse_str(&src).unwrap();
+        lexer_(parsed).unwrap_err().to_string()
+    }
+
+    // Expand a lexer with the given rules.
+    fn expand_rules(rules: &str) -> String {
+        let src = format!(
+            "struct Lexer; fn stream; (text) -> enum Token {{ Number(String) }}, Token::Error; {}",
+            rules
+        );
+        let parsed: Lexer = syn::parse_str(&src).unwrap();
+        lexer_(parsed).unwrap().to_string()
+    }
+
+    // Expand a lexer with a single rule, given by its pattern and optional trailing context.
+    fn expand(pattern: &str, context: Option<&str>) -> String {
+        let context = context.map_or(String::new(), |context| format!("/ r\"{}\"", context));
+        expand_rules(&format!(
+            "r\"{}\" {} => Some(Token::Number(text.to_string())),",
+            pattern, context
+        ))
+    }
+
+    #[test]
+    fn test_empty_match_warning() {
+        assert!(expand("[0-9]*", None).contains(EMPTY_MATCH_WARNING));
+        assert!(!expand("[0-9]+", None).contains(EMPTY_MATCH_WARNING));
+
+        // The trailing context is not consumed, so only the pattern must match the empty string.
+        assert!(expand("[0-9]*", Some("x")).contains(EMPTY_MATCH_WARNING));
+        assert!(!expand("[0-9]+", Some("x*")).contains(EMPTY_MATCH_WARNING));
+    }
+
+    #[test]
+    fn test_rule_errors() {
+        assert!(expand_err(r#"r"(a" => None,"#).contains(INVALID_REGEXP_ERROR));
+        assert!(expand_err(r#"r"a" / r"b)" => None,"#).contains(INVALID_REGEXP_ERROR));
+
+        // Patterns that can never match are rejected, even in an alternative or with context.
+        assert!(expand_err(r#"r"[^\s\S]" => None,"#).contains(EMPTY_REGEXP_ERROR));
+        assert!(expand_err(r#"r"a[^\s\S]" => None,"#).contains(EMPTY_REGEXP_ERROR));
+        assert!(expand_err(r#"r"a" / r"[^\s\S]" => None,"#).contains(EMPTY_REGEXP_ERROR));
+        assert!(!expand_rules(r#"r"a|[^\s\S]" => None,"#).contains(EMPTY_REGEXP_ERROR));
+    }
+
+    #[test]
+    fn test_shadowed_rule_warning() {
+        let warnings = |rules| expand_rules(rules).matches(SHADOWED_RULE_WARNING).count();
+
+        assert_eq!(1, warnings(r#"r"a" => None, r"a" => None,"#));
+        assert_eq!(2, warnings(r#"r"a" => None, r"a" => None, r"a" => None,"#));
+        // A higher priority shadows the earlier rule instead.
+        assert_eq!(
+            1,
+            warnings(r#"r"a" => None, #[priority = 1] r"a" => None,"#)
+        );
+        // Patterns are compared as written, not by the language they describe.
+        assert_eq!(0, warnings(r#"r"a" => None, r"(a)" => None,"#));
+    }
+
+    #[test]
+    fn test_priority_attribute() {
+        let parse = |attr: &str| {
+            let src = format!(
+                "struct Lexer; fn stream; (text) -> Token, Token::Error; {} r\"a\" => None,",
+                attr
+            );
+            syn::parse_str::<Lexer>(&src).map(|parsed| parsed.rules[0].priority)
+        };
+
+        assert_eq!(0, parse("").unwrap());
+        assert_eq!(3, parse("#[priority = 3]").unwrap());
+        // The last of several attributes applies.
+        assert_eq!(2, parse("#[priority = 1] #[priority = 2]").unwrap());
+
+        assert!(parse("#[prio = 1]").is_err());
+        assert!(parse("#[priority]").is_err());
+        assert!(parse("#[priority = \"1\"]").is_err());
+    }
+}
@@ -0,0 +1,48 @@
+// The generated #struct_name::advance_at matches the input at a byte index and returns the tokens
+// of the match along with the byte index past it, without copying the rest of the input. Callers
+// that keep the whole input can drive tokenization with positions alone. The byte index must be on
+// a character boundary.
+//
+// Positions bound by actions count characters, as with #struct_name::#fn_name, so advance_at counts
+// the characters before the byte index. #struct_name::advance_with_rule instead takes and returns
+// a cursor holding both indexes, so that lexing a whole input by cursor takes linear time.
+//
+//
+// GENERATED:
+//
+// impl #struct_name {
+//     #struct_visibility fn advance_at(&self, input: &str, pos: usize)
+//         -> (Option<Tokens<#token_type>>, usize) { ... }
+// }
+
+use std::ops::Range;
+
+use llex::{lexer, Tokens};
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text, pos) -> pub enum Token {
+        Word(String, Range<usize>),
+        Unknown(Range<usize>),
+    }, Token::Unknown(pos);
+
+    r"\s" => None,
+    r"[a-zé]+" => Some(Token::Word(text.to_string(), pos)),
+}
+
+// The input string to pass into the lexer, with multi-byte characters.
+const INPUT_STR: &str = "café ? olé";
+
+fn main() {
+    let lexer = Lexer::new();
+
+    // Tokenize the whole input by position only.
+    let mut pos = 0;
+    while let (Some(tokens), next) = lexer.advance_at(INPUT_STR, pos) {
+        if let Tokens::One(t) = tokens {
+            println!("{:?} {:?}", t, &INPUT_STR[pos..next]);
+        }
+        pos = next;
+    }
+}
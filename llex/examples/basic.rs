@@ -1,12 +1,27 @@
 // lexer! creates a struct with visibility (#struct_visibility) and name (#struct_name). It defines
 // the method #struct_name::#fn_name (e.g. Lexer::stream) to return an iterator for tokens
 // (LexerStream<#token_type>) parsed from the given input. See below example. On error (such as
-// where no tokens can be produced from the remaining non-empty input), the error variant
-// (#error_variant) is returned.
+// where no tokens can be produced from the remaining non-empty input), the offending character is
+// consumed and the error variant (#error_variant) is returned, with its position in the match.
+// The error variant may be any expression of #token_type, with #span_var bound to the offending
+// character (see `examples/errors.rs`). See `examples/positions.rs` to also bind the position of
+// the match, and `examples/groups.rs` to bind the substrings matched by groups of the pattern.
+// Actions may report errors of their own if the lexer has an error type (see
+// `examples/fallible.rs`).
+// Lexers with the same token type may be merged into one (see `examples/merge.rs`).
+// Input may be lexed again after an edit without lexing all of it (see `examples/incremental.rs`).
+// Upcoming tokens may be looked at before consuming them (see `examples/lookahead.rs`).
 //
-// Define the regular expression and their corresponding actions, highest precedence first.  See
+// Define the regular expression and their corresponding actions, highest precedence first (see
+// `examples/priority.rs` to override the order with explicit priorities, and
+// `examples/keywords.rs` for keywords that are also identifiers).  See
 // `regexp2` crate for supported regular expression syntax. The action expressions must return
-// Option<#token_type>.
+// Option<#token_type>, or Vec<#token_type> to emit several tokens (see `examples/multiple.rs`).
+// A regular expression that can never match anything, such as `[^\d\D]`, is a compile error.
+// A rule whose regular expression matches the empty string, such as `[0-9]*`, is warned about,
+// since its empty matches would produce tokens without consuming any input, even if it has
+// trailing context.
+// A rule may only match when followed by trailing context (see `examples/context.rs`).
 //
 //
 // FORMAT:
@@ -24,6 +39,22 @@
 //     #struct_visibility fn stream(&self, input: &str) -> Option<LexerItem<#token_type>> {
 //         ...
 //     }
+//
+//     // The DFA built from the rules, e.g. to inspect it in tests.
+//     #struct_visibility fn dfa(&self) -> &LexerDFA { ... }
+//
+//     // The pattern and action of each rule (see `examples/rules.rs`).
+//     #struct_visibility fn rules() -> &'static [(&'static str, &'static str)] { ... }
+//
+//     // Match at a byte index of the input and return the byte index past the match (see
+//     // `examples/advance.rs`).
+//     #struct_visibility fn advance_at(&self, input: &str, pos: usize)
+//         -> (Option<Tokens<#token_type>>, usize) { ... }
+//
+//     // Match as advance_at does, but at a cursor, and also return the index of the rule that
+//     // matched (see `examples/rules.rs`).
+//     #struct_visibility fn advance_with_rule(&self, input: &str, pos: Cursor)
+//         -> (Option<(Tokens<#token_type>, Option<usize>)>, Cursor) { ... }
 // }
 
 use std::fmt;
@@ -123,6 +154,7 @@ pub enum Token {
 
 fn main() {
     let lexer = Lexer::new();
+
     let chars = INPUT_STR.chars();
     let tokens = lexer.stream(chars);
 
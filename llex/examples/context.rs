@@ -0,0 +1,42 @@
+// A rule may be given trailing context, as in flex: `#regexp / #context` matches #regexp only when
+// it is followed by #context. The context takes part in finding the longest match, but is not
+// part of the token: it is excluded from the span and position of the match, and lexed again as
+// the beginning of the next token. If several splits of the match are possible, the pattern takes
+// the longest prefix such that the context matches the rest.
+//
+// Below, "12" is lexed as pixels since it is followed by "px", which is then lexed as an
+// identifier.
+//
+//
+// FORMAT:
+//
+// #regexp / #context => #action,
+
+use llex::lexer;
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text, pos) -> pub enum Token {
+        Pixels(u32),
+        Number(u32),
+        Ident(String),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"[0-9]+" / r"px" => Some(Token::Pixels(text.parse().unwrap())),
+    r"[0-9]+" => Some(Token::Number(text.parse().unwrap())),
+    r"[a-z]+" => Some(Token::Ident(text.to_string())),
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "12px 3em 5pxx";
+
+fn main() {
+    let lexer = Lexer::new();
+
+    // The context is not part of the match.
+    for t in lexer.stream(INPUT_STR.chars()) {
+        println!("({:?} {:?})", t.token, t.m.range());
+    }
+}
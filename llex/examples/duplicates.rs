@@ -0,0 +1,45 @@
+// A rule whose regular expression is identical to that of a rule of higher precedence can never
+// match, since the other rule always matches the same input. Such a rule is left out of the
+// lexer, and a warning is emitted at its regular expression:
+//
+//     warning: use of deprecated unit struct `_::Warning`: rule is never used: an identical
+//     pattern has a rule of higher precedence
+//
+// Precedence is decided as usual (see `examples/priority.rs`), so a later duplicate with a higher
+// priority shadows an earlier one instead.
+//
+// The warning is a deprecation warning, and so may be silenced with #[allow(deprecated)], as is
+// done in this example.
+
+#![allow(deprecated)]
+
+use llex::lexer;
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text) -> pub enum Token {
+        Ident(String),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"=" => Token::Equals,
+    // Shadowed by the rule above.
+    r"=" => Token::Assign,
+    r"let" => Token::KeywordLet,
+    r"[a-z]+" => Some(Token::Ident(text.to_string())),
+    // Shadows the rule above.
+    #[priority = 1]
+    r"[a-z]+" => Some(Token::Ident(text.to_uppercase())),
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "let x = y";
+
+fn main() {
+    let lexer = Lexer::new();
+    let tokens: Vec<_> = lexer.stream(INPUT_STR.chars()).map(|t| t.token).collect();
+
+    // The priority also lets identifiers win over the keyword.
+    println!("{:?}", tokens);
+}
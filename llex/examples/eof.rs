@@ -0,0 +1,36 @@
+// lexer! can emit a token once at the end of input, so that a parser always sees an explicit end
+// token. Give the end-of-input variant (#eof_variant) after the error variant; like the error
+// variant, it is declared inline if it is a bare path and the token enum is generated. The stream
+// yields the end-of-input token after all other tokens, and None from then on.
+//
+//
+// FORMAT:
+//
+// #struct_visibility struct #struct_name;
+// #fn_visibility fn #fn_name;
+// (#span_var) -> #token_type, #error_variant, #eof_variant;
+
+use llex::lexer;
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text) -> pub enum Token {
+        Integer(i64),
+    }, Token::Error, Token::Eof;
+
+    r"\s" => None,
+    r"[0-9]+" => Some(Token::Integer(text.parse().unwrap())),
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "1 2 ";
+
+fn main() {
+    let lexer = Lexer::new();
+
+    // The end-of-input token is positioned at the end of the input.
+    for t in lexer.stream(INPUT_STR.chars()) {
+        println!("({:?} {:?})", t.token, t.m.range());
+    }
+}
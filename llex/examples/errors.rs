@@ -0,0 +1,29 @@
+// When no rule matches the remaining input, the lexer consumes the offending character and
+// returns the error variant in its place. The match of the returned item holds the position of
+// the character, and the error variant expression can use #span_var, which is bound to the
+// offending character, to carry it in the token. See `examples/resync.rs` to skip a whole run of
+// offending characters instead.
+
+use llex::lexer;
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text) -> pub enum Token {
+        Integer(i64),
+        Unknown(char),
+    }, Token::Unknown(text.chars().next().unwrap());
+
+    r"\s" => None,
+    r"[0-9]+" => Some(Token::Integer(text.parse().unwrap())),
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "12 @";
+
+fn main() {
+    let lexer = Lexer::new();
+    for t in lexer.stream(INPUT_STR.chars()) {
+        println!("({:?} {}:{})", t.token, t.m.start, t.m.end - 1);
+    }
+}
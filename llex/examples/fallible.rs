@@ -0,0 +1,48 @@
+// An error type may be given after the error variant, with `error #error_type;`. The lexer then
+// produces Result<#token_type, #error_type>, and the actions return their tokens in a Result
+// instead, so that an action may report an error, such as a number that overflows its token. Bare
+// enum variants declared inline by actions are still accepted. The error variant is then an
+// expression of #error_type, which is produced as an error when no rule matches.
+//
+//
+// FORMAT:
+//
+// (#span_var) -> #token_type, #error_variant;
+// error #error_type;
+//
+// #regexp => Ok(Some(#token)),
+// #regexp => Err(#error),
+
+use llex::lexer;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexError {
+    Overflow(String),
+    Unknown(char),
+}
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text) -> pub enum Token {
+        Integer(u8),
+    }, LexError::Unknown(text.chars().next().unwrap()), Token::Eof;
+    error LexError;
+
+    r"\s" => Ok(None),
+    r"[0-9]+" => text
+        .parse()
+        .map(|n| Some(Token::Integer(n)))
+        .map_err(|_| LexError::Overflow(text.to_string())),
+    r"\+" => Token::Plus,
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "12 + 300 @";
+
+fn main() {
+    let lexer = Lexer::new();
+    let tokens: Vec<_> = lexer.stream(INPUT_STR.chars()).map(|t| t.token).collect();
+
+    println!("{:?}", tokens);
+}
@@ -0,0 +1,53 @@
+// lexer! can also generate the token type itself. In place of #token_type, declare an enum with
+// visibility (#enum_visibility) and name (#enum_name), optionally followed by a braced list of
+// variants. Rules whose action is a bare `#enum_name::#variant` path declare that unit variant
+// inline and always produce it; other actions are expressions returning Option<#enum_name> as
+// usual. The error variant, if a bare path, is declared inline too.
+//
+// The generated enum derives Debug, Clone, and PartialEq.
+//
+//
+// FORMAT:
+//
+// #struct_visibility struct #struct_name;
+// #fn_visibility fn #fn_name;
+// (#span_var) -> #enum_visibility enum #enum_name { ... }, #error_variant;
+
+use llex::lexer;
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text) -> pub enum Token {
+        Ident(String),
+        Integer(i64),
+    }, Token::Error;
+    // Generated:
+    //
+    //     #[derive(Debug, Clone, PartialEq)]
+    //     pub enum Token {
+    //         Ident(String),
+    //         Integer(i64),
+    //         Error,
+    //         KeywordIf,
+    //         ...
+    //     }
+    //
+
+    r"\s" => None,
+    r"if" => Token::KeywordIf,
+    r"else" => Token::KeywordElse,
+    r"=" => Token::Equals,
+    r";" => Token::Semicolon,
+    r"[A-Za-z_][A-Za-z0-9_]*" => Some(Token::Ident(text.to_string())),
+    r"[0-9]+" => Some(Token::Integer(text.parse().unwrap())),
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "if x = 1; else y = 2;";
+
+fn main() {
+    let lexer = Lexer::new();
+    let tokens: Vec<_> = lexer.stream(INPUT_STR.chars()).map(|t| t.token).collect();
+    println!("{:?}", tokens);
+}
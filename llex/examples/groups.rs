@@ -0,0 +1,45 @@
+// A third identifier (#groups_var) may be given after #pos_var to bind the substrings matched by
+// the parenthesized groups of the rule's pattern, numbered from 1 as in `regexp2::Regex`, so that
+// actions can extract parts of the match. Groups that did not participate in the match are None.
+// Groups are matched by a slower engine than the lexer's DFA, and only once a rule has matched.
+//
+//
+// FORMAT:
+//
+// #struct_visibility struct #struct_name;
+// #fn_visibility fn #fn_name;
+// (#span_var, #pos_var, #groups_var) -> #token_type, #error_variant;
+
+use llex::lexer;
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text, pos, groups) -> pub enum Token {
+        Number(u32),
+        Ident(String),
+        Unknown(String),
+    }, Token::Unknown(text.to_string());
+
+    r"\s" => None,
+    r"0x([0-9A-Fa-f]+)" => {
+        let digits = groups[0].as_ref().unwrap();
+        Some(Token::Number(u32::from_str_radix(digits, 16).unwrap()))
+    }
+    r"[0-9]+" => Some(Token::Number(text.parse().unwrap())),
+    r"([a-z]+)(_([0-9]+))?" => {
+        // The suffix group is None if the identifier has no suffix.
+        let suffix = groups[2].as_deref().unwrap_or("");
+        Some(Token::Ident(format!("{}{}", groups[0].as_ref().unwrap(), suffix)))
+    }
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "0x1F 12 0xff abc_1 x ?";
+
+fn main() {
+    let lexer = Lexer::new();
+    let tokens: Vec<_> = lexer.stream(INPUT_STR.chars()).map(|t| t.token).collect();
+
+    println!("{:?}", tokens);
+}
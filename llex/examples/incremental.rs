@@ -0,0 +1,57 @@
+// llex::relex lexes input again after an edit, such as a keystroke in an editor, without lexing
+// all of it. Given the tokens of the old input and the edit, it restarts lexing shortly before the
+// edit and reuses the old tokens once a new token starts where an old one did after the edit. The
+// result is the same as lexing the whole new input.
+//
+//
+// USAGE:
+//
+// let edit = llex::Edit::new(#old_range, #new_len);
+// let tokens = llex::relex(&lexer, &old_tokens, &edit, #new_input);
+
+use llex::{lexer, relex, Edit, LexerItem};
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text) -> pub enum Token {
+        Ident(String),
+        Number(i64),
+    }, Token::Error, Token::Eof;
+
+    r"\s" => None,
+    r"[a-z]+" => Some(Token::Ident(text.to_string())),
+    r"[0-9]+" => Some(Token::Number(text.parse().unwrap())),
+    r"=" => Token::Assign,
+    r"\+" => Token::Plus,
+    r";" => Token::Semicolon,
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "a = 1 + bc; d = 23; e = f + 4;";
+
+fn lex(lexer: &Lexer, input: &str) -> Vec<LexerItem<Token>> {
+    lexer.stream(input.chars()).collect()
+}
+
+fn positioned(items: &[LexerItem<Token>]) -> Vec<(Token, std::ops::Range<usize>)> {
+    items
+        .iter()
+        .map(|item| (item.token.clone(), item.m.range()))
+        .collect()
+}
+
+fn main() {
+    let lexer = Lexer::new();
+    let old = lex(&lexer, INPUT_STR);
+
+    // Replace the "23" at 16..18 with "2"; only the tokens around it are lexed again.
+    let relexed = relex(
+        &lexer,
+        &old,
+        &Edit::new(16..18, 1),
+        "a = 1 + bc; d = 2; e = f + 4;",
+    );
+
+    println!("{:?}", positioned(&relexed));
+}
@@ -0,0 +1,42 @@
+// Keywords need no lookahead to keep them from swallowing the beginning of identifiers. Declare
+// the keyword rules before a rule for identifiers that also matches the keywords: the longest
+// match wins, so `printf` is matched in full by the identifier rule, and only on exactly `print`
+// do both rules match the same input, where the keyword rule wins by declaration order (or by
+// priority, see `examples/priority.rs`).
+//
+// This is what a negative lookahead such as `print(?![A-Za-z0-9_])`, or `print\b`, would express.
+// It relies on the identifier rule matching every character that may follow a keyword within a
+// word; a keyword followed by a character that no rule continues, such as `print$`, is still
+// lexed as the keyword.
+//
+//
+// FORMAT:
+//
+// #keyword_regexp => #keyword_action,
+// #identifier_regexp => #identifier_action,
+
+use llex::lexer;
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text) -> pub enum Token {
+        Ident(String),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"print" => Token::KeywordPrint,
+    r"[A-Za-z_][A-Za-z0-9_]*" => Some(Token::Ident(text.to_string())),
+    r"\(" => Token::LeftParenthesis,
+    r"\)" => Token::RightParenthesis,
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "print printf(print_2) print() prin";
+
+fn main() {
+    let lexer = Lexer::new();
+    let tokens: Vec<_> = lexer.stream(INPUT_STR.chars()).map(|t| t.token).collect();
+
+    println!("{:?}", tokens);
+}
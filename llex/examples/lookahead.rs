@@ -0,0 +1,41 @@
+// LexerStream::lookahead wraps a stream in a PeekableLexer, which buffers upcoming tokens so that
+// a parser may look ahead before consuming them. PeekableLexer::peek returns the next token,
+// PeekableLexer::peek2 the one after it, and PeekableLexer::peek_nth any token ahead; none of
+// them consume a token, so peeking again returns the same one, and PeekableLexer::next yields the
+// buffered tokens in order before lexing more input. Past the end of input, peeking returns None.
+
+use llex::{lexer, LexerItem};
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text) -> pub enum Token {
+        Ident(String),
+        Integer(i64),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"[0-9]+" => Some(Token::Integer(text.parse().unwrap())),
+    r"[a-z]+" => Some(Token::Ident(text.to_string())),
+    r"=" => Token::Equals,
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "x = 1 y";
+
+// The token of a peeked item.
+fn token(item: Option<&LexerItem<Token>>) -> Option<Token> {
+    item.map(|t| t.token.clone())
+}
+
+fn main() {
+    let lexer = Lexer::new();
+    let mut stream = lexer.stream(INPUT_STR.chars()).lookahead();
+
+    // Peeking does not consume, so the peeked tokens are still yielded in order.
+    println!("{:?} {:?}", token(stream.peek()), token(stream.peek2()));
+    for t in stream {
+        print!("{:?} ", t.token);
+    }
+    println!();
+}
@@ -0,0 +1,65 @@
+// Lexers with the same token type may be merged into one that matches the rules of both, e.g. to
+// extend a core lexer. The longest match of either lexer wins, and the first lexer wins when both
+// match the same longest input, as if its rules had been declared first. Errors and the
+// end-of-input token come from the first lexer.
+//
+// Below, "else" is a keyword even though the operators lexer also matches it, but "x12" is a
+// register since the operators lexer matches more of it than the identifier rule.
+//
+//
+// GENERATED:
+//
+// impl llex::LexerRules<#token_type> for #struct_name { ... }
+//
+// USAGE:
+//
+// let merged: llex::Merged<First, Second> = First::new().merge(Second::new());
+// merged.stream(input)
+
+use llex::{lexer, LexerRules};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Ident(String),
+    Register(u32),
+    KeywordIf,
+    KeywordElse,
+    Assign,
+    Equals,
+    Plus,
+    Error,
+}
+
+lexer! {
+    pub struct Keywords;
+    pub fn stream;
+    (text) -> Token, Token::Error;
+
+    r"\s" => None,
+    r"if" => Some(Token::KeywordIf),
+    r"else" => Some(Token::KeywordElse),
+    r"[a-z]+" => Some(Token::Ident(text.to_string())),
+}
+
+lexer! {
+    pub struct Operators;
+    pub fn stream;
+    (text) -> Token, Token::Error;
+
+    r"=" => Some(Token::Assign),
+    r"==" => Some(Token::Equals),
+    r"\+" => Some(Token::Plus),
+    r"x[0-9]+" => Some(Token::Register(text[1..].parse().unwrap())),
+    // Loses to the keywords lexer, which also matches "else".
+    r"else" => Some(Token::Ident(text.to_string())),
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "if x12 == y else x = x+1 $";
+
+fn main() {
+    let merged = Keywords::new().merge(Operators::new());
+    let tokens: Vec<_> = merged.stream(INPUT_STR.chars()).map(|t| t.token).collect();
+
+    println!("{:?}", tokens);
+}
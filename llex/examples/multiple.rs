@@ -0,0 +1,33 @@
+// An action may return Vec<#token_type> instead of Option<#token_type> to emit several tokens
+// for a single match. The tokens are emitted in order before any more input is consumed, and all
+// of them carry the position of the match.
+//
+// Here `>>` is lexed as two `>` tokens, as is needed when closing nested generics.
+
+use llex::lexer;
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text) -> pub enum Token {
+        Ident(String),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"<" => Token::Less,
+    r">" => Token::Greater,
+    r">>" => vec![Token::Greater, Token::Greater],
+    r"[A-Za-z][A-Za-z0-9]*" => Some(Token::Ident(text.to_string())),
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "Vec<Vec<u8>> x";
+
+fn main() {
+    let lexer = Lexer::new();
+
+    // Both `>` tokens carry the position of `>>`.
+    for t in lexer.stream(INPUT_STR.chars()) {
+        println!("({:?} {:?})", t.token, t.m.range());
+    }
+}
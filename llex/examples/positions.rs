@@ -0,0 +1,36 @@
+// A second identifier (#pos_var) may be given after #span_var to bind the range of character
+// positions of the match in the whole input, so that actions can construct tokens with location
+// information. The error variant can use it too. Without it, actions only take #span_var.
+//
+//
+// FORMAT:
+//
+// #struct_visibility struct #struct_name;
+// #fn_visibility fn #fn_name;
+// (#span_var, #pos_var) -> #token_type, #error_variant;
+
+use std::ops::Range;
+
+use llex::lexer;
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text, pos) -> pub enum Token {
+        Ident(String, Range<usize>),
+        Unknown(Range<usize>),
+    }, Token::Unknown(pos);
+
+    r"\s" => None,
+    r"[A-Za-z]+" => Some(Token::Ident(text.to_string(), pos)),
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "ab  cde ? f";
+
+fn main() {
+    let lexer = Lexer::new();
+    let tokens: Vec<_> = lexer.stream(INPUT_STR.chars()).map(|t| t.token).collect();
+
+    println!("{:?}", tokens);
+}
@@ -0,0 +1,40 @@
+// When several rules match the longest possible input, the earliest declared rule wins by default.
+// A rule may be prefixed with a #[priority = N] attribute to override declaration order: among the
+// rules matching the longest input, the rule with the highest priority wins, and rules of equal
+// priority (0 if not given) are still resolved by declaration order.
+//
+// Priorities only break ties between matches of the same length; the longest match always wins.
+// Below, "iffy" is an identifier even though the keyword rule has a higher priority.
+//
+//
+// FORMAT:
+//
+// #[priority = #priority]
+// #regexp => #action,
+
+use llex::lexer;
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text) -> pub enum Token {
+        Ident(String),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"[A-Za-z_][A-Za-z0-9_]*" => Some(Token::Ident(text.to_string())),
+    // Declared after the identifier rule, but wins over it on "if".
+    #[priority = 1]
+    r"if" => Token::KeywordIf,
+    // Declared after the identifier rule without a priority, so it never wins.
+    r"else" => Token::KeywordElse,
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "if iffy else";
+
+fn main() {
+    let lexer = Lexer::new();
+    let tokens: Vec<_> = lexer.stream(INPUT_STR.chars()).map(|t| t.token).collect();
+    println!("{:?}", tokens);
+}
@@ -0,0 +1,40 @@
+// By default, the lexer skips a single offending character on error, so a run of unmatched input
+// produces an error for every character. An optional resync predicate, a `fn(char) -> bool`
+// expression given after the header, makes the lexer skip offending characters until the
+// predicate holds for the next one. The whole run produces a single error variant, with
+// #span_var bound to the skipped characters.
+//
+//
+// FORMAT:
+//
+// #struct_visibility struct #struct_name;
+// #fn_visibility fn #fn_name;
+// (#span_var) -> #token_type, #error_variant;
+// resync #predicate;
+
+use llex::lexer;
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text) -> pub enum Token {
+        Integer(i64),
+        Unknown(String),
+    }, Token::Unknown(text.to_string());
+    resync char::is_whitespace;
+
+    r"\s" => None,
+    r"[0-9]+" => Some(Token::Integer(text.parse().unwrap())),
+}
+
+// The input string to pass into the lexer function.
+const INPUT_STR: &str = "12 @#$%1 34";
+
+fn main() {
+    let lexer = Lexer::new();
+
+    // The run of illegal characters, up to the next whitespace, produces one error.
+    for t in lexer.stream(INPUT_STR.chars()) {
+        println!("({:?} {:?})", t.token, t.m.range());
+    }
+}
@@ -0,0 +1,51 @@
+// The generated #struct_name::rules lists the pattern and action of each rule, in declaration
+// order, so that tools can show what a lexer recognizes. Actions are written as the tokens of
+// their expressions, and include rules that are shadowed by an identical pattern.
+//
+// #struct_name::advance_with_rule matches like #struct_name::advance_at, but at a cursor rather
+// than a byte index, and also returns the index in #struct_name::rules of the rule that matched,
+// e.g. to highlight tokens by the kind of rule. The index is None for the error variant.
+//
+//
+// GENERATED:
+//
+// impl #struct_name {
+//     #struct_visibility fn rules() -> &'static [(&'static str, &'static str)] { ... }
+//
+//     #struct_visibility fn advance_with_rule(&self, input: &str, pos: Cursor)
+//         -> (Option<(Tokens<#token_type>, Option<usize>)>, Cursor) { ... }
+// }
+
+use llex::{lexer, Cursor, Tokens};
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text) -> pub enum Token {
+        Number(i64),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"[0-9]+" => Some(Token::Number(text.parse().unwrap())),
+    r"\+" => Token::Plus,
+    r"[a-z]+" => None,
+}
+
+// The input string to pass into the lexer.
+const INPUT_STR: &str = "12 + ab ? 3";
+
+fn main() {
+    for (pattern, action) in Lexer::rules() {
+        println!("{} => {}", pattern, action);
+    }
+
+    // The rule that matched each part of the input, by declaration order.
+    let lexer = Lexer::new();
+    let mut pos = Cursor::default();
+    while let (Some((tokens, rule)), next) = lexer.advance_with_rule(INPUT_STR, pos) {
+        if let Tokens::One(t) = tokens {
+            println!("{:?} {:?}", t, rule);
+        }
+        pos = next;
+    }
+}
@@ -0,0 +1,39 @@
+// The generated #struct_name only holds the DFA built from the rules, which lexing never
+// modifies, so it is Send and Sync. A lexer constructed once can be shared behind an Arc and used
+// to tokenize several inputs concurrently.
+
+use std::sync::Arc;
+use std::thread;
+
+use llex::lexer;
+
+lexer! {
+    pub struct Lexer;
+    pub fn stream;
+    (text) -> pub enum Token {
+        Number(i64),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"[0-9]+" => Some(Token::Number(text.parse().unwrap())),
+}
+
+fn main() {
+    let lexer = Arc::new(Lexer::new());
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let lexer = Arc::clone(&lexer);
+            thread::spawn(move || {
+                let input = format!("{} {}", i, i * 10);
+                lexer
+                    .stream(input.chars())
+                    .map(|t| t.token)
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        println!("{:?}", handle.join().unwrap());
+    }
+}
@@ -0,0 +1,84 @@
+use std::ops::Range;
+
+use crate::stream::{LexerDFAMatcher, LexerItem, LexerStream};
+
+/// An edit of some input, replacing the characters in `range` of the old input with `len` new
+/// characters. Positions are character indexes, as in the matches of a [`LexerStream`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub range: Range<usize>,
+    pub len: usize,
+}
+
+impl Edit {
+    #[inline]
+    pub fn new(range: Range<usize>, len: usize) -> Self {
+        Self { range, len }
+    }
+
+    /// Return the position in the new input of a position in the old input at or after the end
+    /// of the edit.
+    #[inline]
+    fn shift(&self, pos: usize) -> usize {
+        pos - self.range.end + self.range.start + self.len
+    }
+}
+
+/// Lex the input again after an edit, reusing the tokens of the old input where possible, and
+/// return the tokens of the whole new input.
+///
+/// Each match is lexed independently of the previous ones, so lexing may restart at the start of
+/// any old token. Lexing restarts one token before the first token that touches the edit, in
+/// case the match of that token had looked ahead into the edit, and stops as soon as a new token
+/// starts where an old token after the edit started: from there on, the input is unchanged and
+/// the old tokens are reused, shifted by the length of the edit. This assumes that no match looks
+/// ahead by more than one token.
+///
+/// `old` must be the tokens of the whole old input, as produced by the same matcher.
+#[inline]
+pub fn relex<T, M>(matcher: M, old: &[LexerItem<T>], edit: &Edit, input: &str) -> Vec<LexerItem<T>>
+where
+    T: Clone,
+    M: LexerDFAMatcher<T>,
+{
+    // Several tokens produced by one match share its position, so restart at the first of them.
+    let touched = old
+        .iter()
+        .position(|item| item.m.end >= edit.range.start)
+        .unwrap_or(old.len());
+    let (restart, restart_pos) = match touched.checked_sub(1) {
+        Some(i) => {
+            let start = old[i].m.start;
+            let first = old.iter().position(|item| item.m.start == start).unwrap();
+            (first, start)
+        }
+        None => (0, 0),
+    };
+
+    let mut items = old[..restart].to_vec();
+    let stream = LexerStream::with_offset(matcher, input.chars().skip(restart_pos), restart_pos);
+    // The next old token that the new tokens may re-synchronize with.
+    let mut next_old = restart;
+    for item in stream {
+        while next_old < old.len()
+            && (old[next_old].m.start < edit.range.end
+                || edit.shift(old[next_old].m.start) < item.m.start)
+        {
+            next_old += 1;
+        }
+
+        if next_old < old.len() && edit.shift(old[next_old].m.start) == item.m.start {
+            items.extend(old[next_old..].iter().map(|old_item| {
+                let mut item = old_item.clone();
+                item.m.start = edit.shift(item.m.start);
+                item.m.end = edit.shift(item.m.end);
+                item
+            }));
+            break;
+        }
+
+        items.push(item);
+    }
+
+    items
+}
@@ -1,9 +1,13 @@
 #![deny(rust_2018_idioms)]
 #![deny(future_incompatible)]
 
+pub mod incremental;
+pub mod merge;
 pub mod stream;
 
+pub use incremental::{relex, Edit};
 pub use llex_macro::lexer;
-pub use stream::{LexerItem, LexerStream};
+pub use merge::{LexerRules, Merged};
+pub use stream::{Cursor, LexerItem, LexerStream, PeekableLexer, Tokens};
 
 pub use regexp2;
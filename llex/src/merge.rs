@@ -0,0 +1,253 @@
+use std::collections::{HashMap, VecDeque};
+use std::iter::Peekable;
+
+use regexp2::{
+    automata::{
+        dfa::{Disjoin, Transition},
+        Match,
+    },
+    class::CharClass,
+};
+
+use crate::stream::{LexerDFA, LexerDFAMatcher, LexerStream, Tokens};
+
+/// A lexer whose rules are matched by a single DFA, with an action for each of its final states.
+/// Implemented by the lexers generated by [`lexer!`](crate::lexer), so that they may be merged.
+pub trait LexerRules<T>: LexerDFAMatcher<T> {
+    /// Return the DFA that matches the rules of the lexer.
+    fn dfa(&self) -> &LexerDFA;
+
+    /// Execute the action of the rule accepted by a final state of the DFA, for a match ending
+    /// in that state. `offset` and the match are as in [`LexerDFAMatcher::tokenize`].
+    fn act(&self, state: usize, m: &Match<char>, offset: usize) -> Tokens<T>;
+
+    /// Return the number of characters at the end of a match ending in a final state of the DFA
+    /// that are the trailing context of its rule, rather than part of the token. By default,
+    /// rules have no trailing context.
+    #[inline]
+    fn trailing_context(&self, _state: usize, _span: &[char]) -> usize {
+        0
+    }
+
+    /// Split the trailing context off of a match ending in a final state of the DFA and execute
+    /// the action of its rule, returning the tokens, the match without the trailing context, and
+    /// the trailing context.
+    #[inline]
+    fn complete_match(
+        &self,
+        state: usize,
+        mut m: Match<char>,
+        offset: usize,
+    ) -> (Tokens<T>, Match<char>, Vec<char>) {
+        let len = self.trailing_context(state, &m.span);
+        let trailing = m.span.split_off(m.span.len() - len);
+        m.end -= len;
+
+        let tokens = self.act(state, &m, offset);
+        (tokens, m, trailing)
+    }
+
+    /// Combine the rules of this lexer with those of another, with the rules of this lexer taking
+    /// precedence (see [`Merged`]).
+    #[inline]
+    fn merge<L>(self, other: L) -> Merged<Self, L>
+    where
+        Self: Sized,
+        L: LexerRules<T>,
+    {
+        Merged::new(self, other)
+    }
+}
+
+/// Two lexers combined into one that matches the rules of both.
+///
+/// The longest match of either lexer wins, and the first lexer wins when both match the same
+/// longest input, as if its rules had been declared before those of the second. Errors and the
+/// end-of-input token are produced by the first lexer. Merged lexers may themselves be merged.
+#[derive(Debug, Clone)]
+pub struct Merged<A, B> {
+    first: A,
+    second: B,
+    /// The product of the DFAs of both lexers.
+    dfa: LexerDFA,
+    /// The lexer whose action each final state of the DFA executes, and the corresponding final
+    /// state of that lexer's DFA.
+    finals: HashMap<usize, Side>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Side {
+    First(usize),
+    Second(usize),
+}
+
+impl<A, B> Merged<A, B> {
+    /// Merge two lexers, with the rules of `first` taking precedence.
+    #[inline]
+    pub fn new<T>(first: A, second: B) -> Self
+    where
+        A: LexerRules<T>,
+        B: LexerRules<T>,
+    {
+        let (dfa, finals) = product(first.dfa(), second.dfa());
+        Self {
+            first,
+            second,
+            dfa,
+            finals,
+        }
+    }
+
+    /// Return an iterator over the tokens of the input.
+    #[inline]
+    pub fn stream<T, I>(&self, input: I) -> LexerStream<T, &Self, I>
+    where
+        A: LexerRules<T>,
+        B: LexerRules<T>,
+        I: Iterator<Item = char>,
+    {
+        LexerStream::new(self, input)
+    }
+}
+
+impl<T, A, B> LexerDFAMatcher<T> for Merged<A, B>
+where
+    A: LexerRules<T>,
+    B: LexerRules<T>,
+{
+    #[inline]
+    fn tokenize<I: Iterator<Item = char>>(
+        &self,
+        input: &mut Peekable<I>,
+        offset: usize,
+    ) -> Option<(Tokens<T>, Match<char>, Vec<char>)> {
+        match self.dfa.find_mut(input) {
+            Some((m, state)) => Some(self.complete_match(state, m, offset)),
+            // Neither lexer matches, so the first produces the error.
+            None => self.first.tokenize(input, offset),
+        }
+    }
+
+    #[inline]
+    fn eof(&self) -> Option<T> {
+        self.first.eof()
+    }
+}
+
+impl<T, A, B> LexerRules<T> for Merged<A, B>
+where
+    A: LexerRules<T>,
+    B: LexerRules<T>,
+{
+    #[inline]
+    fn dfa(&self) -> &LexerDFA {
+        &self.dfa
+    }
+
+    #[inline]
+    fn act(&self, state: usize, m: &Match<char>, offset: usize) -> Tokens<T> {
+        match self.finals[&state] {
+            Side::First(state) => self.first.act(state, m, offset),
+            Side::Second(state) => self.second.act(state, m, offset),
+        }
+    }
+
+    #[inline]
+    fn trailing_context(&self, state: usize, span: &[char]) -> usize {
+        match self.finals[&state] {
+            Side::First(state) => self.first.trailing_context(state, span),
+            Side::Second(state) => self.second.trailing_context(state, span),
+        }
+    }
+}
+
+impl<T, A, B> LexerDFAMatcher<T> for &Merged<A, B>
+where
+    A: LexerRules<T>,
+    B: LexerRules<T>,
+{
+    #[inline]
+    fn tokenize<I: Iterator<Item = char>>(
+        &self,
+        input: &mut Peekable<I>,
+        offset: usize,
+    ) -> Option<(Tokens<T>, Match<char>, Vec<char>)> {
+        (*self).tokenize(input, offset)
+    }
+
+    #[inline]
+    fn eof(&self) -> Option<T> {
+        (*self).eof()
+    }
+}
+
+/// Construct the product of two DFAs, which steps through both at once. Each state of the product
+/// stands for a pair of states, one of either DFA, or none once that DFA has no transition on the
+/// input. A state is final if either of its states is, and the final states are mapped to the
+/// final state of the first DFA if it has one, and of the second otherwise.
+#[inline]
+fn product(first: &LexerDFA, second: &LexerDFA) -> (LexerDFA, HashMap<usize, Side>) {
+    type Pair = (Option<usize>, Option<usize>);
+
+    let mut dfa = LexerDFA::new();
+    let mut finals = HashMap::new();
+
+    let initial = (Some(first.initial_state), Some(second.initial_state));
+    let mut states: HashMap<Pair, usize> = HashMap::new();
+    states.insert(initial, dfa.initial_state);
+    let mut unmarked = VecDeque::new();
+    unmarked.push_back(initial);
+
+    while let Some(pair) = unmarked.pop_front() {
+        let label = states[&pair];
+        let (s1, s2) = pair;
+
+        let side = match (s1, s2) {
+            (Some(s), _) if first.is_final_state(&s) => Some(Side::First(s)),
+            (_, Some(s)) if second.is_final_state(&s) => Some(Side::Second(s)),
+            _ => None,
+        };
+        if let Some(side) = side {
+            dfa.final_states.insert(label);
+            finals.insert(label, side);
+        }
+
+        let row1 = s1.map(|s| first.transition.get_row(&s)).unwrap_or_default();
+        let row2 = s2
+            .map(|s| second.transition.get_row(&s))
+            .unwrap_or_default();
+
+        // Split the transition symbols of both states into disjoint pieces, each of which leads
+        // to a single pair of states, and merge the pieces that lead to the same pair.
+        let classes = row1.keys().chain(row2.keys()).map(|t| &t.0).collect();
+        let mut dests: HashMap<Pair, CharClass> = HashMap::new();
+        for piece in CharClass::disjoin(classes) {
+            let dest = |row: &HashMap<&Transition<CharClass>, &usize>| {
+                row.iter()
+                    .find(|(t, _)| Disjoin::contains(&t.0, &piece))
+                    .map(|(_, &&dest)| dest)
+            };
+            dests
+                .entry((dest(&row1), dest(&row2)))
+                .or_default()
+                .copy_from(&piece);
+        }
+
+        for (dest, mut class) in dests {
+            let dest_label = match states.get(&dest) {
+                Some(&s) => s,
+                None => {
+                    let s = dfa.add_state(false);
+                    states.insert(dest, s);
+                    unmarked.push_back(dest);
+                    s
+                }
+            };
+
+            class.normalize();
+            dfa.add_transition(label, dest_label, Transition(class));
+        }
+    }
+
+    (dfa, finals)
+}
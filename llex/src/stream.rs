@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use std::iter::Peekable;
 
 use regexp2::{
@@ -8,10 +9,93 @@ use regexp2::{
 pub type LexerDFA = DFA<CharClass>;
 
 pub trait LexerDFAMatcher<T>: Clone {
+    /// Match a prefix of the input and return its tokens. `offset` is the position of the input
+    /// in the whole stream, and the returned match is relative to it.
+    ///
+    /// Also returns the characters consumed after the match as the trailing context of its rule,
+    /// which must be read again before the rest of the input.
     fn tokenize<I: Iterator<Item = char>>(
         &self,
         input: &mut Peekable<I>,
-    ) -> Option<(T, Match<char>)>;
+        offset: usize,
+    ) -> Option<(Tokens<T>, Match<char>, Vec<char>)>;
+
+    /// Return the token to emit once at the end of input, if any. By default, no token is
+    /// emitted.
+    #[inline]
+    fn eof(&self) -> Option<T> {
+        None
+    }
+}
+
+/// The tokens produced by a lexer action for a single match, which are emitted in order.
+///
+/// Actions may return an `Option<T>` for at most one token or a `Vec<T>` for any number of
+/// tokens, either of which is converted into this type. Actions of lexers whose tokens are
+/// `Result<T, E>` return either of these in a `Result<_, E>` instead, and an error becomes a
+/// single token.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tokens<T> {
+    None,
+    One(T),
+    Many(Vec<T>),
+}
+
+impl<T> From<Option<T>> for Tokens<T> {
+    #[inline]
+    fn from(token: Option<T>) -> Self {
+        match token {
+            Some(t) => Self::One(t),
+            None => Self::None,
+        }
+    }
+}
+
+impl<T> From<Vec<T>> for Tokens<T> {
+    #[inline]
+    fn from(tokens: Vec<T>) -> Self {
+        Self::Many(tokens)
+    }
+}
+
+impl<T, E, X> From<Result<X, E>> for Tokens<Result<T, E>>
+where
+    X: Into<Tokens<T>>,
+{
+    #[inline]
+    fn from(result: Result<X, E>) -> Self {
+        match result.map(Into::into) {
+            Ok(Tokens::None) => Self::None,
+            Ok(Tokens::One(t)) => Self::One(Ok(t)),
+            Ok(Tokens::Many(tokens)) => Self::Many(tokens.into_iter().map(Ok).collect()),
+            Err(e) => Self::One(Err(e)),
+        }
+    }
+}
+
+/// A position in an input string, both as a byte index, to slice the input, and as a character
+/// index, as in the matches of a [`LexerStream`]. A generated lexer's `advance_with_rule` takes
+/// and returns cursors, so that lexing a whole input by position never counts the characters
+/// before a position again.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Cursor {
+    pub byte: usize,
+    pub char: usize,
+}
+
+impl Cursor {
+    #[inline]
+    pub fn new(byte: usize, char: usize) -> Self {
+        Self { byte, char }
+    }
+
+    /// Return the cursor at byte index `byte` of the input, counting the characters before it.
+    ///
+    /// Panics if `byte` is past the end of the input or not on a character boundary.
+    #[inline]
+    pub fn at(input: &str, byte: usize) -> Self {
+        Self::new(byte, input[..byte].chars().count())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -34,9 +118,16 @@ where
     I: Iterator<Item = char>,
 {
     pub input: Peekable<I>,
+    /// Characters consumed as trailing context by the last match, which are read before the rest
+    /// of the input.
+    replay: VecDeque<char>,
     matcher: M,
     current_item: Option<LexerItem<T>>,
+    /// Tokens produced by the last match that have yet to be emitted.
+    pending: VecDeque<LexerItem<T>>,
     offset: usize,
+    /// Whether the end of input has been reached and the end-of-input token, if any, emitted.
+    finished: bool,
 }
 
 impl<T, M, I> LexerStream<T, M, I>
@@ -46,13 +137,30 @@ where
 {
     #[inline]
     pub fn new(matcher: M, input: I) -> Self {
+        Self::with_offset(matcher, input, 0)
+    }
+
+    /// Create a stream over input that begins at position `offset` of some larger input, so that
+    /// matches are positioned in the larger input. Lexing may begin at the start of any match of
+    /// the larger input, since each match is lexed independently of the previous ones.
+    #[inline]
+    pub fn with_offset(matcher: M, input: I, offset: usize) -> Self {
         Self {
             matcher,
             current_item: None,
+            pending: VecDeque::new(),
             input: input.peekable(),
-            offset: 0,
+            replay: VecDeque::new(),
+            offset,
+            finished: false,
         }
     }
+
+    /// Wrap this stream to allow looking ahead at upcoming tokens.
+    #[inline]
+    pub fn lookahead(self) -> PeekableLexer<Self> {
+        PeekableLexer::new(self)
+    }
 }
 
 impl<'a, T, M, I> Iterator for LexerStream<T, M, I>
@@ -64,23 +172,140 @@ where
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        self.input.peek()?;
-
-        let token_op = self.matcher.tokenize(&mut self.input);
-        match token_op {
-            // If a token was returned, return the token and the remaining input.
-            Some((t, mut m)) => {
-                m.start += self.offset;
-                m.end += self.offset;
-                self.offset += m.end - m.start;
-                Some(LexerItem::new(t, m))
+        // Emit the remaining tokens of the last match before consuming more input.
+        if let Some(item) = self.pending.pop_front() {
+            return Some(item);
+        }
+
+        // At the end of input, emit the end-of-input token only once.
+        if self.replay.is_empty() && self.input.peek().is_none() {
+            if self.finished {
+                return None;
+            }
+            self.finished = true;
+
+            let m = Match::new(self.offset, self.offset, vec![]);
+            return self.matcher.eof().map(|t| LexerItem::new(t, m));
+        }
+
+        let (tokens, mut m, trailing) = if self.replay.is_empty() {
+            self.matcher.tokenize(&mut self.input, self.offset)?
+        } else {
+            let mut input = Replay {
+                buffer: &mut self.replay,
+                input: &mut self.input,
             }
-            // If no token was returned, one input symbol should be consumed and the process
-            // restarted.
-            None => {
-                self.offset += 1;
+            .peekable();
+            let res = self.matcher.tokenize(&mut input, self.offset);
+
+            // The next character may have been peeked, so return it to the buffer.
+            if let Some(c) = input.next() {
+                self.replay.push_front(c);
+            }
+            res?
+        };
+        for c in trailing.into_iter().rev() {
+            self.replay.push_front(c);
+        }
+
+        m.start += self.offset;
+        m.end += self.offset;
+        self.offset += m.end - m.start;
+
+        match tokens {
+            Tokens::One(t) => Some(LexerItem::new(t, m)),
+            // Every token produced by the match shares its position.
+            Tokens::Many(ts) => {
+                self.pending
+                    .extend(ts.into_iter().map(|t| LexerItem::new(t, m.clone())));
                 self.next()
             }
+            // If no token was produced, the matched input is skipped.
+            Tokens::None => self.next(),
+        }
+    }
+}
+
+/// Iterator over the characters of a buffer followed by those of the input.
+struct Replay<'a, I>
+where
+    I: Iterator<Item = char>,
+{
+    buffer: &'a mut VecDeque<char>,
+    input: &'a mut Peekable<I>,
+}
+
+impl<'a, I> Iterator for Replay<'a, I>
+where
+    I: Iterator<Item = char>,
+{
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffer.pop_front().or_else(|| self.input.next())
+    }
+}
+
+/// Wrapper around a token stream that buffers upcoming tokens for lookahead. Tokens are pulled
+/// from the underlying stream only as far as needed.
+#[derive(Debug)]
+pub struct PeekableLexer<S>
+where
+    S: Iterator,
+{
+    stream: S,
+    buffer: VecDeque<S::Item>,
+}
+
+impl<S> PeekableLexer<S>
+where
+    S: Iterator,
+{
+    #[inline]
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            buffer: VecDeque::new(),
+        }
+    }
+
+    /// Return a reference to the next token without consuming it.
+    #[inline]
+    pub fn peek(&mut self) -> Option<&S::Item> {
+        self.peek_nth(0)
+    }
+
+    /// Return a reference to the token after the next without consuming either.
+    #[inline]
+    pub fn peek2(&mut self) -> Option<&S::Item> {
+        self.peek_nth(1)
+    }
+
+    /// Return a reference to the token `n` positions ahead (0 being the next token) without
+    /// consuming any tokens.
+    #[inline]
+    pub fn peek_nth(&mut self, n: usize) -> Option<&S::Item> {
+        while self.buffer.len() <= n {
+            let item = self.stream.next()?;
+            self.buffer.push_back(item);
+        }
+
+        self.buffer.get(n)
+    }
+}
+
+impl<S> Iterator for PeekableLexer<S>
+where
+    S: Iterator,
+{
+    type Item = S::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.buffer.pop_front() {
+            Some(item) => Some(item),
+            None => self.stream.next(),
         }
     }
 }
@@ -0,0 +1,95 @@
+use std::ops::Range;
+
+use llex::{lexer, Cursor, Tokens};
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text, pos) -> enum Token {
+        Word(String, Range<usize>),
+        Unknown(Range<usize>),
+    }, Token::Unknown(pos);
+
+    r"\s" => None,
+    r"[a-zé]+" => Some(Token::Word(text.to_string(), pos)),
+}
+
+// The input string, with multi-byte characters.
+const INPUT_STR: &str = "café ? olé";
+
+#[test]
+fn test_advance_at() {
+    let lexer = Lexer::new();
+
+    // Tokenize the whole input by position only.
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    loop {
+        match lexer.advance_at(INPUT_STR, pos) {
+            (Some(Tokens::One(t)), next) => {
+                tokens.push(t);
+                pos = next;
+            }
+            (Some(_), next) => pos = next,
+            (None, next) => {
+                // At the end of input, the position is returned unchanged.
+                assert_eq!(pos, next);
+                break;
+            }
+        }
+    }
+    assert_eq!(INPUT_STR.len(), pos);
+
+    assert_eq!(
+        vec![
+            Token::Word("café".to_string(), 0..4),
+            Token::Unknown(5..6),
+            Token::Word("olé".to_string(), 7..10),
+        ],
+        tokens
+    );
+
+    // Tokenizing by position produces the same tokens as the stream.
+    let streamed: Vec<_> = lexer.stream(INPUT_STR.chars()).map(|t| t.token).collect();
+    assert_eq!(streamed, tokens);
+}
+
+#[test]
+fn test_advance_at_position() {
+    let lexer = Lexer::new();
+
+    // The byte index past a match can be used to slice the input.
+    let (_, next) = lexer.advance_at(INPUT_STR, 0);
+    assert_eq!(5, next);
+    assert_eq!(" ? olé", &INPUT_STR[next..]);
+
+    // Lexing may start at any character boundary.
+    let (tokens, _) = lexer.advance_at(INPUT_STR, 8);
+    assert_eq!(
+        Some(Tokens::One(Token::Word("olé".to_string(), 7..10))),
+        tokens
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_advance_at_not_boundary() {
+    Lexer::new().advance_at(INPUT_STR, 4);
+}
+
+#[test]
+fn test_advance_with_cursor() {
+    let lexer = Lexer::new();
+
+    // Cursors advance both indexes, so that the characters before a position are not counted.
+    let (matched, next) = lexer.advance_with_rule(INPUT_STR, Cursor::at(INPUT_STR, 5));
+    assert_eq!(Some(Some(0)), matched.map(|(_, rule)| rule));
+    assert_eq!(Cursor::new(6, 5), next);
+
+    let (matched, next) = lexer.advance_with_rule(INPUT_STR, next);
+    assert_eq!(
+        Some(Tokens::One(Token::Unknown(5..6))),
+        matched.map(|(tokens, _)| tokens)
+    );
+    assert_eq!(Cursor::new(7, 6), next);
+}
@@ -0,0 +1,86 @@
+use llex::{lexer, Tokens};
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Pixels(u32),
+        Number(u32),
+        Ident(String),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"[0-9]+" / r"px" => Some(Token::Pixels(text.parse().unwrap())),
+    r"[0-9]+" => Some(Token::Number(text.parse().unwrap())),
+    r"[a-z]+" => Some(Token::Ident(text.to_string())),
+}
+
+lexer! {
+    struct Split;
+    fn stream;
+    (text) -> enum Part {
+        Head(String),
+        Tail(String),
+    }, Part::Error;
+
+    r"a+" / r"a+b" => Some(Part::Head(text.to_string())),
+    r"a*b" => Some(Part::Tail(text.to_string())),
+}
+
+fn ident(s: &str) -> Token {
+    Token::Ident(s.to_string())
+}
+
+#[test]
+fn test_context() {
+    let lexer = Lexer::new();
+    let items: Vec<_> = lexer.stream("12px 3em 5pxx".chars()).collect();
+    let tokens: Vec<_> = items.iter().map(|item| item.token.clone()).collect();
+
+    assert_eq!(
+        vec![
+            Token::Pixels(12),
+            ident("px"),
+            Token::Number(3),
+            ident("em"),
+            Token::Pixels(5),
+            ident("pxx"),
+        ],
+        tokens
+    );
+
+    // The context is not part of the match.
+    assert_eq!(0..2, items[0].m.range());
+    assert_eq!(vec!['1', '2'], items[0].m.span);
+    assert_eq!(2..4, items[1].m.range());
+    assert_eq!(9..10, items[4].m.range());
+}
+
+#[test]
+fn test_context_split() {
+    // The pattern takes the longest prefix such that the context matches the rest.
+    let parts: Vec<_> = Split::new()
+        .stream("aaab".chars())
+        .map(|t| (t.token, t.m.range()))
+        .collect();
+    assert_eq!(
+        vec![
+            (Part::Head("aa".to_string()), 0..2),
+            (Part::Tail("ab".to_string()), 2..4),
+        ],
+        parts
+    );
+}
+
+#[test]
+fn test_context_advance_at() {
+    // Matching at an index of the input also stops before the context.
+    let (advanced, end) = Lexer::new().advance_at("12px", 0);
+    assert_eq!(Some(Tokens::One(Token::Pixels(12))), advanced);
+    assert_eq!(2, end);
+}
+
+#[test]
+fn test_context_rules() {
+    assert_eq!(r"[0-9]+/px", Lexer::rules()[1].0);
+}
@@ -0,0 +1,30 @@
+use llex::lexer;
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Ident(String),
+        Float(f64),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"enum" => Token::KeywordEnum,
+    r"[A-Za-z_][A-Za-z0-9_]*" => Some(Token::Ident(text.to_string())),
+    r"[0-9]+(\.[0-9]+)?" => Some(Token::Float(text.parse().unwrap())),
+}
+
+#[test]
+fn test_dfa() {
+    let lexer = Lexer::new();
+    let dfa = lexer.dfa();
+
+    // The DFA accepts exactly the inputs that some rule matches in full.
+    assert!(dfa.is_match("enum".chars()));
+    assert!(dfa.is_match("enums".chars()));
+    assert!(dfa.is_match("3.14".chars()));
+    assert!(dfa.is_match(" ".chars()));
+    assert!(!dfa.is_match("3.".chars()));
+    assert!(!dfa.is_match("a b".chars()));
+    assert!(!dfa.is_match("".chars()));
+}
@@ -0,0 +1,48 @@
+use std::ops::Range;
+
+use llex::lexer;
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text, pos, groups) -> enum Token {
+        Ident(String),
+        Number(u32, Range<usize>),
+        Pair(String, String),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"in" => Token::KeywordIn,
+    r"int" => Token::KeywordInt,
+    r"if" => Token::KeywordIf,
+    r"[a-z]+" => Some(Token::Ident(text.to_string())),
+    r"[0-9]+" => Some(Token::Number(text.parse().unwrap(), pos)),
+    r"([a-z]+)=([0-9]+)" => Some(Token::Pair(
+        groups[0].clone().unwrap(),
+        groups[1].clone().unwrap(),
+    )),
+}
+
+#[test]
+fn test_dispatch() {
+    let tokens: Vec<_> = Lexer::new()
+        .stream("in int if i inx 42 a=1 ?".chars())
+        .map(|t| t.token)
+        .collect();
+
+    // Each match runs the action of its own rule, including where the DFA states of several rules
+    // share a prefix.
+    assert_eq!(
+        vec![
+            Token::KeywordIn,
+            Token::KeywordInt,
+            Token::KeywordIf,
+            Token::Ident("i".to_string()),
+            Token::Ident("inx".to_string()),
+            Token::Number(42, 16..18),
+            Token::Pair("a".to_string(), "1".to_string()),
+            Token::Error,
+        ],
+        tokens
+    );
+}
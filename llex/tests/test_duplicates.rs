@@ -0,0 +1,47 @@
+#![allow(deprecated)]
+
+use llex::lexer;
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Ident(String),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"=" => Token::Equals,
+    // Shadowed by the rule above.
+    r"=" => None,
+    r"let" => None,
+    r"[a-z]+" => Some(Token::Ident(text.to_string())),
+    // Shadows the rule above.
+    #[priority = 1]
+    r"[a-z]+" => Some(Token::Ident(text.to_uppercase())),
+}
+
+#[test]
+fn test_shadowed_rules() {
+    let tokens: Vec<_> = Lexer::new()
+        .stream("let x = y".chars())
+        .map(|t| t.token)
+        .collect();
+
+    // The priority also lets identifiers win over the keyword.
+    assert_eq!(
+        vec![
+            Token::Ident("LET".to_string()),
+            Token::Ident("X".to_string()),
+            Token::Equals,
+            Token::Ident("Y".to_string()),
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn test_shadowed_rules_listed() {
+    // Shadowed rules are still listed, in declaration order.
+    let patterns: Vec<_> = Lexer::rules().iter().map(|(pattern, _)| *pattern).collect();
+    assert_eq!(vec![r"\s", "=", "=", "let", "[a-z]+", "[a-z]+"], patterns);
+}
@@ -0,0 +1,41 @@
+use llex::lexer;
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Integer(i64),
+    }, Token::Error, Token::Eof;
+
+    r"\s" => None,
+    r"[0-9]+" => Some(Token::Integer(text.parse().unwrap())),
+}
+
+#[test]
+fn test_eof_token() {
+    let lexer = Lexer::new();
+    let mut stream = lexer.stream("1 2 ".chars());
+
+    assert_eq!(Some(Token::Integer(1)), stream.next().map(|t| t.token));
+    assert_eq!(Some(Token::Integer(2)), stream.next().map(|t| t.token));
+
+    // The end-of-input token is positioned at the end of the input, and emitted only once.
+    let eof = stream.next().unwrap();
+    assert_eq!(Token::Eof, eof.token);
+    assert_eq!(4..4, eof.m.range());
+    assert!(stream.next().is_none());
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn test_eof_after_error() {
+    let tokens: Vec<_> = Lexer::new().stream("1?".chars()).map(|t| t.token).collect();
+    assert_eq!(vec![Token::Integer(1), Token::Error, Token::Eof], tokens);
+}
+
+#[test]
+fn test_eof_empty() {
+    // Empty input yields only the end-of-input token.
+    let tokens: Vec<_> = Lexer::new().stream("".chars()).map(|t| t.token).collect();
+    assert_eq!(vec![Token::Eof], tokens);
+}
@@ -0,0 +1,45 @@
+use llex::lexer;
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Integer(i64),
+        Unknown(char),
+    }, Token::Unknown(text.chars().next().unwrap());
+
+    r"\s" => None,
+    r"[0-9]+" => Some(Token::Integer(text.parse().unwrap())),
+}
+
+#[test]
+fn test_offending_character() {
+    let lexer = Lexer::new();
+    let items: Vec<_> = lexer.stream("12 @".chars()).collect();
+    assert_eq!(2, items.len());
+    assert_eq!(Token::Integer(12), items[0].token);
+
+    // The offending character and its position are both reported.
+    let error = &items[1];
+    assert_eq!(Token::Unknown('@'), error.token);
+    assert_eq!(3..4, error.m.range());
+    assert_eq!(vec!['@'], error.m.span);
+}
+
+#[test]
+fn test_consecutive_errors() {
+    // Each offending character is reported separately, and lexing resumes after it.
+    let lexer = Lexer::new();
+    let items: Vec<_> = lexer
+        .stream("é?1".chars())
+        .map(|t| (t.token, t.m.range()))
+        .collect();
+    assert_eq!(
+        vec![
+            (Token::Unknown('é'), 0..1),
+            (Token::Unknown('?'), 1..2),
+            (Token::Integer(1), 2..3),
+        ],
+        items
+    );
+}
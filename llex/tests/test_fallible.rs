@@ -0,0 +1,63 @@
+use llex::lexer;
+
+#[derive(Debug, Clone, PartialEq)]
+enum LexError {
+    Overflow(String),
+    Unknown(char),
+}
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Integer(u8),
+    }, LexError::Unknown(text.chars().next().unwrap()), Token::Eof;
+    error LexError;
+
+    r"\s" => Ok(None),
+    r"[0-9]+" => text
+        .parse()
+        .map(|n| Some(Token::Integer(n)))
+        .map_err(|_| LexError::Overflow(text.to_string())),
+    r"\+" => Token::Plus,
+}
+
+#[test]
+fn test_fallible_actions() {
+    let lexer = Lexer::new();
+    let items: Vec<_> = lexer.stream("12 + 300 @".chars()).collect();
+    let tokens: Vec<_> = items.iter().map(|item| item.token.clone()).collect();
+
+    assert_eq!(
+        vec![
+            Ok(Token::Integer(12)),
+            Ok(Token::Plus),
+            Err(LexError::Overflow("300".to_string())),
+            Err(LexError::Unknown('@')),
+            Ok(Token::Eof),
+        ],
+        tokens
+    );
+
+    // Errors have the position of their match.
+    assert_eq!(5..8, items[2].m.range());
+}
+
+#[test]
+fn test_collect_result() {
+    // The tokens can be collected into a Result, which stops at the first error.
+    let lexer = Lexer::new();
+    let tokens: Result<Vec<_>, _> = lexer.stream("1+2".chars()).map(|t| t.token).collect();
+    assert_eq!(
+        Ok(vec![
+            Token::Integer(1),
+            Token::Plus,
+            Token::Integer(2),
+            Token::Eof
+        ]),
+        tokens
+    );
+
+    let tokens: Result<Vec<_>, _> = lexer.stream("1 256 @".chars()).map(|t| t.token).collect();
+    assert_eq!(Err(LexError::Overflow("256".to_string())), tokens);
+}
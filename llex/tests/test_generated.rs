@@ -0,0 +1,56 @@
+use llex::lexer;
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Ident(String),
+        Integer(i64),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"if" => Token::KeywordIf,
+    r"else" => Token::KeywordElse,
+    r"=" => Token::Equals,
+    r";" => Token::Semicolon,
+    r"[A-Za-z_][A-Za-z0-9_]*" => Some(Token::Ident(text.to_string())),
+    r"[0-9]+" => Some(Token::Integer(text.parse().unwrap())),
+}
+
+fn tokens(input: &str) -> Vec<Token> {
+    Lexer::new()
+        .stream(input.chars())
+        .map(|t| t.token)
+        .collect()
+}
+
+#[test]
+fn test_generated_enum() {
+    assert_eq!(
+        vec![
+            Token::KeywordIf,
+            Token::Ident("x".to_string()),
+            Token::Equals,
+            Token::Integer(1),
+            Token::Semicolon,
+            Token::KeywordElse,
+            Token::Ident("y".to_string()),
+            Token::Equals,
+            Token::Integer(2),
+            Token::Semicolon,
+        ],
+        tokens("if x = 1; else y = 2;")
+    );
+}
+
+#[test]
+fn test_inline_error_variant() {
+    assert_eq!(
+        vec![
+            Token::Ident("x".to_string()),
+            Token::Error,
+            Token::Integer(1)
+        ],
+        tokens("x?1")
+    );
+}
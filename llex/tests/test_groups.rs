@@ -0,0 +1,57 @@
+use llex::lexer;
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text, pos, groups) -> enum Token {
+        Number(u32),
+        Ident(String),
+        Unknown(String),
+    }, Token::Unknown(text.to_string());
+
+    r"\s" => None,
+    r"0x([0-9A-Fa-f]+)" => {
+        let digits = groups[0].as_ref().unwrap();
+        Some(Token::Number(u32::from_str_radix(digits, 16).unwrap()))
+    }
+    r"[0-9]+" => Some(Token::Number(text.parse().unwrap())),
+    r"([a-z]+)(_([0-9]+))?" => {
+        let suffix = groups[2].as_deref().unwrap_or("");
+        Some(Token::Ident(format!("{}{}", groups[0].as_ref().unwrap(), suffix)))
+    }
+}
+
+fn tokens(input: &str) -> Vec<Token> {
+    Lexer::new()
+        .stream(input.chars())
+        .map(|t| t.token)
+        .collect()
+}
+
+#[test]
+fn test_groups() {
+    assert_eq!(
+        vec![
+            Token::Number(31),
+            Token::Number(12),
+            Token::Number(255),
+            Token::Ident("abc1".to_string()),
+        ],
+        tokens("0x1F 12 0xff abc_1")
+    );
+}
+
+#[test]
+fn test_unmatched_group() {
+    // The suffix groups are None if the identifier has no suffix.
+    assert_eq!(vec![Token::Ident("x".to_string())], tokens("x"));
+}
+
+#[test]
+fn test_error_with_groups() {
+    // No rule matched, so there are no groups, but the error variant is still produced.
+    assert_eq!(
+        vec![Token::Unknown("?".to_string()), Token::Number(1)],
+        tokens("?1")
+    );
+}
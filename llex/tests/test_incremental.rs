@@ -0,0 +1,136 @@
+use std::cell::Cell;
+use std::iter::Peekable;
+
+use llex::{
+    lexer, regexp2::automata::Match, relex, stream::LexerDFAMatcher, Edit, LexerItem, Tokens,
+};
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Ident(String),
+        Number(i64),
+    }, Token::Error, Token::Eof;
+
+    r"\s" => None,
+    r"[a-z]+" => Some(Token::Ident(text.to_string())),
+    r"[0-9]+" => Some(Token::Number(text.parse().unwrap())),
+    r"=" => Token::Assign,
+    r"\+" => Token::Plus,
+    r";" => Token::Semicolon,
+}
+
+// Matcher that counts the matches made by the lexer.
+#[derive(Clone)]
+struct Counting<'a> {
+    lexer: &'a Lexer,
+    count: &'a Cell<usize>,
+}
+
+impl<'a> LexerDFAMatcher<Token> for Counting<'a> {
+    fn tokenize<I: Iterator<Item = char>>(
+        &self,
+        input: &mut Peekable<I>,
+        offset: usize,
+    ) -> Option<(Tokens<Token>, Match<char>, Vec<char>)> {
+        self.count.set(self.count.get() + 1);
+        self.lexer.tokenize(input, offset)
+    }
+
+    fn eof(&self) -> Option<Token> {
+        self.lexer.eof()
+    }
+}
+
+const INPUT_STR: &str = "a = 1 + bc; d = 23; e = f + 4;";
+
+fn lex(lexer: &Lexer, input: &str) -> Vec<LexerItem<Token>> {
+    lexer.stream(input.chars()).collect()
+}
+
+fn positioned(items: &[LexerItem<Token>]) -> Vec<(Token, std::ops::Range<usize>)> {
+    items
+        .iter()
+        .map(|item| (item.token.clone(), item.m.range()))
+        .collect()
+}
+
+#[test]
+fn test_relex() {
+    let lexer = Lexer::new();
+    let old = lex(&lexer, INPUT_STR);
+
+    // Replace the "23" at 16..18 with "2", insert "x" after "bc", delete the ";" after "bc", edit
+    // the beginning and the end of the input, and replace the "c" of "bc".
+    let edits = [
+        (Edit::new(16..18, 1), "a = 1 + bc; d = 2; e = f + 4;"),
+        (Edit::new(10..10, 1), "a = 1 + bcx; d = 23; e = f + 4;"),
+        (Edit::new(10..11, 0), "a = 1 + bc d = 23; e = f + 4;"),
+        (Edit::new(0..0, 1), "za = 1 + bc; d = 23; e = f + 4;"),
+        (Edit::new(29..30, 1), "a = 1 + bc; d = 23; e = f + 45"),
+        (Edit::new(9..10, 1), "a = 1 + b1; d = 23; e = f + 4;"),
+    ];
+
+    for (edit, input) in &edits {
+        let count = Cell::new(0);
+        let matcher = Counting {
+            lexer: &lexer,
+            count: &count,
+        };
+        let relexed = relex(matcher, &old, edit, input);
+
+        assert_eq!(
+            positioned(&lex(&lexer, input)),
+            positioned(&relexed),
+            "relexing {:?} differs",
+            input
+        );
+
+        // Only the matches near the edit are made again.
+        assert!(
+            count.get() < 8,
+            "relexing {:?} made {} matches",
+            input,
+            count.get()
+        );
+    }
+}
+
+#[test]
+fn test_relex_around_edit() {
+    let lexer = Lexer::new();
+    let old = lex(&lexer, INPUT_STR);
+
+    // A one-character edit in the middle only relexes the tokens around it.
+    let count = Cell::new(0);
+    let matcher = Counting {
+        lexer: &lexer,
+        count: &count,
+    };
+    let relexed = relex(
+        matcher,
+        &old,
+        &Edit::new(16..18, 1),
+        "a = 1 + bc; d = 2; e = f + 4;",
+    );
+    assert_eq!(Token::Number(2), relexed[8].token);
+    assert_eq!(16..17, relexed[8].m.range());
+    assert_eq!(Token::Eof, relexed.last().unwrap().token);
+    assert!(count.get() <= 4);
+}
+
+#[test]
+fn test_relex_empty() {
+    let lexer = Lexer::new();
+
+    // Inserting into empty input lexes all of it.
+    let old = lex(&lexer, "");
+    let relexed = relex(&lexer, &old, &Edit::new(0..0, 3), "a=1");
+    assert_eq!(positioned(&lex(&lexer, "a=1")), positioned(&relexed));
+
+    // Deleting all of the input leaves only the end-of-input token.
+    let old = lex(&lexer, "a=1");
+    let relexed = relex(&lexer, &old, &Edit::new(0..3, 0), "");
+    assert_eq!(vec![(Token::Eof, 0..0)], positioned(&relexed));
+}
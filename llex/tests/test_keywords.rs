@@ -0,0 +1,50 @@
+use llex::lexer;
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Ident(String),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"print" => Token::KeywordPrint,
+    r"[A-Za-z_][A-Za-z0-9_]*" => Some(Token::Ident(text.to_string())),
+    r"\(" => Token::LeftParenthesis,
+    r"\)" => Token::RightParenthesis,
+}
+
+fn tokens(input: &str) -> Vec<Token> {
+    Lexer::new()
+        .stream(input.chars())
+        .map(|t| t.token)
+        .collect()
+}
+
+fn ident(s: &str) -> Token {
+    Token::Ident(s.to_string())
+}
+
+#[test]
+fn test_keywords() {
+    assert_eq!(
+        vec![
+            Token::KeywordPrint,
+            ident("printf"),
+            Token::LeftParenthesis,
+            ident("print_2"),
+            Token::RightParenthesis,
+            Token::KeywordPrint,
+            Token::LeftParenthesis,
+            Token::RightParenthesis,
+            ident("prin"),
+        ],
+        tokens("print printf(print_2) print() prin")
+    );
+}
+
+#[test]
+fn test_keyword_before_other_character() {
+    // A character that no rule continues ends the keyword.
+    assert_eq!(vec![Token::KeywordPrint, Token::Error], tokens("print$"));
+}
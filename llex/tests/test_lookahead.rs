@@ -0,0 +1,70 @@
+use llex::{lexer, LexerItem, PeekableLexer};
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Ident(String),
+        Integer(i64),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"[0-9]+" => Some(Token::Integer(text.parse().unwrap())),
+    r"[a-z]+" => Some(Token::Ident(text.to_string())),
+    r"=" => Token::Equals,
+}
+
+fn peek_nth<S>(stream: &mut PeekableLexer<S>, n: usize) -> Option<Token>
+where
+    S: Iterator<Item = LexerItem<Token>>,
+{
+    stream.peek_nth(n).map(|t| t.token.clone())
+}
+
+#[test]
+fn test_peek() {
+    let lexer = Lexer::new();
+    let mut stream = lexer.stream("x = 1 y".chars()).lookahead();
+
+    // Peeking does not consume, so repeated peeks return the same token.
+    let x = Token::Ident("x".to_string());
+    assert_eq!(Some(&x), stream.peek().map(|t| &t.token));
+    assert_eq!(Some(&x), stream.peek().map(|t| &t.token));
+    assert_eq!(Some(Token::Equals), stream.peek2().map(|t| t.token.clone()));
+    assert_eq!(Some(Token::Integer(1)), peek_nth(&mut stream, 2));
+
+    // Tokens are yielded in order, whether they were peeked at or not.
+    assert_eq!(Some(x), stream.next().map(|t| t.token));
+    assert_eq!(Some(Token::Equals), stream.peek().map(|t| t.token.clone()));
+    assert_eq!(Some(Token::Equals), stream.next().map(|t| t.token));
+    assert_eq!(Some(Token::Integer(1)), stream.next().map(|t| t.token));
+}
+
+#[test]
+fn test_peek_past_end() {
+    let lexer = Lexer::new();
+    let mut stream = lexer.stream("x = 1 y".chars()).lookahead();
+    for _ in 0..3 {
+        stream.next();
+    }
+
+    // Looking past the end of input returns None, without losing the tokens before it.
+    assert_eq!(None, peek_nth(&mut stream, 1));
+    let y = Token::Ident("y".to_string());
+    assert_eq!(Some(&y), stream.peek().map(|t| &t.token));
+    assert_eq!(Some(6..7), stream.peek().map(|t| t.m.range()));
+    assert_eq!(Some(y), stream.next().map(|t| t.token));
+
+    assert!(stream.peek().is_none());
+    assert!(stream.peek().is_none());
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn test_peek_empty() {
+    let lexer = Lexer::new();
+    let mut empty = lexer.stream("".chars()).lookahead();
+    assert!(empty.peek().is_none());
+    assert!(empty.peek2().is_none());
+    assert!(empty.next().is_none());
+}
@@ -0,0 +1,94 @@
+use llex::{lexer, LexerRules};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Register(u32),
+    KeywordIf,
+    KeywordElse,
+    Assign,
+    Equals,
+    Plus,
+    Error,
+    OperatorError,
+}
+
+lexer! {
+    struct Keywords;
+    fn stream;
+    (text) -> Token, Token::Error;
+
+    r"\s" => None,
+    r"if" => Some(Token::KeywordIf),
+    r"else" => Some(Token::KeywordElse),
+    r"[a-z]+" => Some(Token::Ident(text.to_string())),
+}
+
+lexer! {
+    struct Operators;
+    fn stream;
+    (text) -> Token, Token::OperatorError;
+
+    r"=" => Some(Token::Assign),
+    r"==" => Some(Token::Equals),
+    r"\+" => Some(Token::Plus),
+    r"x[0-9]+" => Some(Token::Register(text[1..].parse().unwrap())),
+    // Loses to the keywords lexer, which also matches "else".
+    r"else" => Some(Token::Ident(text.to_string())),
+}
+
+fn ident(s: &str) -> Token {
+    Token::Ident(s.to_string())
+}
+
+#[test]
+fn test_merge() {
+    let merged = Keywords::new().merge(Operators::new());
+    let items: Vec<_> = merged
+        .stream("if x12 == y else x = x+1 $".chars())
+        .collect();
+    let tokens: Vec<_> = items.iter().map(|item| item.token.clone()).collect();
+
+    assert_eq!(
+        vec![
+            Token::KeywordIf,
+            Token::Register(12),
+            Token::Equals,
+            ident("y"),
+            Token::KeywordElse,
+            ident("x"),
+            Token::Assign,
+            ident("x"),
+            Token::Plus,
+            // Neither lexer matches "1" or "$", so the keywords lexer produces errors.
+            Token::Error,
+            Token::Error,
+        ],
+        tokens
+    );
+    // The longest match of either lexer wins.
+    assert_eq!(3..6, items[1].m.range());
+}
+
+#[test]
+fn test_unmerged() {
+    // Either lexer alone only recognizes its own rules.
+    let keywords: Vec<_> = Keywords::new()
+        .stream("x12".chars())
+        .map(|t| t.token)
+        .collect();
+    assert_eq!(vec![ident("x"), Token::Error, Token::Error], keywords);
+
+    let operators: Vec<_> = Operators::new()
+        .stream("x1 y".chars())
+        .map(|t| t.token)
+        .collect();
+    assert_eq!(
+        vec![
+            Token::Register(1),
+            Token::OperatorError,
+            Token::OperatorError
+        ],
+        operators
+    );
+}
@@ -0,0 +1,52 @@
+use llex::lexer;
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Ident(String),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"<" => Token::Less,
+    r">" => Token::Greater,
+    r">>" => vec![Token::Greater, Token::Greater],
+    r"#" => Vec::<Token>::new(),
+    r"[A-Za-z][A-Za-z0-9]*" => Some(Token::Ident(text.to_string())),
+}
+
+#[test]
+fn test_multiple_tokens() {
+    let lexer = Lexer::new();
+    let items: Vec<_> = lexer.stream("Vec<Vec<u8>> x".chars()).collect();
+    let tokens: Vec<_> = items.iter().map(|item| item.token.clone()).collect();
+
+    assert_eq!(
+        vec![
+            Token::Ident("Vec".to_string()),
+            Token::Less,
+            Token::Ident("Vec".to_string()),
+            Token::Less,
+            Token::Ident("u8".to_string()),
+            Token::Greater,
+            Token::Greater,
+            Token::Ident("x".to_string()),
+        ],
+        tokens
+    );
+
+    // Both `>` tokens carry the position of `>>`, and lexing resumes after it.
+    assert_eq!(10..12, items[5].m.range());
+    assert_eq!(10..12, items[6].m.range());
+    assert_eq!(13..14, items[7].m.range());
+}
+
+#[test]
+fn test_no_tokens() {
+    // An empty list of tokens emits nothing, like None.
+    let tokens: Vec<_> = Lexer::new()
+        .stream("a#>".chars())
+        .map(|t| t.token)
+        .collect();
+    assert_eq!(vec![Token::Ident("a".to_string()), Token::Greater], tokens);
+}
@@ -0,0 +1,44 @@
+use std::ops::Range;
+
+use llex::lexer;
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text, pos) -> enum Token {
+        Ident(String, Range<usize>),
+        Unknown(Range<usize>),
+    }, Token::Unknown(pos);
+
+    r"\s" => None,
+    r"[A-Za-z]+" => Some(Token::Ident(text.to_string(), pos)),
+}
+
+fn tokens(input: &str) -> Vec<Token> {
+    Lexer::new()
+        .stream(input.chars())
+        .map(|t| t.token)
+        .collect()
+}
+
+#[test]
+fn test_positions() {
+    assert_eq!(
+        vec![
+            Token::Ident("ab".to_string(), 0..2),
+            Token::Ident("cde".to_string(), 4..7),
+            Token::Unknown(8..9),
+            Token::Ident("f".to_string(), 10..11),
+        ],
+        tokens("ab  cde ? f")
+    );
+}
+
+#[test]
+fn test_positions_count_characters() {
+    // Positions are character indexes, as in the matches of the stream.
+    assert_eq!(
+        vec![Token::Unknown(0..1), Token::Ident("ab".to_string(), 2..4)],
+        tokens("é ab")
+    );
+}
@@ -0,0 +1,48 @@
+use llex::lexer;
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Ident(String),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"[A-Za-z_][A-Za-z0-9_]*" => Some(Token::Ident(text.to_string())),
+    #[priority = 1]
+    r"if" => Token::KeywordIf,
+    // Never wins, so it would drop the word if it did.
+    r"else" => None,
+    #[priority = 2]
+    r"[0-9]+" => Token::Integer,
+    #[priority = 2]
+    r"[0-9]" => None,
+}
+
+fn tokens(input: &str) -> Vec<Token> {
+    Lexer::new()
+        .stream(input.chars())
+        .map(|t| t.token)
+        .collect()
+}
+
+#[test]
+fn test_priority_over_order() {
+    // A higher priority wins over an earlier rule, but rules without one keep declaration order.
+    assert_eq!(
+        vec![Token::KeywordIf, Token::Ident("else".to_string())],
+        tokens("if else")
+    );
+}
+
+#[test]
+fn test_longest_match_first() {
+    // Priorities only break ties between matches of the same length.
+    assert_eq!(vec![Token::Ident("iffy".to_string())], tokens("iffy"));
+}
+
+#[test]
+fn test_equal_priority() {
+    // Rules of equal priority are resolved by declaration order.
+    assert_eq!(vec![Token::Integer, Token::Integer], tokens("1 23"));
+}
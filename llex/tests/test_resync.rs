@@ -0,0 +1,46 @@
+use llex::lexer;
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Integer(i64),
+        Unknown(String),
+    }, Token::Unknown(text.to_string());
+    resync char::is_whitespace;
+
+    r"\s" => None,
+    r"[0-9]+" => Some(Token::Integer(text.parse().unwrap())),
+}
+
+fn items(input: &str) -> Vec<(Token, std::ops::Range<usize>)> {
+    Lexer::new()
+        .stream(input.chars())
+        .map(|t| (t.token, t.m.range()))
+        .collect()
+}
+
+#[test]
+fn test_resync() {
+    // The run of illegal characters, up to the next whitespace, produces one error.
+    assert_eq!(
+        vec![
+            (Token::Integer(12), 0..2),
+            (Token::Unknown("@#$%1".to_string()), 3..8),
+            (Token::Integer(34), 9..11),
+        ],
+        items("12 @#$%1 34")
+    );
+}
+
+#[test]
+fn test_resync_to_end() {
+    // Without a character satisfying the predicate, the rest of the input is skipped.
+    assert_eq!(
+        vec![
+            (Token::Integer(1), 0..1),
+            (Token::Unknown("@#".to_string()), 2..4),
+        ],
+        items("1 @#")
+    );
+}
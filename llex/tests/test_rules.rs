@@ -0,0 +1,73 @@
+use llex::{lexer, Cursor, Tokens};
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Number(i64),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"[0-9]+" => Some(Token::Number(text.parse().unwrap())),
+    r"\+" => Token::Plus,
+    r"[a-z]+" => None,
+}
+
+#[test]
+fn test_rules() {
+    let rules = Lexer::rules();
+
+    let patterns: Vec<_> = rules.iter().map(|(pattern, _)| *pattern).collect();
+    assert_eq!(vec![r"\s", r"[0-9]+", r"\+", r"[a-z]+"], patterns);
+
+    // The spacing of the tokens may vary, so compare without whitespace.
+    let actions: Vec<String> = rules
+        .iter()
+        .map(|(_, action)| action.split_whitespace().collect())
+        .collect();
+    assert_eq!(
+        vec![
+            "None",
+            "Some(Token::Number(text.parse().unwrap()))",
+            "Token::Plus",
+            "None"
+        ],
+        actions
+    );
+}
+
+#[test]
+fn test_advance_with_rule() {
+    // The rule that matched each part of the input, by declaration order.
+    let input = "12 + ab ? 3";
+    let lexer = Lexer::new();
+    let mut matched = Vec::new();
+    let mut pos = Cursor::default();
+    while let (Some((tokens, rule)), next) = lexer.advance_with_rule(input, pos) {
+        if rule == Some(1) {
+            assert!(matches!(tokens, Tokens::One(Token::Number(_))));
+        }
+        matched.push(rule);
+        pos = next;
+    }
+    assert_eq!(
+        vec![
+            Some(1),
+            Some(0),
+            Some(2),
+            Some(0),
+            Some(3),
+            Some(0),
+            None,
+            Some(0),
+            Some(1),
+        ],
+        matched
+    );
+
+    // The error variant has no rule.
+    assert_eq!(
+        (Some((Tokens::One(Token::Error), None)), Cursor::new(9, 9)),
+        lexer.advance_with_rule(input, Cursor::new(8, 8))
+    );
+}
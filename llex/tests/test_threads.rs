@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use std::thread;
+
+use llex::lexer;
+
+lexer! {
+    struct Lexer;
+    fn stream;
+    (text) -> enum Token {
+        Number(i64),
+    }, Token::Error;
+
+    r"\s" => None,
+    r"[0-9]+" => Some(Token::Number(text.parse().unwrap())),
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_send_sync() {
+    assert_send_sync::<Lexer>();
+}
+
+#[test]
+fn test_shared_lexer() {
+    let lexer = Arc::new(Lexer::new());
+    let handles: Vec<_> = (0..4)
+        .map(|i| {
+            let lexer = Arc::clone(&lexer);
+            thread::spawn(move || {
+                let input = format!("{} {}", i, i * 10);
+                lexer
+                    .stream(input.chars())
+                    .map(|t| t.token)
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    for (i, handle) in handles.into_iter().enumerate() {
+        let i = i as i64;
+        assert_eq!(
+            vec![Token::Number(i), Token::Number(i * 10)],
+            handle.join().unwrap()
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use super::keywords::{self, Colon, Else, Equ, For, If, In, LBrace, Let, RBrace, Semicolon, While};
 use super::{ArrayIndex, Expr, Ident, Span, Spannable, Spanned, Type};
+use crate::scope::Scope;
 
 #[cfg(feature = "serde-impl")]
 use serde::{Deserialize, Serialize};
@@ -8,6 +9,8 @@ use serde::{Deserialize, Serialize};
 #[cfg_attr(feature = "serde-impl", derive(Serialize, Deserialize))]
 pub struct Block {
     pub statements: Vec<Statement>,
+    /// Symbols declared for the block, such as the parameters of a function body.
+    pub scope: Scope,
 
     pub lbrace_t: Spanned<LBrace>,
     pub rbrace_t: Spanned<RBrace>,
@@ -67,7 +70,7 @@ pub struct VarDeclaration {
 impl Spannable for VarDeclaration {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.let_t.span().start, self.semicolon_t.span().end)
+        Span::merge(self.let_t.span(), self.semicolon_t.span())
     }
 }
 
@@ -84,7 +87,7 @@ pub struct VarAssign {
 impl Spannable for VarAssign {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.lhs.span().start, self.semicolon_t.span().end)
+        Span::merge(self.lhs.span(), self.semicolon_t.span())
     }
 }
 
@@ -121,7 +124,7 @@ pub struct ForLoop {
 impl Spannable for ForLoop {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.for_t.span().start, self.body.span().end)
+        Span::merge(self.for_t.span(), self.body.span())
     }
 }
 
@@ -137,7 +140,7 @@ pub struct WhileLoop {
 impl Spannable for WhileLoop {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.while_t.span().start, self.body.span().end)
+        Span::merge(self.while_t.span(), self.body.span())
     }
 }
 
@@ -151,7 +154,7 @@ pub struct Break {
 impl Spannable for Break {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.break_t.span().start, self.semicolon_t.span().end)
+        Span::merge(self.break_t.span(), self.semicolon_t.span())
     }
 }
 
@@ -165,7 +168,7 @@ pub struct Continue {
 impl Spannable for Continue {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.continue_t.span().start, self.semicolon_t.span().end)
+        Span::merge(self.continue_t.span(), self.semicolon_t.span())
     }
 }
 
@@ -180,7 +183,7 @@ pub struct Return {
 impl Spannable for Return {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.return_t.span().start, self.semicolon_t.span().end)
+        Span::merge(self.return_t.span(), self.semicolon_t.span())
     }
 }
 
@@ -211,10 +214,10 @@ impl Spannable for IfBranch {
     #[inline]
     fn span(&self) -> Span {
         let end = match &self.else_body {
-            Some(b) => b.span().end,
-            None => self.body.span().end,
+            Some(b) => b.span(),
+            None => self.body.span(),
         };
-        Span::new(self.if_t.span().start, end)
+        Span::merge(self.if_t.span(), end)
     }
 }
 
@@ -235,8 +238,8 @@ impl Spannable for ElseBranch {
     #[inline]
     fn span(&self) -> Span {
         match self {
-            Self::If { branch, else_t } => Span::new(else_t.span().start, branch.span().end),
-            Self::Block { inner, else_t } => Span::new(else_t.span().start, inner.span().end),
+            Self::If { branch, else_t } => Span::merge(else_t.span(), branch.span()),
+            Self::Block { inner, else_t } => Span::merge(else_t.span(), inner.span()),
         }
     }
 }
@@ -252,6 +255,6 @@ pub struct ExprStatement {
 impl Spannable for ExprStatement {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.expr.span().start, self.semicolon_t.span().end)
+        Span::merge(self.expr.span(), self.semicolon_t.span())
     }
 }
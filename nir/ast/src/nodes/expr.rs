@@ -46,7 +46,7 @@ pub struct ArrayLiteral {
 impl Spannable for ArrayLiteral {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.lbracket_t.span().start, self.rbracket_t.span().end)
+        Span::merge(self.lbracket_t.span(), self.rbracket_t.span())
     }
 }
 
@@ -63,7 +63,7 @@ pub struct FunctionCall {
 impl Spannable for FunctionCall {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.function.span().start, self.rparen_t.span().end)
+        Span::merge(self.function.span(), self.rparen_t.span())
     }
 }
 
@@ -85,7 +85,7 @@ impl BinOpExpr {
 impl Spannable for BinOpExpr {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.e1.span().start, self.e2.span().end)
+        Span::merge(self.e1.span(), self.e2.span())
     }
 }
 
@@ -125,7 +125,7 @@ impl UnaryOpExpr {
 impl Spannable for UnaryOpExpr {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.op.span().start, self.operand.span().end)
+        Span::merge(self.op.span(), self.operand.span())
     }
 }
 
@@ -150,6 +150,6 @@ pub struct ArrayIndex {
 impl Spannable for ArrayIndex {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.array.span().start, self.rbracket_t.span().end)
+        Span::merge(self.array.span(), self.rbracket_t.span())
     }
 }
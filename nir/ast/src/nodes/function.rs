@@ -30,7 +30,7 @@ impl Function {
 impl Spannable for Function {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.vis.span().start, self.body.span().end)
+        Span::merge(self.vis.span(), self.body.span())
     }
 }
 
@@ -46,6 +46,6 @@ pub struct FunctionParam {
 impl Spannable for FunctionParam {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.name.span().start, self.ty.span().end)
+        Span::merge(self.name.span(), self.ty.span())
     }
 }
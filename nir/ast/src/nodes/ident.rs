@@ -34,13 +34,10 @@ pub struct Path {
 impl Spannable for Path {
     #[inline]
     fn span(&self) -> Span {
-        let (start, end) = self
-            .segments
-            .items
-            .first()
-            .map(|item| item.span().start)
-            .map(|start| (start, self.segments.items.last().unwrap().span().end))
-            .unwrap_or_else(|| (0, 0));
-        Span::new(start, end)
+        let segments = &self.segments.items;
+        match (segments.first(), segments.last()) {
+            (Some(first), Some(last)) => Span::merge(first.span(), last.span()),
+            _ => Span::EMPTY,
+        }
     }
 }
@@ -1,4 +1,4 @@
-use super::{Function, Span, Spannable, Struct};
+use super::{Function, Span, Spannable, Struct, Visibility};
 
 #[cfg(feature = "serde-impl")]
 use serde::{Deserialize, Serialize};
@@ -24,15 +24,14 @@ impl Default for Program {
 }
 
 impl Spannable for Program {
+    /// Return the span from the start of the first item to the end of the last item, or the
+    /// empty span at position 0 if there are no items.
     #[inline]
     fn span(&self) -> Span {
-        let (start, end) = self
-            .items
-            .first()
-            .map(|item| item.span().start)
-            .map(|start| (start, self.items.last().unwrap().span().end))
-            .unwrap_or_else(|| (0, 0));
-        Span::new(start, end)
+        match (self.items.first(), self.items.last()) {
+            (Some(first), Some(last)) => Span::merge(first.span(), last.span()),
+            _ => Span::EMPTY,
+        }
     }
 }
 
@@ -43,6 +42,17 @@ pub enum Item {
     Function(Function),
 }
 
+impl Item {
+    /// Return the visibility of the item.
+    #[inline]
+    pub fn vis(&self) -> &Visibility {
+        match self {
+            Self::Struct(s) => &s.vis,
+            Self::Function(f) => &f.vis,
+        }
+    }
+}
+
 impl Spannable for Item {
     #[inline]
     fn span(&self) -> Span {
@@ -52,3 +62,46 @@ impl Spannable for Item {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{keywords, Ident, Spanned, VisibilityKind};
+
+    #[test]
+    fn test_empty_span() {
+        assert_eq!(Span::new(0, 0), Program::new().span());
+    }
+
+    #[test]
+    fn test_span() {
+        // struct a {} struct b {}
+        let program = Program {
+            items: vec![structure("a", 0), structure("b", 12)],
+        };
+        assert_eq!(Span::new(0, 23), program.span());
+
+        let program = Program {
+            items: vec![structure("b", 12)],
+        };
+        assert_eq!(Span::new(12, 23), program.span());
+    }
+
+    /// Create the item `struct name {}` starting at `start`.
+    fn structure(name: &str, start: usize) -> Item {
+        let span = |offset: usize, len: usize| Span::new(start + offset, start + offset + len);
+        Item::Struct(Struct {
+            vis: Visibility {
+                kind: VisibilityKind::Private,
+                span: span(0, 0),
+            },
+            name: Ident {
+                name: Spanned::new(name.to_string(), span(7, 1)),
+            },
+            fields: Default::default(),
+            struct_t: Spanned::new(keywords::Struct, span(0, 6)),
+            lbrace_t: Spanned::new(keywords::LBrace, span(9, 1)),
+            rbrace_t: Spanned::new(keywords::RBrace, span(10, 1)),
+        })
+    }
+}
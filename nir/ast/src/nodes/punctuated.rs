@@ -32,14 +32,45 @@ where
     T: Spannable,
     S: Spannable,
 {
+    /// Return the span from the start of the first item to the end of the last item, or the
+    /// empty span at position 0 if there are no items.
     #[inline]
     fn span(&self) -> Span {
-        let (start, end) = self
-            .items
-            .first()
-            .map(|item| item.span().start)
-            .map(|start| (start, self.items.last().unwrap().span().end))
-            .unwrap_or_else(|| (0, 0));
-        Span::new(start, end)
+        match (self.items.first(), self.items.last()) {
+            (Some(first), Some(last)) => Span::merge(first.span(), last.span()),
+            _ => Span::EMPTY,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::keywords::Comma;
+    use crate::Spanned;
+
+    type Segments = Punctuated<Spanned<char>, Spanned<Comma>>;
+
+    #[test]
+    fn test_empty_span() {
+        assert_eq!(Span::new(0, 0), Segments::default().span());
+    }
+
+    #[test]
+    fn test_span() {
+        // a, b, c
+        let items = vec![
+            Spanned::new('a', Span::new(0, 1)),
+            Spanned::new('b', Span::new(3, 4)),
+            Spanned::new('c', Span::new(6, 7)),
+        ];
+        let seps = vec![
+            Spanned::new(Comma, Span::new(1, 2)),
+            Spanned::new(Comma, Span::new(4, 5)),
+        ];
+        assert_eq!(Span::new(0, 7), Segments::new(items, seps).span());
+
+        let items = vec![Spanned::new('a', Span::new(4, 5))];
+        assert_eq!(Span::new(4, 5), Segments::new(items, vec![]).span());
     }
 }
@@ -36,8 +36,78 @@ pub struct Span {
 }
 
 impl Span {
+    /// The empty span at position 0, for synthesized nodes that have no source text.
+    pub const EMPTY: Span = Span::new(0, 0);
+
     #[inline]
-    pub fn new(start: usize, end: usize) -> Self {
+    pub const fn new(start: usize, end: usize) -> Self {
         Self { start, end }
     }
+
+    /// Create a span that covers both spans, from the earliest start to the latest end.
+    #[inline]
+    pub const fn merge(self, other: Span) -> Self {
+        let start = if self.start < other.start {
+            self.start
+        } else {
+            other.start
+        };
+        let end = if self.end > other.end {
+            self.end
+        } else {
+            other.end
+        };
+        Self::new(start, end)
+    }
+
+    /// Return true if the offset is within the span; both the start and the end are included.
+    #[inline]
+    pub const fn contains(&self, offset: usize) -> bool {
+        self.start <= offset && offset <= self.end
+    }
+
+    /// Return true if the spans share at least one offset.
+    #[inline]
+    pub const fn overlaps(&self, other: &Span) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Span;
+
+    #[test]
+    fn test_contains() {
+        let span = Span::new(3, 6);
+        assert!(!span.contains(2));
+        assert!(span.contains(3));
+        assert!(span.contains(5));
+        assert!(span.contains(6));
+        assert!(!span.contains(7));
+
+        assert!(Span::EMPTY.contains(0));
+        assert!(!Span::EMPTY.contains(1));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let span = Span::new(3, 6);
+        assert!(span.overlaps(&Span::new(0, 3)));
+        assert!(span.overlaps(&Span::new(6, 9)));
+        assert!(span.overlaps(&Span::new(4, 5)));
+        assert!(!span.overlaps(&Span::new(0, 2)));
+        assert!(!span.overlaps(&Span::new(7, 9)));
+    }
+
+    #[test]
+    fn test_merge() {
+        // Disjoint spans, in either order.
+        let (a, b) = (Span::new(2, 4), Span::new(8, 10));
+        assert_eq!(Span::new(2, 10), a.clone().merge(b.clone()));
+        assert_eq!(Span::new(2, 10), Span::merge(b, a));
+
+        // Nested spans.
+        assert_eq!(Span::new(1, 9), Span::new(1, 9).merge(Span::new(3, 4)));
+    }
 }
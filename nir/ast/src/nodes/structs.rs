@@ -27,7 +27,7 @@ impl Struct {
 impl Spannable for Struct {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.struct_t.span().start, self.rbrace_t.span().end)
+        Span::merge(self.vis.span(), self.rbrace_t.span())
     }
 }
 
@@ -43,6 +43,6 @@ pub struct StructField {
 impl Spannable for StructField {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.vis.span().start, self.ty.span().end)
+        Span::merge(self.vis.span(), self.ty.span())
     }
 }
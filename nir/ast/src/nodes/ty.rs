@@ -81,6 +81,44 @@ pub struct ArrayType {
 impl Spannable for ArrayType {
     #[inline]
     fn span(&self) -> Span {
-        Span::new(self.lbracket_t.span().start, self.rbracket_t.span().end)
+        Span::merge(self.lbracket_t.span(), self.rbracket_t.span())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_array_type_span() {
+        // [[i32]]
+        let primitive = Type::Primitive(PrimitiveType {
+            kind: PrimitiveTypeKind::I32,
+            span: Span::new(2, 5),
+        });
+        let inner = Type::Array(Box::new(array(primitive, 1, 5)));
+        let outer = Type::Array(Box::new(array(inner.clone(), 0, 6)));
+
+        assert_eq!(Span::new(1, 6), inner.span());
+        assert_eq!(Span::new(0, 7), outer.span());
+    }
+
+    #[test]
+    fn test_merge() {
+        let a = Span::new(2, 5);
+        let b = Span::new(7, 9);
+
+        assert_eq!(Span::new(2, 9), Span::merge(a.clone(), b.clone()));
+        assert_eq!(Span::new(2, 9), Span::merge(b, a.clone()));
+        assert_eq!(Span::new(0, 5), Span::merge(a, Span::new(0, 3)));
+    }
+
+    /// Create an array type around `ty`, with brackets starting at `lbracket` and `rbracket`.
+    fn array(ty: Type, lbracket: usize, rbracket: usize) -> ArrayType {
+        ArrayType {
+            ty,
+            lbracket_t: Spanned::new(LBracket, Span::new(lbracket, lbracket + 1)),
+            rbracket_t: Spanned::new(RBracket, Span::new(rbracket, rbracket + 1)),
+        }
     }
 }
@@ -10,6 +10,14 @@ pub struct Visibility {
     pub span: Span,
 }
 
+impl Visibility {
+    /// Return whether the node is public.
+    #[inline]
+    pub fn is_public(&self) -> bool {
+        self.kind == VisibilityKind::Public
+    }
+}
+
 impl Spannable for Visibility {
     #[inline]
     fn span(&self) -> Span {
@@ -17,6 +25,7 @@ impl Spannable for Visibility {
     }
 }
 
+/// Visibility of an item or struct field. Nodes without a `pub` modifier are private.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde-impl", derive(Serialize, Deserialize))]
 pub enum VisibilityKind {
@@ -0,0 +1,44 @@
+use crate::{Literal, Reserved, Token, Type};
+
+/// Words lexed as reserved words, types, or boolean literals instead of identifiers.
+///
+/// The lexer matches these with the identifier rule and then looks the identifier up in this
+/// table, so a keyword is added here rather than with a rule of its own, and a longer identifier
+/// that begins with a keyword (e.g. `structure`) is never split.
+pub const KEYWORDS: &[(&str, Token)] = &[
+    ("pub", Token::Reserved(Reserved::Pub)),
+    ("using", Token::Reserved(Reserved::Using)),
+    ("struct", Token::Reserved(Reserved::Struct)),
+    ("fn", Token::Reserved(Reserved::Function)),
+    ("let", Token::Reserved(Reserved::Let)),
+    ("while", Token::Reserved(Reserved::While)),
+    ("for", Token::Reserved(Reserved::For)),
+    ("in", Token::Reserved(Reserved::In)),
+    ("break", Token::Reserved(Reserved::Break)),
+    ("continue", Token::Reserved(Reserved::Continue)),
+    ("bye", Token::Reserved(Reserved::Return)),
+    ("bool", Token::Type(Type::Bool)),
+    ("char", Token::Type(Type::Char)),
+    ("str", Token::Type(Type::Str)),
+    ("i8", Token::Type(Type::I8)),
+    ("i16", Token::Type(Type::I16)),
+    ("i32", Token::Type(Type::I32)),
+    ("i64", Token::Type(Type::I64)),
+    ("u8", Token::Type(Type::U8)),
+    ("u16", Token::Type(Type::U16)),
+    ("u32", Token::Type(Type::U32)),
+    ("u64", Token::Type(Type::U64)),
+    ("f32", Token::Type(Type::F32)),
+    ("f64", Token::Type(Type::F64)),
+    ("true", Token::Literal(Literal::Boolean(true))),
+    ("false", Token::Literal(Literal::Boolean(false))),
+];
+
+/// Return the token for a word in the keyword table, or [`None`] if it is an identifier.
+#[inline]
+pub fn keyword(word: &str) -> Option<Token> {
+    KEYWORDS
+        .iter()
+        .find(|(kw, _)| *kw == word)
+        .map(|(_, token)| token.clone())
+}
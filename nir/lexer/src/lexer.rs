@@ -1,4 +1,8 @@
-pub use crate::{Literal, Reserved, Token, Type};
+pub use crate::{Literal, Reserved, Token};
+
+use crate::keywords::keyword;
+
+use std::ops::Range;
 
 macro_rules! reserved {
     ($variant:ident) => {
@@ -6,12 +10,6 @@ macro_rules! reserved {
     };
 }
 
-macro_rules! ty {
-    ($variant:ident) => {
-        Some(Token::Type(Type::$variant))
-    };
-}
-
 macro_rules! literal {
     ($variant:expr) => {
         Some(Token::Literal($variant))
@@ -25,32 +23,6 @@ llex::lexer! {
 
     r"\s" => None,
 
-    "pub" => reserved!(Pub),
-    "using" => reserved!(Using),
-    "struct" => reserved!(Struct),
-    "fn" => reserved!(Function),
-    "let" => reserved!(Let),
-    "while" => reserved!(While),
-    "for" => reserved!(For),
-    "in" => reserved!(In),
-    "break" => reserved!(Break),
-    "continue" => reserved!(Continue),
-    "bye" => reserved!(Return),
-
-    "bool" => ty!(Bool),
-    "char" => ty!(Char),
-    "str" => ty!(Str),
-    "i8" => ty!(I8),
-    "i16" => ty!(I16),
-    "i32" => ty!(I32),
-    "i64" => ty!(I64),
-    "u8" => ty!(U8),
-    "u16" => ty!(U16),
-    "u32" => ty!(U32),
-    "u64" => ty!(U64),
-    "f32" => ty!(F32),
-    "f64" => ty!(F64),
-
     r"{" => reserved!(LBrace),
     r"}" => reserved!(RBrace),
     r"\[" => reserved!(LBracket),
@@ -83,10 +55,8 @@ llex::lexer! {
     r"\|" => reserved!(Bar),
     r"\|\|" => reserved!(DoubleBar),
 
-    "true" => literal!(Literal::Boolean(true)),
-    "false" => literal!(Literal::Boolean(false)),
-
-    r"[A-Za-z_][A-Za-z0-9_]*" => Some(Token::Ident(text.to_string())),
+    // Reserved words, types, and boolean literals are identifiers found in the keyword table.
+    r"[A-Za-z_][A-Za-z0-9_]*" => Some(keyword(text).unwrap_or_else(|| Token::Ident(text.to_string()))),
     r#""[^"]*""# => {
         let text = text.strip_prefix("\"").unwrap();
         let text = text.strip_suffix("\"").unwrap();
@@ -103,9 +73,38 @@ llex::lexer! {
     }
 }
 
+impl Lexer {
+    /// Lex the input into tokens, each with the range of byte offsets of its whole lexeme in the
+    /// input.
+    #[inline]
+    pub fn spanned<'a>(
+        &'a self,
+        input: &'a str,
+    ) -> impl Iterator<Item = (Token, Range<usize>)> + 'a {
+        // Matches are positioned by character, so convert their positions to byte offsets while
+        // walking through the input once.
+        let mut chars = input.chars();
+        let (mut char_pos, mut byte_pos) = (0, 0);
+        let mut to_byte = move |pos: usize| {
+            while char_pos < pos {
+                byte_pos += chars.next().map_or(0, char::len_utf8);
+                char_pos += 1;
+            }
+            byte_pos
+        };
+
+        self.stream(input.chars()).map(move |item| {
+            let start = to_byte(item.m.start);
+            let end = to_byte(item.m.end);
+            (item.token, start..end)
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::{Type, KEYWORDS};
 
     #[test]
     fn test_keywords() {
@@ -120,6 +119,46 @@ mod test {
         assert_eq!(tokens.next(), Some(Token::Reserved(Reserved::In)));
     }
 
+    #[test]
+    fn test_keyword_table() {
+        let mut tokens = lex("structure struct i32x i32 trueish true _fn");
+
+        assert_eq!(tokens.next(), Some(Token::Ident("structure".to_string())));
+        assert_eq!(tokens.next(), Some(Token::Reserved(Reserved::Struct)));
+        assert_eq!(tokens.next(), Some(Token::Ident("i32x".to_string())));
+        assert_eq!(tokens.next(), Some(Token::Type(Type::I32)));
+        assert_eq!(tokens.next(), Some(Token::Ident("trueish".to_string())));
+        assert_eq!(tokens.next(), Some(Token::Literal(Literal::Boolean(true))));
+        assert_eq!(tokens.next(), Some(Token::Ident("_fn".to_string())));
+        assert_eq!(tokens.next(), None);
+
+        // Every keyword in the table is lexed as its token.
+        for (word, token) in KEYWORDS {
+            assert_eq!(lex(word).collect::<Vec<_>>(), vec![token.clone()]);
+        }
+    }
+
+    #[test]
+    fn test_spans() {
+        let lexer = Lexer::new();
+        let spanned: Vec<_> = lexer.spanned("  struct").collect();
+        assert_eq!(vec![(Token::Reserved(Reserved::Struct), 2..8)], spanned);
+
+        // Spans cover whole lexemes, in bytes rather than characters.
+        let spanned: Vec<_> = lexer.spanned("x: i32 = \"é\";").collect();
+        assert_eq!(
+            vec![
+                (Token::Ident("x".to_string()), 0..1),
+                (Token::Reserved(Reserved::Colon), 1..2),
+                (Token::Type(Type::I32), 3..6),
+                (Token::Reserved(Reserved::Equ), 7..8),
+                (Token::Literal(Literal::Str("é".to_string())), 9..13),
+                (Token::Reserved(Reserved::Semicolon), 13..14),
+            ],
+            spanned
+        );
+    }
+
     fn lex(input: &str) -> impl Iterator<Item = Token> {
         let lexer = Lexer::new();
         let tokens: Vec<_> = lexer.stream(input.chars()).map(|item| item.token).collect();
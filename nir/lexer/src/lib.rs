@@ -1,6 +1,8 @@
+mod keywords;
 mod lexer;
 mod reserved;
 
+pub use keywords::{keyword, KEYWORDS};
 pub use lexer::Lexer;
 pub use reserved::Reserved;
 
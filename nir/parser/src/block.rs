@@ -1,5 +1,6 @@
 use crate::{ExpectedToken, Parse, ParseError, ParseInput, ParseResult, Symbol};
 
+use ast::scope::Scope;
 use ast::{
     keywords::{Equ, LBrace, RBrace, Semicolon},
     ArrayIndex, Block, Break, Continue, ElseBranch, Expr, ExprStatement, ForLoop, IfBranch, IfElse,
@@ -19,8 +20,17 @@ where
         // Parse statements.
         let mut statements = Vec::new();
         while !input.peek_is(&reserved!(RBrace)) {
-            let statement = input.parse()?;
-            statements.push(statement);
+            match input.parse() {
+                Ok(statement) => statements.push(statement),
+                // Skip the rest of a malformed statement to report errors in later ones.
+                Err(()) if !input.is_empty() => {
+                    input.recover_until(&[reserved!(Semicolon), reserved!(RBrace)]);
+                    if input.peek_is(&reserved!(Semicolon)) {
+                        input.next();
+                    }
+                }
+                Err(()) => return Err(()),
+            }
         }
 
         // Parse right brace.
@@ -28,6 +38,7 @@ where
 
         Ok(Self {
             statements,
+            scope: Scope::new(),
             lbrace_t,
             rbrace_t,
         })
@@ -348,3 +359,38 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{ExpectedToken, ParseError, ParseInput};
+
+    use ast::{Block, Statement};
+    use lexer::{Reserved, Token};
+
+    #[test]
+    fn test_recover_statements() {
+        let mut input = ParseInput::lex("{ let a b = 1; let b = 2; a = 1; }");
+        let parsed: Block = input.parse().unwrap();
+
+        // The malformed statements are skipped, and an error is reported for each.
+        assert_eq!(1, parsed.statements.len());
+        assert!(matches!(parsed.statements[0], Statement::VarAssign(_)));
+        let found: Vec<_> = input
+            .errors
+            .iter()
+            .map(|err| match err {
+                ParseError::MismatchedToken(sy, expected) => (sy.0.clone(), expected.clone()),
+                err => panic!("unexpected error {:?}", err),
+            })
+            .collect();
+        let colon = ExpectedToken::Reserved(Reserved::Colon);
+        assert_eq!(
+            vec![
+                (Token::Ident("b".into()), colon.clone()),
+                (Token::Reserved(Reserved::Equ), colon),
+            ],
+            found
+        );
+        assert!(input.is_empty());
+    }
+}
@@ -2,8 +2,8 @@ use crate::Symbol;
 
 use std::fmt;
 
-use ast::{Ident, Spannable};
-use lexer::Reserved;
+use ast::{Ident, Span, Spannable};
+use lexer::{Reserved, Token};
 
 #[cfg(feature = "serde-impl")]
 use serde::{Deserialize, Serialize};
@@ -24,12 +24,43 @@ pub enum ParseError {
 
     #[error("unexpected token {:?} at position {}, expected one of {:?}", .0.inner(), .0.span().start, .1)]
     UnexpectedToken(Symbol, Vec<ExpectedToken>),
+    #[error("expected {}, found {:?} at position {}..{}", .1, .0.inner(), .0.span().start, .0.span().end)]
+    MismatchedToken(Symbol, ExpectedToken),
     #[error("unexpected end-of-file")]
     UnexpectedEof(Vec<ExpectedToken>),
     #[error("lexer error")]
     LexerError,
 }
 
+impl ParseError {
+    /// Return the span of source text that the error points to, or [`None`] if the error is not
+    /// at a specific position (e.g. for unexpected end-of-file).
+    #[inline]
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ParseError::DuplicateIdent(ident) | ParseError::UndeclaredVariable(ident) => {
+                Some(ident.span())
+            }
+            ParseError::UnexpectedToken(sy, _) | ParseError::MismatchedToken(sy, _) => {
+                Some(sy.span())
+            }
+            ParseError::NoMainFunction | ParseError::UnexpectedEof(_) | ParseError::LexerError => {
+                None
+            }
+        }
+    }
+}
+
+/// Sort errors in source order by the start of their spans. Errors without a span are placed
+/// last, and errors at the same position keep their relative order.
+#[inline]
+pub fn sort_by_span(errors: &mut [ParseError]) {
+    errors.sort_by_key(|err| match err.span() {
+        Some(span) => (false, span.start),
+        None => (true, 0),
+    });
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde-impl", derive(Serialize, Deserialize))]
 pub enum ExpectedToken {
@@ -39,6 +70,19 @@ pub enum ExpectedToken {
     Type,
     Reserved(Reserved),
     Expr,
+    /// A specific token, such as a particular identifier.
+    Exact(Token),
+}
+
+impl From<&Token> for ExpectedToken {
+    /// Expect exactly the given token; reserved tokens are expected by their variant.
+    #[inline]
+    fn from(token: &Token) -> Self {
+        match token {
+            Token::Reserved(reserved) => ExpectedToken::Reserved(reserved.clone()),
+            _ => ExpectedToken::Exact(token.clone()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -65,6 +109,51 @@ impl fmt::Display for ExpectedToken {
             ExpectedToken::Type => write!(f, "<identifier>"),
             ExpectedToken::Reserved(reserved) => write!(f, "{}", reserved),
             ExpectedToken::Expr => write!(f, "<expression>"),
+            ExpectedToken::Exact(token) => write!(f, "{}", token),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{sort_by_span, ExpectedToken, ParseError};
+
+    use ast::{Ident, Span, Spanned};
+    use lexer::Token;
+
+    #[test]
+    fn test_sort_by_span() {
+        let ident = |name: &str, start| Ident {
+            name: Spanned::new(name.into(), Span::new(start, start + 1)),
+        };
+        let unexpected = |start| {
+            let sy = Spanned::new(Token::Unknown, Span::new(start, start));
+            ParseError::UnexpectedToken(sy, vec![ExpectedToken::Expr])
+        };
+
+        let mut errors = vec![
+            ParseError::UnexpectedEof(vec![ExpectedToken::Ident]),
+            unexpected(12),
+            ParseError::DuplicateIdent(ident("b", 7)),
+            ParseError::NoMainFunction,
+            ParseError::UndeclaredVariable(ident("a", 3)),
+            unexpected(7),
+        ];
+        sort_by_span(&mut errors);
+
+        let starts: Vec<_> = errors
+            .iter()
+            .map(|err| err.span().map(|span| span.start))
+            .collect();
+        assert_eq!(
+            vec![Some(3), Some(7), Some(7), Some(12), None, None],
+            starts
+        );
+
+        // Errors at the same position and errors without positions keep their order.
+        assert!(matches!(errors[1], ParseError::DuplicateIdent(_)));
+        assert!(matches!(errors[2], ParseError::UnexpectedToken(..)));
+        assert!(matches!(errors[4], ParseError::UnexpectedEof(_)));
+        assert!(matches!(errors[5], ParseError::NoMainFunction));
+    }
+}
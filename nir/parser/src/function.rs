@@ -1,8 +1,8 @@
-use crate::{Parse, ParseInput, ParseResult, Rsv, Symbol};
+use crate::{Parse, ParseError, ParseInput, ParseResult, Rsv, Symbol};
 
 use ast::{
-    keywords::Comma, punctuated::Punctuated, Function, FunctionParam, PrimitiveType,
-    PrimitiveTypeKind, Span, Type,
+    keywords::Comma, punctuated::Punctuated, scope::SymbolEntry, Block, Function, FunctionParam,
+    PrimitiveType, PrimitiveTypeKind, Span, Type, Visibility,
 };
 
 impl<I> Parse<I> for Function
@@ -13,60 +13,76 @@ where
     fn parse(input: &mut ParseInput<I>) -> ParseResult<Self> {
         // Parse visibility.
         let vis = input.parse()?;
-        // Parse fn token.
-        let fn_t = input.consume()?;
-        // Parse name.
-        let name = input.parse()?;
-        // Parse left parenthesis.
-        let lparen_t = input.consume()?;
-
-        // Parse function parameters.
-        let params = if input.peek_is(&reserved!(RParen)) {
-            // If next is right parenthesis, there are no parameters.
-            Punctuated::default()
-        } else {
-            let params = input.parse::<Punctuated<FunctionParam, Rsv<Comma>>>()?;
-            let seps = params
-                .seps
-                .into_iter()
-                .map(|sep| sep.into_inner())
-                .collect();
-            Punctuated::new(params.items, seps)
-        };
-
-        // Parse right parenthesis.
-        let rparen_t = input.consume()?;
-
-        // Parse arrow.
-        let arrow_t = input.consume_opt()?;
-        // Parse the return type (if any).
-        let return_type = match arrow_t {
-            Some(_) => input.parse()?,
-            None => {
-                let last_pos = input.last_pos();
-
-                Type::Primitive(PrimitiveType {
-                    kind: PrimitiveTypeKind::Unit,
-                    span: Span::new(last_pos, last_pos),
-                })
-            }
-        };
+        parse_function(input, vis)
+    }
+}
 
-        // Parse block.
-        let body = input.parse()?;
+/// Parse the rest of a function declaration after its visibility.
+#[inline]
+pub(crate) fn parse_function<I>(input: &mut ParseInput<I>, vis: Visibility) -> ParseResult<Function>
+where
+    I: Iterator<Item = Symbol>,
+{
+    // Parse fn token.
+    let fn_t = input.consume()?;
+    // Parse name.
+    let name = input.parse()?;
+    // Parse left parenthesis.
+    let lparen_t = input.consume()?;
 
-        Ok(Self {
-            vis,
-            name,
-            params,
-            return_type,
-            body,
-            fn_t,
-            lparen_t,
-            rparen_t,
-            arrow_t,
-        })
+    // Parse function parameters.
+    let params = if input.peek_is(&reserved!(RParen)) {
+        // If next is right parenthesis, there are no parameters.
+        Punctuated::default()
+    } else {
+        let params = input.parse::<Punctuated<FunctionParam, Rsv<Comma>>>()?;
+        let seps = params
+            .seps
+            .into_iter()
+            .map(|sep| sep.into_inner())
+            .collect();
+        Punctuated::new(params.items, seps)
+    };
+
+    // Parse right parenthesis.
+    let rparen_t = input.consume()?;
+
+    // Parse arrow.
+    let arrow_t = input.consume_opt()?;
+    // Parse the return type (if any).
+    let return_type = match arrow_t {
+        Some(_) => input.parse()?,
+        None => {
+            let last_pos = input.last_pos();
+
+            Type::Primitive(PrimitiveType {
+                kind: PrimitiveTypeKind::Unit,
+                span: Span::new(last_pos, last_pos),
+            })
+        }
+    };
+
+    // Parse block.
+    let mut body: Block = input.parse()?;
+
+    // Enter parameters into the scope of the body.
+    for param in &params.items {
+        if !body.scope.insert_nodup(param.name.clone(), SymbolEntry {}) {
+            input.error(ParseError::DuplicateIdent(param.name.clone()));
+        }
     }
+
+    Ok(Function {
+        vis,
+        name,
+        params,
+        return_type,
+        body,
+        fn_t,
+        lparen_t,
+        rparen_t,
+        arrow_t,
+    })
 }
 
 impl<I> Parse<I> for FunctionParam
@@ -82,3 +98,72 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{ParseError, ParseInput};
+
+    use ast::{Function, PrimitiveTypeKind, Spannable, Type};
+
+    #[test]
+    fn test_no_params() {
+        let mut input = ParseInput::lex("fn main() -> i32 {}");
+        let parsed: Function = input.parse().unwrap();
+
+        assert!(input.errors.is_empty());
+        assert_eq!("main", parsed.name.name_str());
+        assert!(parsed.params_vec().is_empty());
+        assert!(parsed.arrow_t.is_some());
+        assert!(matches!(
+            &parsed.return_type,
+            Type::Primitive(ty) if ty.kind == PrimitiveTypeKind::I32
+        ));
+        assert!(parsed.body.scope.inner.is_empty());
+    }
+
+    #[test]
+    fn test_params() {
+        let mut input = ParseInput::lex("fn f(a: i32, b: [u8]) -> bool {}");
+        let parsed: Function = input.parse().unwrap();
+
+        assert!(input.errors.is_empty());
+        let params = parsed.params_vec();
+        assert_eq!(2, params.len());
+        assert_eq!("a", params[0].name.name_str());
+        assert!(matches!(params[0].ty, Type::Primitive(_)));
+        assert_eq!("b", params[1].name.name_str());
+        assert!(matches!(params[1].ty, Type::Array(_)));
+
+        // Parameters are in the scope of the body.
+        assert!(parsed.body.scope.contains("a"));
+        assert!(parsed.body.scope.contains("b"));
+    }
+
+    #[test]
+    fn test_omitted_return_type() {
+        let mut input = ParseInput::lex("fn f(a: i32) {}");
+        let parsed: Function = input.parse().unwrap();
+
+        assert!(input.errors.is_empty());
+        assert!(parsed.arrow_t.is_none());
+        match &parsed.return_type {
+            Type::Primitive(ty) => {
+                assert_eq!(PrimitiveTypeKind::Unit, ty.kind);
+                // The unit type is placed after the parameter list.
+                assert_eq!(parsed.rparen_t.span().end, ty.span.start);
+            }
+            ty => panic!("unexpected return type {:?}", ty),
+        }
+    }
+
+    #[test]
+    fn test_duplicate_params() {
+        let mut input = ParseInput::lex("fn f(a: i32, a: u8) {}");
+        let _: Function = input.parse().unwrap();
+
+        match &input.errors[..] {
+            [ParseError::DuplicateIdent(ident)] => assert_eq!("a", ident.name_str()),
+            errors => panic!("unexpected errors {:?}", errors),
+        }
+    }
+}
@@ -1,6 +1,6 @@
-use crate::{Parse, ParseInput, Symbol};
+use crate::{function::parse_function, structs::parse_struct, Parse, ParseInput, Symbol};
 
-use ast::{Function, Item, Struct};
+use ast::Item;
 
 impl<I> Parse<I> for Item
 where
@@ -9,7 +9,7 @@ where
     /// Parse a top-level item, either a struct or function declaration.
     #[inline]
     fn parse(input: &mut ParseInput<I>) -> Result<Self, ()> {
-        // Parse visibility and replace later.
+        // Parse visibility, which applies to the struct or function that follows.
         let vis = input.parse()?;
 
         // Ensure that next token is not another visibility token.
@@ -29,21 +29,9 @@ where
 
         let item = match &peeked.0 {
             // Parse a struct.
-            reserved!(Struct) => {
-                let mut s: Struct = input.parse()?;
-                // Patch visibility.
-                s.vis = vis;
-
-                Item::Struct(s)
-            }
+            reserved!(Struct) => Item::Struct(parse_struct(input, vis)?),
             // Parse a function.
-            reserved!(Function) => {
-                let mut f: Function = input.parse()?;
-                // Patch visibility.
-                f.vis = vis;
-
-                Item::Function(f)
-            }
+            reserved!(Function) => Item::Function(parse_function(input, vis)?),
             // If neither, throw an error.
             _ => {
                 let next = input.next().unwrap();
@@ -58,3 +46,55 @@ where
         Ok(item)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{ParseError, ParseInput};
+
+    use ast::{Item, Span, Spannable, VisibilityKind};
+
+    fn parse(src: &str) -> Item {
+        let mut input = ParseInput::lex(src);
+        let item = input.parse().unwrap();
+        assert!(input.errors.is_empty());
+        item
+    }
+
+    #[test]
+    fn test_private() {
+        let item = parse("struct S {}");
+        assert!(matches!(item, Item::Struct(_)));
+        assert_eq!(VisibilityKind::Private, item.vis().kind);
+        assert_eq!(Span::new(0, 0), item.vis().span());
+        assert_eq!(Span::new(0, 10), item.span());
+
+        let item = parse("  fn f() {}");
+        assert!(matches!(item, Item::Function(_)));
+        assert!(!item.vis().is_public());
+        assert_eq!(Span::new(2, 2), item.vis().span());
+    }
+
+    #[test]
+    fn test_public() {
+        let item = parse("pub struct S {}");
+        assert!(matches!(item, Item::Struct(_)));
+        assert_eq!(VisibilityKind::Public, item.vis().kind);
+        assert_eq!(Span::new(0, 2), item.vis().span());
+        assert_eq!(Span::new(0, 14), item.span());
+
+        let item = parse("pub fn f() {}");
+        assert!(matches!(item, Item::Function(_)));
+        assert!(item.vis().is_public());
+        assert_eq!(Span::new(0, 12), item.span());
+    }
+
+    #[test]
+    fn test_repeated_pub() {
+        let mut input = ParseInput::lex("pub pub fn f() {}");
+        assert!(input.parse::<Item>().is_err());
+        assert!(matches!(
+            &input.errors[..],
+            [ParseError::UnexpectedToken(..)]
+        ));
+    }
+}
@@ -22,7 +22,7 @@ mod visibility;
 pub use ast;
 
 // Export error facilities directly.
-pub use self::error::{ExpectedToken, ParseError, Result};
+pub use self::error::{sort_by_span, ExpectedToken, ParseError, Result};
 // Export parser directly.
 pub use self::parser::Parser;
 
@@ -147,6 +147,33 @@ where
         }
     }
 
+    /// Consume the next token, which must be the expected one. Otherwise, an error is recorded
+    /// with the expected token and the token found in its place.
+    #[inline]
+    pub fn expect(&mut self, expected: &Token) -> ParseResult<Symbol> {
+        match self.peek() {
+            Some(next) if next.0 != *expected => {
+                self.next();
+                self.error(ParseError::MismatchedToken(next, expected.into()));
+                Err(())
+            }
+            _ => self.next_checked(expected, || vec![expected.into()]),
+        }
+    }
+
+    /// Skip tokens until the next one is one of `stops`, or the input is exhausted, to
+    /// resynchronize after an error. The stopping token is not consumed, and the skipped tokens
+    /// are not recorded as errors.
+    #[inline]
+    pub fn recover_until(&mut self, stops: &[Token]) {
+        while let Some(next) = self.peek() {
+            if stops.contains(&next.0) {
+                break;
+            }
+            self.next();
+        }
+    }
+
     /// Peek next item; returns cloned symbol for simplicity.
     #[inline]
     pub fn peek(&mut self) -> Option<Symbol> {
@@ -184,12 +211,12 @@ where
 
     #[inline]
     pub fn consume<R: ttypes::ReservedVariant>(&mut self) -> ParseResult<Spanned<R>> {
-        self.next_checked(&Token::Reserved(R::variant()), || {
-            vec![ExpectedToken::Reserved(R::variant())]
-        })
-        .map(|r| Spanned::new(R::new(), r.1))
+        self.expect(&Token::Reserved(R::variant()))
+            .map(|r| Spanned::new(R::new(), r.1))
     }
 
+    /// Consume the next token if it is the reserved word `R`. Otherwise, nothing is consumed and
+    /// no error is recorded.
     #[inline]
     pub fn consume_opt<R: ttypes::ReservedVariant>(&mut self) -> ParseResult<Option<Spanned<R>>> {
         match self.peek() {
@@ -202,6 +229,19 @@ where
     }
 }
 
+#[cfg(test)]
+impl ParseInput<std::vec::IntoIter<Symbol>> {
+    /// Lex source text into parser input.
+    pub fn lex(src: &str) -> Self {
+        let lexer = lexer::Lexer::new();
+        let tokens: Vec<_> = lexer
+            .spanned(src)
+            .map(|(token, span)| Spanned::new(token, ast::Span::new(span.start, span.end - 1)))
+            .collect();
+        Self::new(tokens.into_iter())
+    }
+}
+
 pub(crate) struct Rsv<R>(R)
 where
     R: ttypes::ReservedVariant;
@@ -249,3 +289,95 @@ where
         input.peek_is(&Token::Reserved(R::variant()))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ParseInput, Symbol};
+    use crate::{ExpectedToken, ParseError};
+
+    use ast::{Span, Spanned};
+    use lexer::{types as ttypes, Reserved, Token};
+
+    fn symbols(tokens: Vec<Token>) -> Vec<Symbol> {
+        tokens
+            .into_iter()
+            .enumerate()
+            .map(|(i, token)| Spanned::new(token, Span::new(i, i + 1)))
+            .collect()
+    }
+
+    #[test]
+    fn test_recover_until() {
+        let tokens = vec![
+            Token::Ident("a".into()),
+            Token::Reserved(Reserved::Plus),
+            Token::Reserved(Reserved::RBrace),
+            Token::Reserved(Reserved::Semicolon),
+            Token::Ident("b".into()),
+        ];
+        let stops = [
+            Token::Reserved(Reserved::Semicolon),
+            Token::Reserved(Reserved::RBrace),
+        ];
+
+        let mut input = ParseInput::new(symbols(tokens).into_iter());
+        input.recover_until(&stops);
+
+        // Stops before the first stop token, without recording errors.
+        assert_eq!(
+            Some(Token::Reserved(Reserved::RBrace)),
+            input.peek().map(|sy| sy.0)
+        );
+        assert_eq!(2, input.last_pos());
+        assert!(input.errors.is_empty());
+
+        // Does nothing if already at a stop token.
+        input.recover_until(&stops);
+        assert_eq!(2, input.last_pos());
+
+        // Drains the rest of the input if there are no stop tokens.
+        input.next();
+        input.recover_until(&[Token::Reserved(Reserved::Comma)]);
+        assert!(input.is_empty());
+        assert!(input.errors.is_empty());
+    }
+
+    #[test]
+    fn test_expect() {
+        let tokens = vec![Token::Reserved(Reserved::LParen), Token::Ident("a".into())];
+        let mut input = ParseInput::new(symbols(tokens).into_iter());
+
+        assert!(input.expect(&Token::Reserved(Reserved::LParen)).is_ok());
+        assert!(input.expect(&Token::Ident("b".into())).is_err());
+        assert!(input.expect(&Token::Reserved(Reserved::RParen)).is_err());
+
+        match &input.errors[..] {
+            [ParseError::MismatchedToken(sy, expected), ParseError::UnexpectedEof(eof_expected)] => {
+                assert_eq!(Token::Ident("a".into()), sy.0);
+                assert_eq!(Span::new(1, 2), sy.1);
+                assert_eq!(&ExpectedToken::Exact(Token::Ident("b".into())), expected);
+                assert_eq!(
+                    &vec![ExpectedToken::Reserved(Reserved::RParen)],
+                    eof_expected
+                );
+            }
+            errors => panic!("unexpected errors {:?}", errors),
+        }
+    }
+
+    #[test]
+    fn test_consume_opt() {
+        let tokens = vec![Token::Reserved(Reserved::Comma), Token::Ident("a".into())];
+        let mut input = ParseInput::new(symbols(tokens).into_iter());
+
+        let comma = input.consume_opt::<ttypes::Comma>();
+        assert!(matches!(comma, Ok(Some(_))));
+
+        // A mismatch leaves the token in the input.
+        assert!(matches!(input.consume_opt::<ttypes::Comma>(), Ok(None)));
+        assert!(input.errors.is_empty());
+        assert_eq!(1, input.last_pos());
+        assert_eq!(Some(Token::Ident("a".into())), input.next().map(|sy| sy.0));
+        assert!(input.is_empty());
+    }
+}
@@ -1,6 +1,8 @@
-use crate::{Parse, ParseInput, ParseResult, Rsv, Symbol};
+use crate::{Parse, ParseError, ParseInput, ParseResult, Symbol};
 
-use ast::{keywords::Comma, punctuated::Punctuated, Struct, StructField};
+use ast::{keywords::Comma, punctuated::Punctuated, Struct, StructField, Visibility};
+
+use std::collections::HashSet;
 
 impl<I> Parse<I> for Struct
 where
@@ -11,42 +13,50 @@ where
     fn parse(input: &mut ParseInput<I>) -> ParseResult<Self> {
         // Parse visibility.
         let vis = input.parse()?;
-        // Ensure next token is struct.
-        let struct_t = input.consume()?;
-        // Parse struct name.
-        let name = input.parse()?;
-        // Ensure next token is opening brace.
-        let lbrace_t = input.consume()?;
-
-        // Parse fields.
-        let fields = if input.peek_is(&reserved!(RBrace)) {
-            Punctuated::default()
-        } else {
-            let fields = input.parse::<Punctuated<StructField, Rsv<Comma>>>()?;
-
-            // TODO: ensure no duplicate fields.
-            // if has_duplicates(&fields.items) {}
-
-            let seps = fields
-                .seps
-                .into_iter()
-                .map(|sep| sep.into_inner())
-                .collect();
-            Punctuated::new(fields.items, seps)
-        };
-
-        // Consume closing brace.
-        let rbrace_t = input.consume()?;
+        parse_struct(input, vis)
+    }
+}
 
-        Ok(Self {
-            vis,
-            name,
-            fields,
-            struct_t,
-            lbrace_t,
-            rbrace_t,
-        })
+/// Parse the rest of a struct declaration after its visibility.
+#[inline]
+pub(crate) fn parse_struct<I>(input: &mut ParseInput<I>, vis: Visibility) -> ParseResult<Struct>
+where
+    I: Iterator<Item = Symbol>,
+{
+    // Ensure next token is struct.
+    let struct_t = input.consume()?;
+    // Parse struct name.
+    let name = input.parse()?;
+    // Ensure next token is opening brace.
+    let lbrace_t = input.consume()?;
+
+    // Parse fields, allowing a trailing comma.
+    let mut fields = Punctuated::default();
+    while !input.peek_is(&reserved!(RBrace)) {
+        fields.items.push(input.parse()?);
+
+        match input.consume_opt::<Comma>()? {
+            Some(comma_t) => fields.seps.push(comma_t.0),
+            None => break,
+        }
+    }
+
+    // Ensure no duplicate fields.
+    for field in duplicates(&fields.items) {
+        input.error(ParseError::DuplicateIdent(field.name.clone()));
     }
+
+    // Consume closing brace.
+    let rbrace_t = input.consume()?;
+
+    Ok(Struct {
+        vis,
+        name,
+        fields,
+        struct_t,
+        lbrace_t,
+        rbrace_t,
+    })
 }
 
 impl<I> Parse<I> for StructField
@@ -64,8 +74,74 @@ where
     }
 }
 
+/// Return the fields with the same name as an earlier field.
 #[inline]
-#[allow(dead_code)]
-fn has_duplicates(fields: &[StructField]) -> bool {
-    (1..fields.len()).any(|i| fields[i..].contains(&fields[i - 1]))
+fn duplicates(fields: &[StructField]) -> Vec<&StructField> {
+    let mut seen = HashSet::new();
+    fields
+        .iter()
+        .filter(|field| !seen.insert(field.name.name_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ParseError, ParseInput};
+
+    use ast::{Spannable, Struct, Type, VisibilityKind};
+
+    fn parse(src: &str) -> (Result<Struct, ()>, Vec<ParseError>) {
+        let mut input = ParseInput::lex(src);
+        let parsed = input.parse();
+        (parsed, input.errors)
+    }
+
+    #[test]
+    fn test_empty() {
+        let (parsed, errors) = parse("struct Empty {}");
+        let parsed = parsed.unwrap();
+
+        assert!(errors.is_empty());
+        assert_eq!("Empty", parsed.name.name_str());
+        assert!(parsed.fields().is_empty());
+    }
+
+    #[test]
+    fn test_fields() {
+        let (parsed, errors) = parse("struct Point { x: i32 }");
+        let parsed = parsed.unwrap();
+
+        assert!(errors.is_empty());
+        let fields = parsed.fields();
+        assert_eq!(1, fields.len());
+        assert_eq!("x", fields[0].name.name_str());
+        assert_eq!(VisibilityKind::Private, fields[0].vis.kind);
+        assert!(matches!(fields[0].ty, Type::Primitive(_)));
+
+        // Trailing comma and visibility.
+        let (parsed, errors) = parse("struct Point { pub x: i32, y: [u8], }");
+        let parsed = parsed.unwrap();
+
+        assert!(errors.is_empty());
+        let fields = parsed.fields();
+        assert_eq!(2, fields.len());
+        assert_eq!(2, parsed.fields.seps.len());
+        assert_eq!(VisibilityKind::Public, fields[0].vis.kind);
+        assert_eq!(VisibilityKind::Private, fields[1].vis.kind);
+        assert!(matches!(fields[1].ty, Type::Array(_)));
+    }
+
+    #[test]
+    fn test_duplicate_field() {
+        let (_, errors) = parse("struct Point { x: i32, y: i32, x: u8 }");
+
+        match &errors[..] {
+            [ParseError::DuplicateIdent(ident)] => {
+                assert_eq!("x", ident.name_str());
+                // The second declaration is reported.
+                assert_eq!(31, ident.span().start);
+            }
+            errors => panic!("unexpected errors {:?}", errors),
+        }
+    }
 }
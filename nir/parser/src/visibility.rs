@@ -6,6 +6,8 @@ impl<I> Parse<I> for Visibility
 where
     I: Iterator<Item = Symbol>,
 {
+    /// Parse an optional `pub` modifier. Without one, the visibility is private, with an empty
+    /// span at the start of the next token.
     #[inline]
     fn parse(input: &mut ParseInput<I>) -> Result<Self, ()> {
         let (kind, span) = match input.peek() {
@@ -15,7 +17,7 @@ where
                     (VisibilityKind::Public, spanned.1)
                 }
                 _ => {
-                    let pos = input.last_pos();
+                    let pos = peeked.1.start;
                     (VisibilityKind::Private, Span::new(pos, pos))
                 }
             },
@@ -6,7 +6,7 @@ use diagnostic::{AsDiagnostic, DiagnosticEmitError, DiagnosticFormat};
 use lexer::Lexer;
 use parser::{
     ast::{Program, Span, Spanned},
-    Parser,
+    sort_by_span, Parser,
 };
 
 #[derive(Debug)]
@@ -47,14 +47,19 @@ impl Compiler {
 
     #[inline]
     pub fn parse(&self, input: impl IntoIterator<Item = char>) -> Result<Program, CompileError> {
+        let input: String = input.into_iter().collect();
         let tokens = self
             .lexer
-            .stream(input.into_iter())
-            .map(|item| Spanned::new(item.token, Span::new(item.m.start, item.m.end - 1)));
+            .spanned(&input)
+            .map(|(token, span)| Spanned::new(token, Span::new(span.start, span.end - 1)));
 
         match self.parser.parse(tokens) {
             Ok(program) => Ok(program),
-            Err(errors) => Err(CompileError { parse: errors }),
+            Err(mut errors) => {
+                // Report errors in source order.
+                sort_by_span(&mut errors);
+                Err(CompileError { parse: errors })
+            }
         }
     }
 
@@ -61,6 +61,16 @@ impl AsDiagnostic for CompileError {
                     let expected = join_expected_token(expected);
                     diagnostic!(0, 0, "unexpected EOF, expected one of {}", expected)
                 }
+                ParseError::MismatchedToken(found, expected) => {
+                    let span = &found.1;
+                    diagnostic!(
+                        span.start,
+                        span.end,
+                        "unexpected '{}', expected '{}'",
+                        found.0,
+                        expected
+                    )
+                }
                 ParseError::UnexpectedToken(found, expected) => {
                     let expected = join_expected_token(expected);
                     let span = &found.1;
@@ -7,4 +7,8 @@ mod tests;
 mod parser;
 mod regexp;
 
+pub use crate::parser::ast::{syntax_tree, Node, Operator, SyntaxTree};
+pub use crate::parser::common::CharType;
+pub use crate::parser::dfa::{tree_to_dfa_with_tables, FindAll, FollowposTables, Match, DFA};
+pub use crate::parser::{regex_to_dfa, ParseError};
 pub use crate::regexp::*;
@@ -1,6 +1,8 @@
 use super::common::CharType;
 use super::ParseError;
 
+use std::fmt;
+
 #[derive(Debug, PartialEq)]
 pub enum Node<T, U> {
     Leaf(T),
@@ -25,6 +27,52 @@ enum OperatorFlag {
 
 pub type SyntaxTree = Node<CharType, Operator>;
 
+impl fmt::Display for SyntaxTree {
+    /// Write the tree with one node per line, each child indented under its parent, e.g. for `a*`:
+    ///
+    /// ```text
+    /// concat
+    ///   star
+    ///     'a'
+    ///   #
+    /// ```
+    ///
+    /// Leaves are written as their characters, with `.` for any character and `#` for the end
+    /// marker, and empty nodes as `ε`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl SyntaxTree {
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{:width$}", "", width = 2 * depth)?;
+        match self {
+            Node::Leaf(c) => writeln!(f, "{}", c),
+            Node::None => writeln!(f, "ε"),
+            Node::Branch(op, c1, c2) => {
+                writeln!(f, "{}", op)?;
+                c1.fmt_indented(f, depth + 1)?;
+                // The star has a single child; its second one is always empty.
+                if *op != Operator::Kleene {
+                    c2.fmt_indented(f, depth + 1)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operator::Kleene => write!(f, "star"),
+            Operator::Concat => write!(f, "concat"),
+            Operator::Alter => write!(f, "alter"),
+        }
+    }
+}
+
 pub fn syntax_tree(expr: &str) -> Result<SyntaxTree, ParseError> {
     let mut op_stack = Vec::new();
     let mut node_stack = Vec::new();
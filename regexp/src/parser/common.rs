@@ -1,3 +1,4 @@
+use std::fmt;
 use std::hash::Hash;
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -18,3 +19,15 @@ impl CharType {
         }
     }
 }
+
+impl fmt::Display for CharType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharType::Char(c) => write!(f, "{:?}", c),
+            CharType::Newline => write!(f, "{:?}", '\n'),
+            CharType::Whitespace => write!(f, "{:?}", ' '),
+            CharType::EndMarker => write!(f, "#"),
+            CharType::Any => write!(f, "."),
+        }
+    }
+}
@@ -22,18 +22,143 @@ impl DFA {
     pub fn is_match(&self, s: &str) -> bool {
         let mut pos = self.start;
         for c in s.chars() {
-            let char_type = CharType::from_plain(c);
-            pos = match self.trans.get(&pos, &char_type) {
-                Some(next) => *next,
-                None => match self.trans.get(&pos, &CharType::Any) {
-                    Some(next) => *next,
-                    None => return false,
-                },
+            pos = match self.step(pos, c) {
+                Some(next) => next,
+                None => return false,
             };
         }
 
         self.accepting.contains(&pos)
     }
+
+    // Finds the longest match at the beginning of the given string.
+    pub fn find(&self, s: &str) -> Option<Match> {
+        self.find_at(s, 0)
+    }
+
+    // Finds the longest match beginning at character index `start` of the given string, rather
+    // than anywhere after it.
+    pub fn find_at(&self, s: &str, start: usize) -> Option<Match> {
+        let rest = s.char_indices().nth(start).map_or("", |(i, _)| &s[i..]);
+        self.find_prefix(rest, start)
+    }
+
+    // Iterates through the successive non-overlapping matches in the given string, trying each
+    // character index in turn as the beginning of a match.
+    pub fn find_all<'d, 'i>(&'d self, s: &'i str) -> FindAll<'d, 'i> {
+        FindAll {
+            dfa: self,
+            rest: s,
+            pos: 0,
+            last_end: None,
+            finished: false,
+        }
+    }
+
+    // Finds the longest match at the beginning of `rest`, which begins at character index `start`
+    // of some input, reading the characters of the match only once.
+    fn find_prefix(&self, rest: &str, start: usize) -> Option<Match> {
+        let mut pos = self.start;
+        let mut span = Vec::new();
+        let mut end = if self.accepting.contains(&pos) {
+            Some(0)
+        } else {
+            None
+        };
+
+        for c in rest.chars() {
+            pos = match self.step(pos, c) {
+                Some(next) => next,
+                None => break,
+            };
+
+            span.push(c);
+            if self.accepting.contains(&pos) {
+                end = Some(span.len());
+            }
+        }
+
+        end.map(|end| {
+            span.truncate(end);
+            Match {
+                start,
+                end: start + end,
+                span,
+            }
+        })
+    }
+
+    fn step(&self, pos: u32, c: char) -> Option<u32> {
+        let char_type = CharType::from_plain(c);
+        match self.trans.get(&pos, &char_type) {
+            Some(next) => Some(*next),
+            None => self.trans.get(&pos, &CharType::Any).cloned(),
+        }
+    }
+}
+
+/// A match in some input string, with character indexes into it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Match {
+    /// Index of the first character matched.
+    pub start: usize,
+    /// Index of the last character matched + 1.
+    pub end: usize,
+    pub span: Vec<char>,
+}
+
+/// Iterator over the successive non-overlapping matches of a [`DFA`] in some input string.
+#[derive(Debug)]
+pub struct FindAll<'d, 'i> {
+    dfa: &'d DFA,
+    // The input after the character index `pos`, so that the input before it is never read
+    // again.
+    rest: &'i str,
+    pos: usize,
+    // Character index of the end of the last match, where an empty match is not reported.
+    last_end: Option<usize>,
+    // Whether a match has been tried at the end of input.
+    finished: bool,
+}
+
+impl<'d, 'i> FindAll<'d, 'i> {
+    // Advances past the given number of bytes and characters of the rest of the input.
+    fn advance(&mut self, bytes: usize, chars: usize) {
+        self.rest = &self.rest[bytes..];
+        self.pos += chars;
+    }
+}
+
+impl<'d, 'i> Iterator for FindAll<'d, 'i> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            // An empty match right after another match is skipped, as the regex crate does.
+            let m = self
+                .dfa
+                .find_prefix(self.rest, self.pos)
+                .filter(|m| !(m.span.is_empty() && self.last_end == Some(m.start)));
+
+            // Step past empty matches and unmatched characters so that the search always makes
+            // progress.
+            match (&m, self.rest.chars().next()) {
+                (Some(m), _) if !m.span.is_empty() => {
+                    let bytes = m.span.iter().map(|c| c.len_utf8()).sum();
+                    self.advance(bytes, m.span.len());
+                }
+                (_, Some(c)) => self.advance(c.len_utf8(), 1),
+                (_, None) => self.finished = true,
+            }
+
+            if let Some(m) = m {
+                self.last_end = Some(m.end);
+                return Some(m);
+            }
+        }
+
+        None
+    }
 }
 
 pub type DTran = Table<u32, CharType, u32>;
@@ -78,6 +203,16 @@ where
     }
 }
 
+impl<T, U, V> Default for Table<T, U, V>
+where
+    T: Eq + Hash,
+    U: Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Debug)]
 struct DState {
     label: u32,
@@ -109,10 +244,59 @@ macro_rules! hash_set {
     }};
 }
 
+/// The positions of an augmented syntax tree and their `followpos` sets, as computed by the
+/// direct construction of **Algorithm 3.36**.
+///
+/// Positions are numbered from 1, in the order their leaves appear in the expression, so the
+/// endmarker `#` is always the last position. An empty expression has no positions.
+#[derive(Debug, Default)]
+pub struct FollowposTables {
+    /// `firstpos` of the root, which makes up the start state of the DFA.
+    pub firstpos: HashSet<u32>,
+    /// The character at each position.
+    pub chars: HashMap<u32, CharType>,
+    /// The `followpos` set of each position.
+    pub followpos: HashMap<u32, HashSet<u32>>,
+}
+
+impl FollowposTables {
+    fn from_base(base: &DFABase) -> Self {
+        let mut tables = Self {
+            firstpos: base.root_firstpos.clone(),
+            ..Self::default()
+        };
+
+        for (&pos, leaf) in &base.leaves {
+            if let Some(c) = &leaf.character {
+                tables.chars.insert(pos, c.clone());
+            }
+            tables.followpos.insert(pos, leaf.followpos.clone());
+        }
+
+        tables
+    }
+}
+
+/// Like [`DFA::from_ast`], but also returns the position and `followpos` tables computed during
+/// the construction, for inspection.
+pub fn tree_to_dfa_with_tables(tree: &SyntaxTree) -> Result<(DFA, FollowposTables), ParseError> {
+    let base = calculate_functions(tree)?;
+    let dfa = base_to_dfa(base.as_ref())?;
+    let tables = base
+        .as_ref()
+        .map(FollowposTables::from_base)
+        .unwrap_or_default();
+    Ok((dfa, tables))
+}
+
+fn tree_to_dfa(tree: &SyntaxTree) -> Result<DFA, ParseError> {
+    base_to_dfa(calculate_functions(tree)?.as_ref())
+}
+
 /// Implements steps 2 and 3 of **Algorithm 3.36** in *Compilers: Principles,
 /// Techniques, and Tool*, Second Edition.
-fn tree_to_dfa(tree: &SyntaxTree) -> Result<DFA, ParseError> {
-    let base = match calculate_functions(tree)? {
+fn base_to_dfa(base: Option<&DFABase>) -> Result<DFA, ParseError> {
+    let base = match base {
         Some(b) => b,
         None => {
             return Ok(DFA {
@@ -3,7 +3,7 @@ pub mod common;
 pub mod dfa;
 pub mod error;
 
-pub use dfa::DFA;
+pub use dfa::{Match, DFA};
 pub use error::ParseError;
 
 /// This function attempts to implement **Algorithm 3.36**, the conversion of a regular expression
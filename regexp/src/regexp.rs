@@ -1,5 +1,6 @@
-use crate::parser::DFA;
+use crate::parser::dfa::FindAll;
 use crate::parser::{self, error::*};
+use crate::parser::{Match, DFA};
 
 #[derive(Debug)]
 pub struct RegExp {
@@ -11,6 +12,18 @@ impl RegExp {
         self.dfa.is_match(s)
     }
 
+    pub fn find(&self, s: &str) -> Option<Match> {
+        self.dfa.find(s)
+    }
+
+    pub fn find_at(&self, s: &str, start: usize) -> Option<Match> {
+        self.dfa.find_at(s, start)
+    }
+
+    pub fn find_all<'r, 'i>(&'r self, s: &'i str) -> FindAll<'r, 'i> {
+        self.dfa.find_all(s)
+    }
+
     pub fn new(expr: &str) -> Result<Self, ParseError> {
         let dfa = parser::regex_to_dfa(&expr)?;
         Ok(Self { dfa })
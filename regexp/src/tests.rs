@@ -1,4 +1,4 @@
-use crate::RegExp;
+use crate::{regex_to_dfa, syntax_tree, tree_to_dfa_with_tables, FollowposTables, RegExp, DFA};
 
 macro_rules! run_tests {
     ($exprs:expr, $valids:expr, $invalids:expr) => {{
@@ -175,3 +175,105 @@ fn test_malformed() {
     ];
     run_invalid_tests!(&exprs);
 }
+
+#[test]
+fn test_syntax_tree_display() {
+    let tree = syntax_tree("a(b|c)*").unwrap();
+    let expected = "\
+concat
+  concat
+    'a'
+    star
+      alter
+        'b'
+        'c'
+  #
+";
+    assert_eq!(expected, tree.to_string());
+
+    let tree = syntax_tree(". \\*").unwrap();
+    assert_eq!(
+        "concat\n  concat\n    concat\n      .\n      ' '\n    '*'\n  #\n",
+        tree.to_string()
+    );
+
+    assert_eq!("ε\n", syntax_tree("").unwrap().to_string());
+}
+
+#[test]
+fn test_followpos_tables() {
+    use crate::CharType;
+    use std::collections::HashSet;
+
+    // The canonical example of Algorithm 3.36: (a|b)*abb#, with positions 1 to 6.
+    let tree = syntax_tree("(a|b)*abb").unwrap();
+    let (dfa, tables): (DFA, FollowposTables) = tree_to_dfa_with_tables(&tree).unwrap();
+
+    let set = |positions: &[u32]| positions.iter().cloned().collect::<HashSet<_>>();
+    assert_eq!(set(&[1, 2, 3]), tables.firstpos);
+
+    let chars = ['a', 'b', 'a', 'b', 'b'];
+    for (i, &c) in chars.iter().enumerate() {
+        assert_eq!(CharType::from_plain(c), tables.chars[&(i as u32 + 1)]);
+    }
+    assert_eq!(CharType::EndMarker, tables.chars[&6]);
+
+    let expected = [
+        set(&[1, 2, 3]),
+        set(&[1, 2, 3]),
+        set(&[4]),
+        set(&[5]),
+        set(&[6]),
+        set(&[]),
+    ];
+    for (i, followpos) in expected.iter().enumerate() {
+        assert_eq!(followpos, &tables.followpos[&(i as u32 + 1)]);
+    }
+
+    assert!(dfa.is_match("abb"));
+    assert!(dfa.is_match("babaabb"));
+    assert!(!dfa.is_match("abba"));
+
+    let (_, tables) = tree_to_dfa_with_tables(&syntax_tree("").unwrap()).unwrap();
+    assert!(tables.followpos.is_empty());
+}
+
+#[test]
+fn test_find() {
+    let dfa = regex_to_dfa("ab*").unwrap();
+    assert!(dfa.is_match("abbb"));
+
+    let m = dfa.find("abbbc").unwrap();
+    assert_eq!((0, 4), (m.start, m.end));
+    assert_eq!(vec!['a', 'b', 'b', 'b'], m.span);
+    assert_eq!(None, dfa.find("cab"));
+
+    let m = dfa.find_at("cab", 1).unwrap();
+    assert_eq!((1, 3), (m.start, m.end));
+
+    let ranges: Vec<_> = dfa.find_all("abxaabb").map(|m| (m.start, m.end)).collect();
+    assert_eq!(vec![(0, 2), (3, 4), (4, 7)], ranges);
+
+    // Empty matches are found between characters.
+    let re = RegExp::new("a*").unwrap();
+    let ranges: Vec<_> = re.find_all("b").map(|m| (m.start, m.end)).collect();
+    assert_eq!(vec![(0, 0), (1, 1)], ranges);
+
+    // Indexes count characters, not bytes.
+    let m = dfa.find_at("éab", 1).unwrap();
+    assert_eq!((1, 3), (m.start, m.end));
+    assert_eq!(vec!['a', 'b'], m.span);
+    let spans: Vec<_> = dfa.find_all("éabéa").map(|m| (m.start, m.span)).collect();
+    assert_eq!(vec![(1, vec!['a', 'b']), (4, vec!['a'])], spans);
+    assert_eq!(None, dfa.find_at("éab", 4));
+}
+
+#[test]
+fn test_find_all_empty_after_match() {
+    // An empty match where the previous match ends is skipped.
+    let re = RegExp::new("a*").unwrap();
+    let ranges: Vec<_> = re.find_all("baab").map(|m| (m.start, m.end)).collect();
+    assert_eq!(vec![(0, 0), (1, 3), (4, 4)], ranges);
+    let ranges: Vec<_> = re.find_all("aa").map(|m| (m.start, m.end)).collect();
+    assert_eq!(vec![(0, 2)], ranges);
+}
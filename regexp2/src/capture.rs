@@ -0,0 +1,303 @@
+//! Matching of regular expressions with capture groups.
+//!
+//! DFAs cannot track which part of the input each group matched, so capture groups are matched by
+//! a separate engine over the syntax tree of the regular expression. For each node and starting
+//! position, the engine computes the set of positions at which the node can finish matching,
+//! along with the group spans of the preferred path to each position. Results are memoized by node
+//! and starting position and shared across the search for the leftmost match. For a syntax tree of
+//! m nodes and an input of n characters, there are O(m * n) memoized results, each combining up to
+//! O(n^2) pairs of positions, so a search takes O(m * n^3) time and O(m * n^2) space in the worst
+//! case. This is much slower than DFA matching, so it is only used when captures are requested.
+//!
+//! Matches are leftmost-longest. Among paths ending at the same position, the one in which
+//! earlier subexpressions match as much as possible is preferred, and groups repeated by `*` or
+//! `+` report their last iteration.
+
+use crate::class::CharClass;
+use crate::parser::{self, Anchors, Operator, ParseError, Parser};
+
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use automata::Match;
+
+/// Node in the syntax tree of a regular expression with capture groups.
+#[derive(Clone, Debug)]
+pub(crate) enum Node {
+    Class(CharClass),
+    Empty,
+    Concatenation(Box<Node>, Box<Node>),
+    Union(Box<Node>, Box<Node>),
+    KleeneStar(Box<Node>),
+    Plus(Box<Node>),
+    Optional(Box<Node>),
+    Group(usize, Box<Node>),
+}
+
+/// A regular expression parser that produces a syntax tree that records capture groups.
+#[derive(Debug, Default)]
+pub(crate) struct CaptureParser;
+
+impl Parser<Node> for CaptureParser {
+    /// Implement the shift action. A new leaf node is pushed to the parsing stack.
+    #[inline]
+    fn shift_action(
+        &self,
+        stack: &mut Vec<Node>,
+        _: &mut Vec<Operator>,
+        c: CharClass,
+    ) -> parser::Result<()> {
+        stack.push(Node::Class(c));
+        Ok(())
+    }
+
+    /// Implement the reduce action. The most recent operator is popped from the stack and child
+    /// nodes are popped from the node stack, and a new node is pushed to the stack.
+    #[inline]
+    fn reduce_action(
+        &self,
+        stack: &mut Vec<Node>,
+        op_stack: &mut Vec<Operator>,
+    ) -> parser::Result<()> {
+        let op = op_stack.pop().ok_or(ParseError::UnbalancedOperators)?;
+        let mut pop = || {
+            stack
+                .pop()
+                .map(Box::new)
+                .ok_or(ParseError::UnbalancedOperators)
+        };
+
+        let new_node = match op {
+            Operator::Union => {
+                let c2 = pop()?;
+                Node::Union(pop()?, c2)
+            }
+            Operator::Concatenation => {
+                let c2 = pop()?;
+                Node::Concatenation(pop()?, c2)
+            }
+            Operator::KleeneStar => Node::KleeneStar(pop()?),
+            Operator::Plus => Node::Plus(pop()?),
+            Operator::Optional => Node::Optional(pop()?),
+            Operator::EmptyPlaceholder => Node::Empty,
+            Operator::LeftParen => return Err(ParseError::UnbalancedParentheses),
+        };
+
+        stack.push(new_node);
+        Ok(())
+    }
+
+    /// Implement the group action. The topmost node is wrapped in a group node.
+    #[inline]
+    fn group_action(&self, stack: &mut Vec<Node>, index: usize) -> parser::Result<()> {
+        let node = stack.pop().ok_or(ParseError::UnbalancedOperators)?;
+        stack.push(Node::Group(index, Box::new(node)));
+        Ok(())
+    }
+}
+
+/// Spans of each capture group, indexed by group number. Index 0 is unused.
+type Slots = Vec<Option<(usize, usize)>>;
+
+/// Map of positions at which a node can finish matching to the group spans of the preferred path.
+type Ends = BTreeMap<usize, Slots>;
+
+/// A regular expression compiled for capture group matching.
+#[derive(Debug, Clone)]
+pub(crate) struct Program {
+    root: Node,
+    groups: usize,
+    anchors: Anchors,
+}
+
+impl Program {
+    #[inline]
+    pub(crate) fn new(expr: &str) -> parser::Result<Self> {
+        let (root, anchors) = CaptureParser.parse_anchored(expr)?;
+        let groups = Self::count_groups(&root);
+        Ok(Self {
+            root,
+            groups,
+            anchors,
+        })
+    }
+
+    #[inline]
+    fn count_groups(node: &Node) -> usize {
+        match node {
+            Node::Class(_) | Node::Empty => 0,
+            Node::Concatenation(c1, c2) | Node::Union(c1, c2) => {
+                Self::count_groups(c1) + Self::count_groups(c2)
+            }
+            Node::KleeneStar(c) | Node::Plus(c) | Node::Optional(c) => Self::count_groups(c),
+            Node::Group(_, c) => 1 + Self::count_groups(c),
+        }
+    }
+
+    /// Find the leftmost-longest match in the input and the spans of each group.
+    #[inline]
+    pub(crate) fn captures(&self, input: &str) -> Option<Captures> {
+        let input: Vec<char> = input.chars().collect();
+        let mut matcher = Matcher {
+            input: &input,
+            groups: self.groups,
+            memo: HashMap::new(),
+        };
+
+        // Anchored expressions may only start or end at the boundaries of the input.
+        let last_start = if self.anchors.start { 0 } else { input.len() };
+        (0..=last_start).find_map(|start| {
+            let ends = matcher.ends(&self.root, start);
+            let longest = if self.anchors.end {
+                ends.get_key_value(&input.len())
+            } else {
+                ends.iter().next_back()
+            };
+            longest.map(|(&end, slots)| {
+                let span = |(s, e): (usize, usize)| Match::new(s, e, input[s..e].to_vec());
+
+                let mut groups = vec![Some(span((start, end)))];
+                groups.extend(slots.iter().skip(1).map(|slot| slot.map(span)));
+                Captures { groups }
+            })
+        })
+    }
+}
+
+/// The result of matching a regular expression with capture groups.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Captures {
+    /// Match for each group, where index 0 is the entire match. Groups that did not participate
+    /// in the match are [`None`].
+    groups: Vec<Option<Match<char>>>,
+}
+
+impl Captures {
+    /// Return the match for the group with the given number, where group 0 is the entire match
+    /// and other groups are numbered from 1 in the order of their opening parentheses.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<&Match<char>> {
+        self.groups.get(i).and_then(Option::as_ref)
+    }
+
+    /// Return the substring matched by the group with the given number.
+    #[inline]
+    pub fn get_str(&self, i: usize) -> Option<String> {
+        self.get(i).map(|m| m.span.iter().collect())
+    }
+
+    /// Iterate through the matches of every group, including group 0.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = Option<&Match<char>>> {
+        self.groups.iter().map(Option::as_ref)
+    }
+}
+
+struct Matcher<'a> {
+    input: &'a [char],
+    groups: usize,
+    /// Memoized results, keyed by node address, starting position, and whether or not the node
+    /// is repeated by a Kleene star.
+    memo: HashMap<(*const Node, usize, bool), Rc<Ends>>,
+}
+
+impl<'a> Matcher<'a> {
+    /// Compute the positions at which `node` can finish matching when starting at `pos`.
+    fn ends(&mut self, node: &Node, pos: usize) -> Rc<Ends> {
+        let key = (node as *const Node, pos, false);
+        if let Some(ends) = self.memo.get(&key) {
+            return Rc::clone(ends);
+        }
+
+        let mut ends = Ends::new();
+        match node {
+            Node::Class(c) => {
+                if matches!(self.input.get(pos), Some(&is) if c.contains(is)) {
+                    ends.insert(pos + 1, self.empty_slots());
+                }
+            }
+            Node::Empty => {
+                ends.insert(pos, self.empty_slots());
+            }
+            Node::Concatenation(c1, c2) => {
+                // Prefer paths where the first operand matches more of the input.
+                let first = self.ends(c1, pos);
+                for (&e1, s1) in first.iter().rev() {
+                    for (&e2, s2) in self.ends(c2, e1).iter() {
+                        ends.entry(e2).or_insert_with(|| Self::overlay(s1, s2));
+                    }
+                }
+            }
+            Node::Union(c1, c2) => {
+                ends = (*self.ends(c1, pos)).clone();
+                for (&end, slots) in self.ends(c2, pos).iter() {
+                    ends.entry(end).or_insert_with(|| slots.clone());
+                }
+            }
+            Node::KleeneStar(c) => {
+                ends = (*self.repeat(c, pos)).clone();
+            }
+            // c+ is matched as c c*.
+            Node::Plus(c) => {
+                let first = self.ends(c, pos);
+                for (&e1, s1) in first.iter().rev() {
+                    for (&e2, s2) in self.repeat(c, e1).iter() {
+                        ends.entry(e2).or_insert_with(|| Self::overlay(s1, s2));
+                    }
+                }
+            }
+            Node::Optional(c) => {
+                ends = (*self.ends(c, pos)).clone();
+                ends.entry(pos).or_insert_with(|| self.empty_slots());
+            }
+            Node::Group(index, c) => {
+                for (&end, slots) in self.ends(c, pos).iter() {
+                    let mut slots = slots.clone();
+                    slots[*index] = Some((pos, end));
+                    ends.insert(end, slots);
+                }
+            }
+        }
+
+        let ends = Rc::new(ends);
+        self.memo.insert(key, Rc::clone(&ends));
+        ends
+    }
+
+    /// Compute the positions at which `node*` can finish matching when starting at `pos`.
+    fn repeat(&mut self, node: &Node, pos: usize) -> Rc<Ends> {
+        let key = (node as *const Node, pos, true);
+        if let Some(ends) = self.memo.get(&key) {
+            return Rc::clone(ends);
+        }
+
+        let mut ends = Ends::new();
+        let first = self.ends(node, pos);
+        for (&e1, s1) in first.iter().rev() {
+            // Skip iterations that consume no input, since they make no progress.
+            if e1 == pos {
+                continue;
+            }
+
+            for (&e2, s2) in self.repeat(node, e1).iter() {
+                ends.entry(e2).or_insert_with(|| Self::overlay(s1, s2));
+            }
+        }
+        ends.entry(pos).or_insert_with(|| self.empty_slots());
+
+        let ends = Rc::new(ends);
+        self.memo.insert(key, Rc::clone(&ends));
+        ends
+    }
+
+    /// Combine the group spans of two consecutive paths, preferring those of the later path.
+    #[inline]
+    fn overlay(s1: &Slots, s2: &Slots) -> Slots {
+        s1.iter().zip(s2).map(|(a, b)| b.or(*a)).collect()
+    }
+
+    #[inline]
+    fn empty_slots(&self) -> Slots {
+        vec![None; self.groups + 1]
+    }
+}
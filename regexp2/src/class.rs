@@ -3,8 +3,10 @@ use crate::ranges::{DECIMAL_NUMBER, LETTER};
 
 use std::cmp;
 use std::convert::TryInto;
+use std::fmt;
 use std::hash::Hash;
 use std::iter;
+use std::ops::RangeInclusive;
 
 /// The lowest Unicode scalar value.
 const USV_START_1: char = '\u{0}';
@@ -30,9 +32,11 @@ impl CharClass {
         self.ranges.iter().any(|r| r.contains(c))
     }
 
+    /// Determine if the character class matches no characters, i.e. if it has no ranges, or only
+    /// ranges whose start is after their end.
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.ranges.is_empty()
+        self.iter().all(CharRange::is_empty)
     }
 
     // Union of the intersections of each range in `Self` with each range in `other`.
@@ -47,19 +51,47 @@ impl CharClass {
         })
     }
 
-    /// Return the complement of the union of the ranges in the character class.
+    /// Return the complement of the union of the ranges in the character class, i.e. the
+    /// characters that are in none of its ranges. The complement never contains the surrogate
+    /// code points, which are not valid `char`s, and the complement of an empty class is all
+    /// characters.
     #[inline]
     pub fn complement(&self) -> Self {
-        let mut it = self.iter().map(|r| r.complement().into());
+        let mut class = self.clone();
+        class.normalize();
+        let mut ranges: Vec<_> = class.into_iter().filter(|r| !r.is_empty()).collect();
+        ranges.sort_by_key(|r| r.start);
+
+        // Gaps that span the surrogate code points are split around them, as in
+        // `CharClass::all`.
+        let mut complement = CharClass::new();
+        let mut add_gap = |start, end| {
+            if start <= USV_END_1 && end >= USV_START_2 {
+                complement.add_range(CharRange::new(start, USV_END_1));
+                complement.add_range(CharRange::new(USV_START_2, end));
+            } else {
+                complement.add_range(CharRange::new(start, end));
+            }
+        };
+
+        // Start of the next gap, or None if the last range reaches the highest character.
+        let mut next = Some(USV_START_1);
+        for r in ranges {
+            match next {
+                Some(start) if start < r.start => add_gap(start, char_before(r.start)),
+                _ => {}
+            }
+            next = if r.end == USV_END_2 {
+                None
+            } else {
+                Some(char_after(r.end))
+            };
+        }
 
-        // fold_first
-        it.next()
-            .map(|complement| {
-                it.fold(complement, |union: CharClass, complement| {
-                    union.intersection(&complement)
-                })
-            })
-            .unwrap_or_else(CharClass::new)
+        if let Some(start) = next {
+            add_gap(start, USV_END_2);
+        }
+        complement
     }
 
     /// Copy the ranges in `other` to this `Self`.
@@ -75,9 +107,92 @@ impl CharClass {
     pub fn add_range(&mut self, range: CharRange) {
         self.ranges.insert(range);
     }
+
+    /// Merge overlapping and adjacent ranges, leaving the minimal set of ranges that cover the
+    /// same characters.
+    #[inline]
+    pub fn normalize(&mut self) {
+        let mut ranges: Vec<_> = self.iter().cloned().collect();
+        ranges.sort_by_key(|r| r.start);
+
+        let mut merged: Vec<CharRange> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) => match last.merge(&r) {
+                    Some(union) => *last = union,
+                    None => merged.push(r),
+                },
+                None => merged.push(r),
+            }
+        }
+
+        self.ranges = DisjointSet::new();
+        self.extend(merged);
+    }
+}
+
+impl fmt::Display for CharClass {
+    /// Write the character class in bracket syntax, with its ranges merged and in ascending order.
+    /// A class that contains both the lowest and highest characters is written as the negation of
+    /// the gaps between its ranges, e.g. `[^\n]` for all characters but the newline.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut class = self.clone();
+        class.normalize();
+        let mut ranges: Vec<_> = class.into_iter().collect();
+        ranges.sort_by_key(|r| r.start);
+
+        let negated = matches!(
+            (ranges.first(), ranges.last()),
+            (Some(first), Some(last)) if first.start == USV_START_1 && last.end == USV_END_2
+        );
+        if negated {
+            // Ranges are merged, so the gaps are never empty.
+            ranges = ranges
+                .windows(2)
+                .map(|w| CharRange::new(char_after(w[0].end), char_before(w[1].start)))
+                .collect();
+        }
+
+        write!(f, "[{}", if negated { "^" } else { "" })?;
+        for r in ranges {
+            write!(f, "{}", r)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Return the character after the given one, skipping the surrogate code points.
+#[inline]
+fn char_after(c: char) -> char {
+    if c == USV_END_1 {
+        USV_START_2
+    } else {
+        (c as u32 + 1).try_into().unwrap()
+    }
+}
+
+/// Return the character before the given one, skipping the surrogate code points.
+#[inline]
+fn char_before(c: char) -> char {
+    if c == USV_START_2 {
+        USV_END_1
+    } else {
+        (c as u32 - 1).try_into().unwrap()
+    }
 }
 
 impl CharClass {
+    /// Create a character class of all characters.
+    #[inline]
+    pub fn all() -> Self {
+        let ranges = vec![
+            CharRange::new(USV_START_1, USV_END_1),
+            CharRange::new(USV_START_2, USV_END_2),
+        ];
+        ranges.into()
+    }
+
     /// Create a character class of all characters except the newline character.
     #[inline]
     pub fn all_but_newline() -> Self {
@@ -130,6 +245,22 @@ impl CharClass {
             ranges: DisjointSet::new(),
         }
     }
+
+    /// Create a normalized character class of the given characters, in which adjacent characters
+    /// are merged into ranges.
+    #[inline]
+    pub fn from_chars<I: IntoIterator<Item = char>>(chars: I) -> Self {
+        Self::from_ranges(chars.into_iter().map(|c| (c, c)))
+    }
+
+    /// Create a normalized character class of the given inclusive ranges, in which overlapping
+    /// and adjacent ranges are merged.
+    #[inline]
+    pub fn from_ranges<I: IntoIterator<Item = (char, char)>>(ranges: I) -> Self {
+        let mut class: Self = ranges.into_iter().map(CharRange::from).collect();
+        class.normalize();
+        class
+    }
 }
 
 impl Default for CharClass {
@@ -322,10 +453,22 @@ impl CharRange {
         self.start <= c && c <= self.end
     }
 
+    /// Determine if the range contains no characters, i.e. if its start is after its end.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+
+    /// Determine if two ranges have any characters in common.
+    #[inline]
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
     /// Return the range that is the intersection between two ranges.
     #[inline]
     pub fn intersection(&self, other: &Self) -> Option<Self> {
-        if other.start > self.end || self.start > other.end {
+        if !self.intersects(other) {
             None
         } else {
             let start = cmp::max(self.start, other.start);
@@ -334,49 +477,106 @@ impl CharRange {
         }
     }
 
-    /// Return the set of ranges that equals the complement of this range. Because Unicode scalar
-    /// values, which `char` encodes, consist of all Unicode code points except high-surrogate and
-    /// low-surrogate code points, characters between the values of 0xD7FF and 0xE000, exclusive,
-    /// are omitted.
+    /// Return the range that covers exactly the characters of both ranges, if they overlap or are
+    /// adjacent, or [`None`] if there are characters between them. Ranges on either side of the
+    /// surrogate code points are adjacent.
     #[inline]
-    pub fn complement(&self) -> Vec<Self> {
-        let mut ranges = Vec::new();
-
-        let shift_char = |c, up: bool| {
-            let shifted = if up { c as u32 + 1 } else { c as u32 - 1 };
-            shifted.try_into().unwrap()
+    pub fn merge(&self, other: &Self) -> Option<Self> {
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
         };
 
-        if self.start > USV_START_2 {
-            let r1 = Self::new(USV_START_2, shift_char(self.start, false));
-            ranges.push(r1);
-
-            let r2 = Self::new(USV_START_1, USV_END_1);
-            ranges.push(r2);
-        } else if self.start > USV_START_1 {
-            let r = Self::new(USV_START_1, shift_char(self.start, false));
-            ranges.push(r);
+        if first.touches(second) {
+            Some(Self::new(first.start, cmp::max(first.end, second.end)))
+        } else {
+            None
         }
+    }
 
-        if self.end < USV_END_1 {
-            let r1 = Self::new(shift_char(self.end, true), USV_END_1);
-            ranges.push(r1);
+    /// Determine if the given range, which must not start before this range, overlaps or
+    /// immediately follows this range. Ranges on either side of the surrogate code points are
+    /// considered adjacent.
+    #[inline]
+    fn touches(&self, next: &Self) -> bool {
+        next.start as u32 <= self.end as u32 + 1
+            || (self.end == USV_END_1 && next.start == USV_START_2)
+    }
+
+    /// Return the set of ranges that equals the complement of this range, in order. Because
+    /// Unicode scalar values, which `char` encodes, consist of all Unicode code points except
+    /// high-surrogate and low-surrogate code points, characters between the values of 0xD7FF and
+    /// 0xE000, exclusive, are omitted.
+    #[inline]
+    pub fn complement(&self) -> Vec<Self> {
+        CharClass::from(self.clone())
+            .complement()
+            .into_iter()
+            .collect()
+    }
+
+    /// Iterate through the characters in the range, in order. The surrogate code points are
+    /// skipped, since no `char` has their values.
+    #[inline]
+    pub fn chars(&self) -> RangeInclusive<char> {
+        self.start..=self.end
+    }
+}
+
+/// Return the range of characters with values from `start` to `end`, inclusive, where either
+/// bound may be a surrogate code point, or [`None`] if there are no such characters. Bounds that
+/// are surrogate code points are moved to the nearest character within the range.
+#[inline]
+pub(crate) fn scalar_range(start: u32, end: u32) -> Option<CharRange> {
+    let start = match char::from_u32(start) {
+        Some(c) => c,
+        None if start <= USV_END_2 as u32 => USV_START_2,
+        None => return None,
+    };
+    let end = match char::from_u32(end) {
+        Some(c) => c,
+        None if end <= USV_END_2 as u32 => USV_END_1,
+        None => USV_END_2,
+    };
+
+    if start <= end {
+        Some(CharRange::new(start, end))
+    } else {
+        None
+    }
+}
 
-            let r2 = Self::new(USV_START_2, USV_END_2);
-            ranges.push(r2);
-        } else if self.end < USV_END_2 {
-            let r = Self::new(shift_char(self.end, true), USV_END_2);
-            ranges.push(r);
+impl fmt::Display for CharRange {
+    /// Write the range as `start-end`, or as a single character if both bounds are the same.
+    /// Characters that have special meaning in a bracketed character class are escaped, as are
+    /// non-printable characters.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_class_char(f, self.start)?;
+        if self.start != self.end {
+            write!(f, "-")?;
+            write_class_char(f, self.end)?;
         }
+        Ok(())
+    }
+}
 
-        ranges
+/// Write a character as it would appear in a bracketed character class.
+#[inline]
+fn write_class_char(f: &mut fmt::Formatter<'_>, c: char) -> fmt::Result {
+    match c {
+        '[' | ']' | '^' | '-' | '\\' => write!(f, "\\{}", c),
+        // Quotes are escaped by `escape_debug`, but need not be here.
+        '\'' | '"' => write!(f, "{}", c),
+        _ => write!(f, "{}", c.escape_debug()),
     }
 }
 
 impl Intersect for CharRange {
     #[inline]
     fn intersect(&self, other: &Self) -> bool {
-        self.intersection(other).is_some()
+        self.intersects(other)
     }
 
     #[inline]
@@ -7,6 +7,7 @@ mod ast;
 mod disjoint;
 mod ranges;
 
+pub mod capture;
 pub mod class;
 pub mod parser;
 
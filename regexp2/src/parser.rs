@@ -20,9 +20,23 @@ where
     T: Clone + Eq + Hash,
     Transition<T>: From<CharClass>,
 {
+    /// Whether `.` matches all characters, including the newline character. If false (the
+    /// default), `.` matches all characters but the newline character.
+    pub dot_matches_newline: bool,
+    /// The maximum number of states in the constructed NFA; parsing fails with
+    /// [ParseError::TooLarge] rather than construct a larger one. Defaults to
+    /// [DEFAULT_MAX_STATES].
+    pub max_states: usize,
+    /// Whether unescaped whitespace outside of bracketed char classes is ignored, and `#` starts
+    /// a comment to the end of the line, so that long expressions can be split across annotated
+    /// lines. False by default.
+    pub verbose: bool,
     _phantom: PhantomData<T>,
 }
 
+/// The default maximum number of states in an NFA constructed by [NFAParser].
+pub const DEFAULT_MAX_STATES: usize = 100_000;
+
 impl<T> NFAParser<T>
 where
     T: Clone + Eq + Hash,
@@ -32,11 +46,85 @@ where
     #[inline]
     pub fn new() -> Self {
         NFAParser {
+            dot_matches_newline: false,
+            max_states: DEFAULT_MAX_STATES,
+            verbose: false,
             _phantom: PhantomData,
         }
     }
 }
 
+impl<T> NFAParser<T>
+where
+    T: Clone + Eq + Hash,
+    Transition<T>: From<CharClass>,
+{
+    /// Return [ParseError::TooLarge] if an NFA with the given number of states would exceed
+    /// [NFAParser::max_states].
+    #[inline]
+    fn check_size(&self, states: usize) -> Result<()> {
+        if states > self.max_states {
+            Err(ParseError::TooLarge)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<T> NFAParser<T>
+where
+    T: Clone + Eq + Hash,
+    Transition<T>: From<CharClass>,
+{
+    /// Check that an expression parses, without building its NFA. The same errors are returned as
+    /// by [Parser::parse] with this parser's settings, except [ParseError::TooLarge], which depends
+    /// on the size of the NFA.
+    #[inline]
+    pub fn validate(&self, expr: &str) -> Result<()> {
+        let validator = Validator {
+            verbose: self.verbose,
+        };
+        validator.parse(expr)
+    }
+}
+
+/// Parser that keeps only placeholders on its stack, for [NFAParser::validate].
+struct Validator {
+    verbose: bool,
+}
+
+impl Parser<()> for Validator {
+    #[inline]
+    fn shift_action(&self, stack: &mut Vec<()>, _: &mut Vec<Operator>, _: CharClass) -> Result<()> {
+        stack.push(());
+        Ok(())
+    }
+
+    /// Pop as many operands as the operator takes, so that missing operands are reported as when
+    /// building an NFA.
+    #[inline]
+    fn reduce_action(&self, stack: &mut Vec<()>, op_stack: &mut Vec<Operator>) -> Result<()> {
+        let op = op_stack.pop().ok_or(ParseError::UnbalancedOperators)?;
+        let operands = match op {
+            Operator::Union | Operator::Concatenation => 2,
+            Operator::KleeneStar | Operator::Plus | Operator::Optional => 1,
+            Operator::EmptyPlaceholder => 0,
+            Operator::LeftParen => return Err(ParseError::UnbalancedParentheses),
+        };
+
+        for _ in 0..operands {
+            stack.pop().ok_or(ParseError::UnbalancedOperators)?;
+        }
+        stack.push(());
+        Ok(())
+    }
+
+    #[inline]
+    fn verbose(&self) -> bool {
+        self.verbose
+    }
+}
+
 impl<T> Default for NFAParser<T>
 where
     T: Clone + Eq + Hash,
@@ -84,9 +172,11 @@ where
 
         match op {
             // A union NFA is constructed from the 2 operands of the union operator.
+            // The size of each new NFA is checked before it is constructed.
             Operator::Union => {
                 let c2 = stack.pop().ok_or(ParseError::UnbalancedOperators)?;
                 let c1 = stack.pop().ok_or(ParseError::UnbalancedOperators)?;
+                self.check_size(c1.total_states + c2.total_states + 2)?;
                 new_nfa = NFA::union(&c1, &c2);
             }
             // A concatenated NFA is constructed from the 2 operands of the concatenation
@@ -94,21 +184,26 @@ where
             Operator::Concatenation => {
                 let c2 = stack.pop().ok_or(ParseError::UnbalancedOperators)?;
                 let c1 = stack.pop().ok_or(ParseError::UnbalancedOperators)?;
+                self.check_size(c1.total_states + c2.total_states)?;
                 new_nfa = NFA::concatenation(&c1, &c2);
             }
             // A new NFA is constructed from the most recent NFA on the stack for kleene star,
             // plus, and optional operators.
             Operator::KleeneStar => {
                 let c1 = stack.pop().ok_or(ParseError::UnbalancedOperators)?;
+                self.check_size(c1.total_states + 2)?;
                 new_nfa = NFA::kleene_star(&c1);
             }
             Operator::Plus => {
+                // The operand is copied twice, so nested pluses double the size at each level.
                 let c1 = stack.pop().ok_or(ParseError::UnbalancedOperators)?;
+                self.check_size(2 * c1.total_states + 2)?;
                 let kleene = NFA::kleene_star(&c1);
                 new_nfa = NFA::concatenation(&kleene, &c1);
             }
             Operator::Optional => {
                 let c1 = stack.pop().ok_or(ParseError::UnbalancedOperators)?;
+                self.check_size(c1.total_states + 4)?;
                 let c2 = NFA::new_epsilon();
                 new_nfa = NFA::union(&c1, &c2);
             }
@@ -123,6 +218,22 @@ where
         stack.push(new_nfa);
         Ok(())
     }
+
+    /// `.` matches the newline character only if [NFAParser::dot_matches_newline] is set.
+    #[inline]
+    fn dot_class(&self) -> CharClass {
+        if self.dot_matches_newline {
+            CharClass::all()
+        } else {
+            CharClass::all_but_newline()
+        }
+    }
+
+    /// Whitespace and comments are ignored only if [NFAParser::verbose] is set.
+    #[inline]
+    fn verbose(&self) -> bool {
+        self.verbose
+    }
 }
 
 pub struct ASTParser<T>
@@ -250,20 +361,131 @@ where
 
     fn reduce_action(&self, stack: &mut Vec<T>, op_stack: &mut Vec<Operator>) -> Result<()>;
 
+    /// Implement the action taken when a parenthesized group is closed, after its contents have
+    /// been reduced to the topmost item on the stack. Groups are numbered from 1 in the order of
+    /// their opening parentheses. Does nothing by default.
+    #[inline]
+    fn group_action(&self, _stack: &mut Vec<T>, _index: usize) -> Result<()> {
+        Ok(())
+    }
+
+    /// Return the character class matched by `.`. By default, this is all characters but the
+    /// newline character.
+    #[inline]
+    fn dot_class(&self) -> CharClass {
+        CharClass::all_but_newline()
+    }
+
+    /// Return whether unescaped whitespace and comments (from `#` to the end of the line) outside
+    /// of bracketed char classes are ignored. False by default.
+    #[inline]
+    fn verbose(&self) -> bool {
+        false
+    }
+
     /// Compile a regular expresion.
+    ///
+    /// Returns [ParseError::NoOutput] if the shift and reduce actions leave nothing on the stack,
+    /// [ParseError::MalformedExpression] if they leave more than one item, or
+    /// [ParseError::UnsupportedAnchor] if the expression is anchored, since the compiled
+    /// output alone cannot check input boundaries; see [Parser::parse_anchored].
+    #[inline]
+    fn parse(&self, expr: &str) -> Result<T> {
+        self.parse_chars(expr.chars())
+    }
+
+    /// Compile a regular expression read from an iterator of characters rather than a string, as
+    /// [Parser::parse] does. Where the meaning of a character depends on those after it, such as
+    /// for `\p{...}` or `{`, the iterator is cloned to look ahead.
+    #[inline]
+    fn parse_chars<I>(&self, chars: I) -> Result<T>
+    where
+        I: IntoIterator<Item = char>,
+        I::IntoIter: Clone,
+    {
+        match self.parse_anchored_chars(chars)? {
+            (
+                output,
+                Anchors {
+                    start: false,
+                    end: false,
+                },
+            ) => Ok(output),
+            _ => Err(ParseError::UnsupportedAnchor),
+        }
+    }
+
+    /// Compile a regular expression that may begin with `\A` or end with `\z`, returning the
+    /// anchors separately so that the matcher can check them against the input boundaries.
+    ///
+    /// Anchors apply to the whole expression, so `\A` must come before anything else and `\z`
+    /// after everything else, and an expression beginning with `\A` may not have a `|` outside
+    /// of parentheses; otherwise, returns [ParseError::MisplacedAnchor].
     #[inline]
-    fn parse(&self, expr: &str) -> Result<Option<T>> {
+    fn parse_anchored(&self, expr: &str) -> Result<(T, Anchors)> {
+        self.parse_anchored_chars(expr.chars())
+    }
+
+    /// Compile a regular expression that may be anchored, as [Parser::parse_anchored] does, read
+    /// from an iterator of characters as by [Parser::parse_chars].
+    #[inline]
+    fn parse_anchored_chars<I>(&self, chars: I) -> Result<(T, Anchors)>
+    where
+        I: IntoIterator<Item = char>,
+        I::IntoIter: Clone,
+    {
         // Overall super spaghetti, needs refactoring and cleaning up.
         let mut state = ParserState::new(
             |stack, op_stack, c| self.shift_action(stack, op_stack, c),
             |stack, op_stack| self.reduce_action(stack, op_stack),
+            |stack, index| self.group_action(stack, index),
         );
 
-        let mut chars = expr.chars();
+        let mut anchors = Anchors::default();
+        let mut chars = chars.into_iter();
         let mut next = chars.next();
+        let mut is_empty = true;
+        let mut top_level_union = false;
         while next.is_some() {
             let c = next.unwrap();
 
+            // In verbose mode, skip whitespace and comments that are not escaped or in a char
+            // class.
+            if self.verbose() && !state.escaped && !state.in_char_class {
+                if c == '#' {
+                    next = chars.find(|&c| c == '\n').and_then(|_| chars.next());
+                    continue;
+                } else if c.is_whitespace() {
+                    next = chars.next();
+                    continue;
+                }
+            }
+
+            // Anchors are zero-width, so they leave the expression empty.
+            if c == '\\' && !state.escaped && !state.in_char_class {
+                let anchor = chars.clone().next().filter(|&a| a == 'A' || a == 'z');
+                if let Some(a) = anchor {
+                    chars.next();
+                    if a == 'A' && is_empty && !anchors.start {
+                        anchors.start = true;
+                    } else if a == 'z' && !anchors.end && !top_level_union {
+                        // A top-level union would leave earlier branches outside of `\z`.
+                        anchors.end = true;
+                    } else {
+                        return Err(ParseError::MisplacedAnchor);
+                    }
+
+                    next = chars.next();
+                    continue;
+                }
+            }
+
+            // Nothing may follow `\z`.
+            if anchors.end {
+                return Err(ParseError::MisplacedAnchor);
+            }
+            is_empty = false;
+
             match c {
                 '|' => {
                     if state.escaped {
@@ -278,8 +500,12 @@ where
                     } else if state.in_char_class {
                         // If not escaped and in char class, push to char range buffer.
                         state.append_char_range_buf(c);
+                    } else if anchors.start && state.group_stack.is_empty() {
+                        // A top-level union would leave later branches outside of `\A`.
+                        return Err(ParseError::MisplacedAnchor);
                     } else {
                         // If not escaped and not in char class, handle this as union operator.
+                        top_level_union |= state.group_stack.is_empty();
                         state.handle_union()?;
                     }
                 }
@@ -375,7 +601,8 @@ where
                 }
                 '[' => {
                     if state.in_char_class {
-                        // Set [ in char class if currently within brackets.
+                        // Set [ in char class if currently within brackets, escaped or not.
+                        state.escaped = false;
                         state.append_char_range_buf(c);
                     } else if state.escaped {
                         // Handle [ as literal if escaped and not in char class.
@@ -407,9 +634,13 @@ where
                 }
                 '\\' => {
                     if state.escaped {
-                        // If escaped, handle this as literal \
+                        // If escaped, handle this as literal \, in or out of a char class.
                         state.escaped = false;
-                        state.handle_literal_char(c)?;
+                        if state.in_char_class {
+                            state.append_char_range_buf(c);
+                        } else {
+                            state.handle_literal_char(c)?;
+                        }
                     } else {
                         // If unescaped and in char class, handle next.
                         // If unescaped and not in char class, handle next.
@@ -458,16 +689,39 @@ where
                         // If unescaped and in char class, push . to char range buf as literal.
                         state.append_char_range_buf(c);
                     } else {
-                        // If unescaped and not in char class, add ranges for all chars except \n to
-                        // char class buf.
-                        let cc = CharClass::all_but_newline();
+                        // If unescaped and not in char class, add ranges for the chars matched by
+                        // . (by default, all chars except \n) to char class buf.
+                        let cc = self.dot_class();
                         state.handle_char_class(cc)?;
                     }
                 }
+                '{' => {
+                    if state.escaped {
+                        // If escaped, handle this as a literal {, in or out of a char class.
+                        state.escaped = false;
+                        if state.in_char_class {
+                            state.append_char_range_buf(c);
+                        } else {
+                            state.handle_literal_char(c)?;
+                        }
+                    } else if state.in_char_class {
+                        // If unescaped and in char class, push { to char range buf as literal.
+                        state.append_char_range_buf(c);
+                    } else if is_repetition_count(chars.clone()) {
+                        // A repetition count such as {2} or {2,5} is reserved for bounded
+                        // repetition, so that its meaning does not change once supported.
+                        return Err(ParseError::UnsupportedRepetition);
+                    } else {
+                        // Otherwise, handle this as a literal {.
+                        state.handle_literal_char(c)?;
+                    }
+                }
                 _ => {
                     // Kinda spaghetti:
                     let mut is_special = true;
+                    let escaped = state.escaped;
                     let mut cc = CharClass::new();
+                    let mut literal = None;
                     if state.escaped {
                         state.escaped = false;
                         // If sequence is \d,
@@ -479,13 +733,22 @@ where
                             cc = CharClass::word();
                         } else if c == 'W' {
                             cc = CharClass::word().complement();
-                        } else if c == 'n' {
-                            cc = '\n'.into();
                         } else if c == 's' {
                             cc = CharClass::whitespace();
                         } else if c == 'S' {
                             cc = CharClass::whitespace().complement();
+                        } else if (c == 'p' || c == 'P') && chars.clone().next() == Some('{') {
+                            // If sequence is \p{Name} or its negation \P{Name},
+                            chars.next();
+                            let category = read_category(&mut chars)?;
+                            cc = if c == 'p' {
+                                category
+                            } else {
+                                category.complement()
+                            };
                         } else {
+                            // If sequence escapes a single char, such as \t or \u{1F600},
+                            literal = read_escaped_char(c, &mut chars)?;
                             is_special = false;
                         }
                     } else {
@@ -499,6 +762,18 @@ where
                         } else {
                             state.handle_char_class(cc)?;
                         }
+                    } else if let Some(literal) = literal {
+                        if state.in_char_class && literal == '-' {
+                            state.append_literal_dash();
+                        } else if state.in_char_class {
+                            state.append_char_range_buf(literal);
+                        } else {
+                            state.handle_literal_char(literal)?;
+                        }
+                    } else if state.in_char_class && escaped && c == '-' {
+                        // If escaped dash in char class, push it as a literal rather than a range
+                        // separator.
+                        state.append_literal_dash();
                     } else if state.in_char_class {
                         // If in char class, push char to range buffer.
                         state.append_char_range_buf(c);
@@ -512,19 +787,107 @@ where
             next = chars.next();
         }
 
-        if expr.is_empty() {
-            state.op_stack.push(Operator::EmptyPlaceholder);
+        // An empty expression, or an empty last branch of a union, matches the empty string.
+        if is_empty || (!state.insert_concat && state.op_stack.last() == Some(&Operator::Union)) {
+            state.push_empty()?;
         }
 
         while !state.op_stack.is_empty() {
             state.reduce_stack()?;
         }
 
-        let head = state.stack.into_iter().last();
-        Ok(head)
+        // A well-formed expression reduces to exactly one item.
+        let output = state.stack.pop().ok_or(ParseError::NoOutput)?;
+        if !state.stack.is_empty() {
+            return Err(ParseError::MalformedExpression);
+        }
+        Ok((output, anchors))
     }
 }
 
+/// Zero-width assertions at the boundaries of a regular expression, which must be checked by the
+/// matcher against the boundaries of the whole input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Anchors {
+    /// Whether the expression begins with `\A`, so that it only matches at the start of input.
+    pub start: bool,
+    /// Whether the expression ends with `\z`, so that it only matches at the end of input.
+    pub end: bool,
+}
+
+/// Read the name of a Unicode general category after `\p{` or `\P{`, up to and including the
+/// closing `}`, and return the class of its characters. Only the categories with character tables
+/// are supported: `L` (letters) and `Nd` (decimal numbers), or their long names.
+#[inline]
+fn read_category<I: Iterator<Item = char>>(chars: &mut I) -> Result<CharClass> {
+    let mut name = String::new();
+    loop {
+        match chars.next() {
+            Some('}') => break,
+            Some(c) => name.push(c),
+            None => return Err(ParseError::UnknownCategory),
+        }
+    }
+
+    match name.as_str() {
+        "L" | "Letter" => Ok(CharClass::letter()),
+        "Nd" | "Decimal_Number" => Ok(CharClass::decimal_number()),
+        _ => Err(ParseError::UnknownCategory),
+    }
+}
+
+/// Return the char written by an escape sequence for a single char, given the char after the `\`:
+/// `\n`, `\r`, `\t`, `\0`, or `\u{...}` with the hexadecimal value of a Unicode scalar value. These
+/// are the escapes written by the [`fmt::Display`] implementation of [`CharClass`]. Return
+/// [`None`] for any other char, which is escaped as itself.
+#[inline]
+fn read_escaped_char<I>(c: char, chars: &mut I) -> Result<Option<char>>
+where
+    I: Iterator<Item = char> + Clone,
+{
+    let escaped = match c {
+        'n' => '\n',
+        'r' => '\r',
+        't' => '\t',
+        '0' => '\0',
+        'u' if chars.clone().next() == Some('{') => {
+            chars.next();
+            let mut hex = String::new();
+            loop {
+                match chars.next() {
+                    Some('}') => break,
+                    Some(c) => hex.push(c),
+                    None => return Err(ParseError::InvalidEscape),
+                }
+            }
+
+            u32::from_str_radix(&hex, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or(ParseError::InvalidEscape)?
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(escaped))
+}
+
+/// Determine if the characters after a `{` complete a repetition count: `n}`, `n,}`, or `n,m}`,
+/// where `n` and `m` are decimal numbers.
+#[inline]
+fn is_repetition_count<I: Iterator<Item = char>>(rest: I) -> bool {
+    let mut digits = 0;
+    let mut comma = false;
+    for c in rest {
+        match c {
+            '0'..='9' => digits += 1,
+            ',' if !comma && digits > 0 => comma = true,
+            '}' => return digits > 0,
+            _ => return false,
+        }
+    }
+    false
+}
+
 /// Set of valid operators.
 #[derive(Debug, PartialEq)]
 pub enum Operator {
@@ -538,14 +901,18 @@ pub enum Operator {
 }
 
 #[derive(Debug)]
-struct ParserState<T, SF, RF>
+struct ParserState<T, SF, RF, GF>
 where
     SF: Copy + FnMut(&mut Vec<T>, &mut Vec<Operator>, CharClass) -> Result<()>,
     RF: Copy + FnMut(&mut Vec<T>, &mut Vec<Operator>) -> Result<()>,
+    GF: Copy + FnMut(&mut Vec<T>, usize) -> Result<()>,
 {
     stack: Vec<T>,
     op_stack: Vec<Operator>,
-    paren_count_stack: Vec<usize>,
+    /// Indexes of the currently open groups.
+    group_stack: Vec<usize>,
+    /// Number of groups opened so far.
+    group_count: usize,
 
     escaped: bool,
     insert_concat: bool,
@@ -556,6 +923,7 @@ where
 
     shift_action: SF,
     reduce_action: RF,
+    group_action: GF,
 }
 
 #[derive(Debug)]
@@ -580,17 +948,19 @@ impl CharRangeBuf {
     }
 }
 
-impl<T, SF, RF> ParserState<T, SF, RF>
+impl<T, SF, RF, GF> ParserState<T, SF, RF, GF>
 where
     SF: Copy + FnMut(&mut Vec<T>, &mut Vec<Operator>, CharClass) -> Result<()>,
     RF: Copy + FnMut(&mut Vec<T>, &mut Vec<Operator>) -> Result<()>,
+    GF: Copy + FnMut(&mut Vec<T>, usize) -> Result<()>,
 {
     #[inline]
-    fn new(shift_action: SF, reduce_action: RF) -> Self {
+    fn new(shift_action: SF, reduce_action: RF, group_action: GF) -> Self {
         Self {
             stack: Vec::new(),
             op_stack: Vec::new(),
-            paren_count_stack: Vec::new(),
+            group_stack: Vec::new(),
+            group_count: 0,
 
             escaped: false,
             insert_concat: false,
@@ -601,6 +971,7 @@ where
 
             shift_action,
             reduce_action,
+            group_action,
         }
     }
 
@@ -626,8 +997,15 @@ where
 
     #[inline]
     fn handle_union(&mut self) -> Result<()> {
+        // An empty branch, e.g. at the start of `|a` or `(|a)`, matches the empty string.
+        if !self.insert_concat {
+            self.push_empty()?;
+        }
+
+        // Alternation has the lowest precedence, so every operator before it in the branch is
+        // collapsed, e.g. both the star and the concatenation in `ab*|c`.
         let op = Operator::Union;
-        self.precedence_reduce_stack(&op)?;
+        while self.precedence_reduce_stack(&op)? {}
 
         self.op_stack.push(op);
         self.insert_concat = false;
@@ -637,6 +1015,8 @@ where
 
     #[inline]
     fn handle_kleene_star(&mut self) -> Result<()> {
+        self.check_quantifier_operand()?;
+
         let op = Operator::KleeneStar;
         self.precedence_reduce_stack(&op)?;
 
@@ -648,6 +1028,8 @@ where
 
     #[inline]
     fn handle_plus(&mut self) -> Result<()> {
+        self.check_quantifier_operand()?;
+
         let op = Operator::Plus;
         self.precedence_reduce_stack(&op)?;
 
@@ -659,6 +1041,8 @@ where
 
     #[inline]
     fn handle_optional(&mut self) -> Result<()> {
+        self.check_quantifier_operand()?;
+
         let op = Operator::Optional;
         self.precedence_reduce_stack(&op)?;
 
@@ -668,6 +1052,18 @@ where
         Ok(())
     }
 
+    /// Return an error if a quantifier (`*`, `+`, or `?`) would have no operand to apply to. This
+    /// is the case at the start of the expression, and directly after `(` or `|`; an operand has
+    /// been parsed exactly when concatenation would be inserted before the next one.
+    #[inline]
+    fn check_quantifier_operand(&self) -> Result<()> {
+        if self.insert_concat {
+            Ok(())
+        } else {
+            Err(ParseError::DanglingQuantifier)
+        }
+    }
+
     #[inline]
     fn handle_left_paren(&mut self) -> Result<()> {
         let op = Operator::LeftParen;
@@ -678,7 +1074,8 @@ where
         }
 
         self.op_stack.push(op);
-        self.paren_count_stack.push(self.stack.len());
+        self.group_count += 1;
+        self.group_stack.push(self.group_count);
         self.insert_concat = false;
 
         Ok(())
@@ -686,26 +1083,26 @@ where
 
     #[inline]
     fn handle_right_paren(&mut self) -> Result<()> {
-        let last_op = self
-            .op_stack
-            .last()
-            .ok_or(ParseError::UnbalancedOperators)?;
-        let prev_node_count = self
-            .paren_count_stack
-            .last()
-            .ok_or(ParseError::UnbalancedParentheses)?;
+        if self.op_stack.is_empty() {
+            return Err(ParseError::UnbalancedOperators);
+        }
 
-        if *last_op == Operator::LeftParen && *prev_node_count == self.stack.len() {
-            self.op_stack.pop().ok_or(ParseError::UnbalancedOperators)?;
-            self.op_stack.push(Operator::EmptyPlaceholder);
+        // An empty group `()`, or an empty last branch of a union, e.g. in `(a|)`, matches the
+        // empty string.
+        if !self.insert_concat {
+            self.push_empty()?;
+        }
+
+        while !self.op_stack.is_empty() && *self.op_stack.last().unwrap() != Operator::LeftParen {
             self.reduce_stack()?;
-        } else {
-            while !self.op_stack.is_empty() && *self.op_stack.last().unwrap() != Operator::LeftParen
-            {
-                self.reduce_stack()?;
-            }
-            self.op_stack.pop().ok_or(ParseError::UnbalancedOperators)?;
         }
+        self.op_stack.pop().ok_or(ParseError::UnbalancedOperators)?;
+
+        let index = self
+            .group_stack
+            .pop()
+            .ok_or(ParseError::UnbalancedParentheses)?;
+        self.group_action(index)?;
 
         self.insert_concat = true;
 
@@ -724,7 +1121,9 @@ where
 
         self.handle_incomplete_char_range_buf();
 
-        // Call shift action on completed char class.
+        // Negation applies to the union of all the ranges and shorthand classes between the
+        // brackets, so only complement once every one of them has been merged in.
+        self.char_class_buf.0.normalize();
         let char_class = if self.char_class_buf.1 {
             self.char_class_buf.0.complement()
         } else {
@@ -792,6 +1191,21 @@ where
         // There should never be a situation where all spots are filled.
     }
 
+    /// Append an escaped dash to the char range buffer, so that it is a literal char that may
+    /// bound a range, but never separates one.
+    #[inline]
+    fn append_literal_dash(&mut self) {
+        if self.char_range_buf.0.is_some() && self.char_range_buf.1.is_none() {
+            // A dash in the second spot would start a range, so add the char in the first spot as
+            // a single-char range and start over with the dash as the start of a range.
+            self.handle_incomplete_char_range_buf();
+            self.char_range_buf.0 = Some('-');
+        } else {
+            // Otherwise the dash is either the start or the end of a range.
+            self.append_char_range_buf('-');
+        }
+    }
+
     #[inline]
     fn clear_char_class_buf(&mut self) {
         self.char_class_buf = (CharClass::new(), false);
@@ -854,6 +1268,13 @@ where
         self.op_stack.push(op);
     }
 
+    /// Push an operand that matches only the empty string.
+    #[inline]
+    fn push_empty(&mut self) -> Result<()> {
+        self.op_stack.push(Operator::EmptyPlaceholder);
+        self.reduce_stack()
+    }
+
     #[inline]
     fn push_concatenation(&mut self) {
         self.op_stack.push(Operator::Concatenation);
@@ -868,6 +1289,11 @@ where
     fn reduce_action(&mut self) -> Result<()> {
         (self.reduce_action)(&mut self.stack, &mut self.op_stack)
     }
+
+    #[inline]
+    fn group_action(&mut self, index: usize) -> Result<()> {
+        (self.group_action)(&mut self.stack, index)
+    }
 }
 
 /// Error returned when attempting to parse an invalid regular expression.
@@ -879,6 +1305,31 @@ pub enum ParseError {
     UnbalancedParentheses,
     /// Bracketed character classes may not empty.
     EmptyCharacterClass,
+    /// A quantifier (`*`, `+`, or `?`) is not preceded by an operand, such as at the start of the
+    /// expression or directly after `(` or `|`.
+    DanglingQuantifier,
+    /// The NFA for the expression would have more states than the parser allows.
+    TooLarge,
+    /// The parser produced no output for the expression.
+    NoOutput,
+    /// The parser left more than one item on the stack, so the expression did not reduce to a
+    /// single output.
+    MalformedExpression,
+    /// A repetition count such as `{2}` or `{2,5}`, which is reserved for bounded repetition but
+    /// not yet supported. A `{` that does not begin a repetition count is a literal, and `\{` is
+    /// always a literal.
+    UnsupportedRepetition,
+    /// A `\p{...}` or `\P{...}` escape that is unclosed or names an unsupported Unicode
+    /// category.
+    UnknownCategory,
+    /// A `\u{...}` escape that is unclosed or does not hold the hexadecimal value of a Unicode
+    /// scalar value.
+    InvalidEscape,
+    /// An anchor (`\A` or `\z`) that does not apply to the whole expression.
+    MisplacedAnchor,
+    /// An anchor (`\A` or `\z`) in an expression compiled by [Parser::parse], which cannot
+    /// represent it.
+    UnsupportedAnchor,
 }
 
 impl fmt::Display for ParseError {
@@ -888,6 +1339,15 @@ impl fmt::Display for ParseError {
             Self::UnbalancedOperators => write!(f, "unbalanced operators"),
             Self::UnbalancedParentheses => write!(f, "unbalanced parentheses"),
             Self::EmptyCharacterClass => write!(f, "empty character class"),
+            Self::DanglingQuantifier => write!(f, "quantifier with no preceding operand"),
+            Self::TooLarge => write!(f, "expression exceeds the maximum automaton size"),
+            Self::NoOutput => write!(f, "no output produced"),
+            Self::MalformedExpression => write!(f, "expression did not reduce to a single output"),
+            Self::UnsupportedRepetition => write!(f, "bounded repetition is not supported"),
+            Self::UnknownCategory => write!(f, "unknown Unicode category"),
+            Self::InvalidEscape => write!(f, "invalid Unicode escape"),
+            Self::MisplacedAnchor => write!(f, "anchor does not apply to the whole expression"),
+            Self::UnsupportedAnchor => write!(f, "anchors are not supported here"),
         }
     }
 }
@@ -1,19 +1,28 @@
-use crate::class::{CharClass, CharRange};
-use crate::parser::{self, NFAParser, Parser};
+use crate::capture::{Captures, Program};
+use crate::class::{scalar_range, CharClass};
+use crate::parser::{self, Anchors, NFAParser, Parser};
 
-use std::convert::TryInto;
+use std::ops::Range;
+use std::sync::OnceLock;
 
 pub use automata::Match;
-use automata::{dfa::Disjoin, nfa::Transition, DFA, NFA};
+use automata::{
+    alphabet::Union,
+    dfa::{Complement, Disjoin},
+    nfa::Transition,
+    DFA, NFA,
+};
 
 /// A compiled regular expression for matching strings. It may be used to determine if given
 /// strings are within the language described by the regular expression.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RegExp<E: Engine> {
     /// The regular expression represented by this structure.
     expr: String,
     /// The compiled backend of the regular expression used to evaluate input strings.
     engine: E,
+    /// The anchors of the regular expression, which the engine cannot represent.
+    anchors: Anchors,
 }
 
 impl<E: Engine> RegExp<E> {
@@ -41,7 +50,13 @@ impl<E: Engine> RegExp<E> {
 
     #[inline]
     pub fn find_at(&self, input: &str, start: usize) -> Option<Match<char>> {
-        self.engine.find_at(input, start)
+        if self.anchors.start && start != 0 {
+            return None;
+        }
+
+        self.engine
+            .find_at(input, start)
+            .filter(|m| !self.anchors.end || m.end == input.chars().count())
     }
 
     #[inline]
@@ -51,7 +66,14 @@ impl<E: Engine> RegExp<E> {
 
     #[inline]
     pub fn find_shortest_at(&self, input: &str, start: usize) -> Option<Match<char>> {
-        self.engine.find_shortest_at(input, start)
+        // A match ending at the end of input is the longest one, so it is also the only one.
+        if self.anchors.end {
+            self.find_at(input, start)
+        } else if self.anchors.start && start != 0 {
+            None
+        } else {
+            self.engine.find_shortest_at(input, start)
+        }
     }
 }
 
@@ -60,11 +82,12 @@ impl RegExp<NFA<CharClass>> {
     #[inline]
     pub fn new(expr: &str) -> parser::Result<Self> {
         let parser = NFAParser::new();
-        let nfa: NFA<CharClass> = parser.parse(expr)?.unwrap();
+        let (nfa, anchors): (NFA<CharClass>, _) = parser.parse_anchored(expr)?;
 
         Ok(RegExp {
             expr: expr.to_owned(),
             engine: nfa,
+            anchors,
         })
     }
 }
@@ -74,12 +97,258 @@ impl RegExp<DFA<CharClass>> {
     #[inline]
     pub fn new_with_dfa(expr: &str) -> parser::Result<Self> {
         let parser = NFAParser::new();
-        let nfa: NFA<CharClass> = parser.parse(expr)?.unwrap();
-        let dfa = nfa.into();
+        let (nfa, anchors): (NFA<CharClass>, _) = parser.parse_anchored(expr)?;
+        let mut dfa: DFA<CharClass> = nfa.into();
+        // Stop matching as soon as no match is possible.
+        dfa.remove_dead_transitions();
 
         Ok(RegExp {
             expr: expr.to_owned(),
             engine: dfa,
+            anchors,
+        })
+    }
+}
+
+/// A regular expression compiled to a DFA.
+///
+/// Unlike [`RegExp::find`], [`Regex::find`] and [`Regex::find_all`] search for leftmost-longest
+/// matches anywhere in the input, rather than only at its beginning. [`Regex::is_full_match`]
+/// instead determines if the entire input is matched, like [`RegExp::is_match`].
+#[derive(Debug, Clone)]
+pub struct Regex {
+    inner: RegExp<DFA<CharClass>>,
+    /// The regular expression compiled for capture group matching, on the first call to
+    /// [`Regex::captures`].
+    program: OnceLock<Program>,
+}
+
+impl Regex {
+    /// Compile a regular expression.
+    #[inline]
+    pub fn new(expr: &str) -> parser::Result<Self> {
+        let inner = RegExp::new_with_dfa(expr)?;
+        Ok(Self {
+            inner,
+            program: OnceLock::new(),
+        })
+    }
+
+    /// Return the regular expression that was compiled.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.inner.expr
+    }
+
+    /// Determine if the entire input string is within the language described by the regular
+    /// expression. Use [`Regex::find`] to determine if any part of it is.
+    #[inline]
+    pub fn is_full_match(&self, input: &str) -> bool {
+        self.inner.is_match(input)
+    }
+
+    /// Find the leftmost-longest match in the input string.
+    #[inline]
+    pub fn find(&self, input: &str) -> Option<Match<char>> {
+        self.find_all(input).next()
+    }
+
+    /// Iterate through the successive non-overlapping leftmost-longest matches in the input
+    /// string. Match positions are character indexes.
+    #[inline]
+    pub fn find_all<'r, 'i>(&'r self, input: &'i str) -> FindAll<'r, 'i> {
+        FindAll {
+            regex: self,
+            input,
+            pos: 0,
+            byte_pos: 0,
+        }
+    }
+
+    /// Find the leftmost-longest match in the input string, with byte offsets into the input.
+    #[inline]
+    pub fn find_str<'i>(&self, input: &'i str) -> Option<StrMatch<'i>> {
+        self.find_all_str(input).next()
+    }
+
+    /// Iterate through the same matches as [`Regex::find_all`], but with byte offsets into the
+    /// input, so that they can be used to slice it.
+    #[inline]
+    pub fn find_all_str<'r, 'i>(&'r self, input: &'i str) -> FindAllStr<'r, 'i> {
+        FindAllStr {
+            inner: self.find_all(input),
+        }
+    }
+
+    /// Split the input string on the matches found by [`Regex::find_all`], returning the substrings
+    /// between them. There is always one more substring than there are matches, so a match at the
+    /// beginning or end of the input produces an empty first or last substring, and empty input
+    /// is a single empty substring unless the expression matches the empty string. Empty matches
+    /// split between characters, e.g. `a*` splits `"baab"` into `["", "b", "", "b", ""]`.
+    #[inline]
+    pub fn split<'i>(&self, input: &'i str) -> Vec<&'i str> {
+        let mut pieces = Vec::new();
+        let mut last = 0;
+        for m in self.find_all_str(input) {
+            pieces.push(&input[last..m.start]);
+            last = m.end;
+        }
+        pieces.push(&input[last..]);
+        pieces
+    }
+
+    /// Replace each match found by [`Regex::find_all`] in the input string with `rep`, which is
+    /// inserted literally. Empty matches insert `rep` between characters, e.g. replacing `a*` in
+    /// `"baab"` with `"-"` gives `"-b--b-"`.
+    #[inline]
+    pub fn replace_all(&self, input: &str, rep: &str) -> String {
+        let mut replaced = String::with_capacity(input.len());
+        let mut last = 0;
+        for m in self.find_all_str(input) {
+            replaced.push_str(&input[last..m.start]);
+            replaced.push_str(rep);
+            last = m.end;
+        }
+        replaced.push_str(&input[last..]);
+        replaced
+    }
+
+    /// Find the leftmost-longest match in the input string and the substrings matched by each
+    /// parenthesized group. See [`capture`](crate::capture) for the matching approach.
+    #[inline]
+    pub fn captures(&self, input: &str) -> Option<Captures> {
+        self.program
+            .get_or_init(|| {
+                // The expression was already parsed successfully by `Regex::new`.
+                Program::new(self.as_str()).expect("expression should parse")
+            })
+            .captures(input)
+    }
+}
+
+/// Determine if the entire input string is within the language described by the regular
+/// expression, as [`Regex::is_full_match`] does, without keeping the compiled expression.
+///
+/// The expression is compiled on every call, so this is meant for one-off checks; compile a
+/// [`Regex`] once to match several inputs.
+///
+/// ```
+/// assert!(regexp2::is_match(r"\d+(\.\d+)?", "3.14").unwrap());
+/// assert!(!regexp2::is_match(r"\d+(\.\d+)?", "3.").unwrap());
+/// assert!(regexp2::is_match("(a", "a").is_err());
+/// ```
+#[inline]
+pub fn is_match(expr: &str, input: &str) -> parser::Result<bool> {
+    // Simulating the NFA avoids the cost of constructing a DFA for a single input.
+    Ok(RegExp::new(expr)?.is_match(input))
+}
+
+/// Iterator over the successive non-overlapping matches of a [`Regex`] in some input string.
+#[derive(Debug)]
+pub struct FindAll<'r, 'i> {
+    regex: &'r Regex,
+    input: &'i str,
+    /// Character index to begin searching for the next match.
+    pos: usize,
+    /// Byte offset of the character at `pos`, or past the end of the input once it has all been
+    /// searched.
+    byte_pos: usize,
+}
+
+impl<'r, 'i> FindAll<'r, 'i> {
+    /// Find the next match, along with its range of byte offsets. The input is resliced at the
+    /// current byte offset rather than skipping characters from the beginning.
+    #[inline]
+    fn next_match(&mut self) -> Option<(Match<char>, Range<usize>)> {
+        // The input is resliced, so `\A` is checked against the absolute position here.
+        let anchored = self.regex.inner.anchors.start;
+        while self.byte_pos <= self.input.len() && !(anchored && self.pos > 0) {
+            let (start, byte_start) = (self.pos, self.byte_pos);
+            match self.regex.inner.find_at(&self.input[byte_start..], 0) {
+                Some(mut m) => {
+                    let byte_end = byte_start + m.span.iter().map(|c| c.len_utf8()).sum::<usize>();
+                    m.start += start;
+                    m.end += start;
+
+                    if m.span.is_empty() {
+                        // Step past empty matches so that the search always makes progress.
+                        self.step();
+                    } else {
+                        self.pos = m.end;
+                        self.byte_pos = byte_end;
+                    }
+
+                    return Some((m, byte_start..byte_end));
+                }
+                None => self.step(),
+            }
+        }
+
+        None
+    }
+
+    /// Move past the character at the current position.
+    #[inline]
+    fn step(&mut self) {
+        let len = self.input[self.byte_pos..]
+            .chars()
+            .next()
+            .map_or(1, char::len_utf8);
+        self.pos += 1;
+        self.byte_pos += len;
+    }
+}
+
+impl<'r, 'i> Iterator for FindAll<'r, 'i> {
+    type Item = Match<char>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_match().map(|(m, _)| m)
+    }
+}
+
+/// A match in some input string, with byte offsets into it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrMatch<'i> {
+    /// Byte offset of the start of the match.
+    pub start: usize,
+    /// Byte offset just past the end of the match.
+    pub end: usize,
+    input: &'i str,
+}
+
+impl<'i> StrMatch<'i> {
+    /// Return the range of byte offsets of the match.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Return the matched substring.
+    #[inline]
+    pub fn as_str(&self) -> &'i str {
+        &self.input[self.range()]
+    }
+}
+
+/// Iterator over the successive non-overlapping matches of a [`Regex`] in some input string, with
+/// byte offsets.
+#[derive(Debug)]
+pub struct FindAllStr<'r, 'i> {
+    inner: FindAll<'r, 'i>,
+}
+
+impl<'r, 'i> Iterator for FindAllStr<'r, 'i> {
+    type Item = StrMatch<'i>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.inner.input;
+        self.inner.next_match().map(|(_, range)| StrMatch {
+            start: range.start,
+            end: range.end,
+            input,
         })
     }
 }
@@ -158,9 +427,10 @@ impl Disjoin for CharClass {
         starts
             .into_iter()
             .filter_map(|(x, c)| {
+                // A piece may begin or end among the surrogate code points, e.g. between a range
+                // ending at U+D7FF and one beginning at U+E000 that are both within a third.
                 let ret = if x > prev && count != 0 {
-                    let ret = CharRange::new(prev.try_into().unwrap(), (x - 1).try_into().unwrap());
-                    Some(ret.into())
+                    scalar_range(prev, x - 1).map(CharClass::from)
                 } else {
                     None
                 };
@@ -176,3 +446,32 @@ impl Disjoin for CharClass {
         !self.intersection(other).is_empty()
     }
 }
+
+impl Complement for CharClass {
+    #[inline]
+    fn uncovered(vec: Vec<&Self>) -> Vec<Self> {
+        let mut covered = CharClass::new();
+        for cc in vec {
+            covered.copy_from(cc);
+        }
+
+        let uncovered = covered.complement();
+        if uncovered.is_empty() {
+            Vec::new()
+        } else {
+            vec![uncovered]
+        }
+    }
+}
+
+impl Union for CharClass {
+    #[inline]
+    fn union(vec: Vec<&Self>) -> Self {
+        let mut union = CharClass::new();
+        for cc in vec {
+            union.copy_from(cc);
+        }
+        union.normalize();
+        union
+    }
+}
@@ -0,0 +1,96 @@
+use regexp2::{
+    automata::{dfa::DFAFromNFA, Alphabet, DFA, NFA},
+    class::{CharClass, CharRange},
+    parser::{NFAParser, Parser},
+};
+
+fn nfa(expr: &str) -> NFA<CharClass> {
+    NFAParser::new().parse(expr).unwrap()
+}
+
+fn ranges(class: &CharClass) -> Vec<(char, char)> {
+    class.iter().map(|r| (r.start, r.end)).collect()
+}
+
+/// Return the number of transitions of the DFA, and the number of ranges in their symbols.
+fn size(dfa: &DFA<CharClass>) -> (usize, usize) {
+    let transitions: Vec<_> = dfa.transition.into_iter().collect();
+    let ranges = transitions.iter().map(|(_, t, _)| t.0.iter().count()).sum();
+    (transitions.len(), ranges)
+}
+
+#[test]
+fn test_alphabet_classes() {
+    let a_z: CharClass = CharRange::new('a', 'z').into();
+    let digit_x: CharClass = vec![CharRange::new('0', '9'), CharRange::new('x', 'x')].into();
+    let alphabet = Alphabet::new(vec![&a_z, &digit_x]);
+
+    // Digits and x are covered by the second symbol, but only x by the first; a-w and y-z are
+    // covered by the first only, and so are one class.
+    assert_eq!(3, alphabet.len());
+    let classes: Vec<_> = alphabet.classes().iter().map(ranges).collect();
+    assert_eq!(
+        vec![
+            vec![('0', '9')],
+            vec![('a', 'w'), ('y', 'z')],
+            vec![('x', 'x')]
+        ],
+        classes
+    );
+
+    assert_eq!(vec![1, 2], alphabet.classes_of(&a_z));
+    assert_eq!(vec![0, 2], alphabet.classes_of(&digit_x));
+    assert_eq!(Some(1), alphabet.class_of(&'b'));
+    assert_eq!(Some(2), alphabet.class_of(&'x'));
+    assert_eq!(None, alphabet.class_of(&'A'));
+    assert_eq!(ranges(&a_z), ranges(&alphabet.symbol(&[1, 2])));
+}
+
+#[test]
+fn test_with_alphabet() {
+    let nfa = nfa("[a-z]+|x[0-9]");
+    let alphabet = Alphabet::of_nfa(&nfa);
+    let subset = DFAFromNFA::with_alphabet(&nfa, &alphabet);
+    let dfa = &subset.dfa;
+
+    let classes = |input: &str| -> Vec<usize> {
+        input
+            .chars()
+            .map(|c| alphabet.class_of(&c).unwrap())
+            .collect()
+    };
+    for input in &["abc", "x", "x1", "xyz"] {
+        assert!(dfa.is_match(classes(input)), "{} not matched", input);
+    }
+    for input in &["", "1", "x12", "ab1"] {
+        assert!(!dfa.is_match(classes(input)), "{} matched", input);
+    }
+}
+
+#[test]
+fn test_partitioned() {
+    let nfa = nfa(r"[a-z]+|x[0-9]|\p{L}\d*");
+    let alphabet = Alphabet::of_nfa(&nfa);
+    let partitioned = DFAFromNFA::partitioned(&nfa).dfa;
+    let disjoined: DFA<CharClass> = nfa.into();
+
+    for input in &["abc", "x1", "xyz", "é12", "日1", "x"] {
+        assert!(partitioned.is_match(input.chars()), "{} not matched", input);
+        assert!(disjoined.is_match(input.chars()), "{} not matched", input);
+    }
+    for input in &["", "1", "x1a", "é1x", "日本"] {
+        assert!(!partitioned.is_match(input.chars()), "{} matched", input);
+        assert!(!disjoined.is_match(input.chars()), "{} matched", input);
+    }
+
+    // Each pair of states has at most one transition, and states are merged regardless of how
+    // they would otherwise split the letters.
+    let (transitions, _) = size(&partitioned.minimize());
+    let (disjoined_transitions, disjoined_ranges) = size(&disjoined.minimize());
+    assert!(transitions * 10 < disjoined_transitions);
+
+    // Joined classes repeat their ranges in each transition, but with transitions on class
+    // numbers, the ranges are stored once, in the alphabet.
+    let class_ranges: usize = alphabet.classes().iter().map(|c| c.iter().count()).sum();
+    assert!(class_ranges < disjoined_ranges);
+}
@@ -0,0 +1,120 @@
+use regexp2::automata::NFA;
+use regexp2::class::CharClass;
+use regexp2::parser::{NFAParser, ParseError, Parser};
+use regexp2::{RegExp, Regex};
+
+fn ranges(re: &Regex, input: &str) -> Vec<(usize, usize)> {
+    re.find_all(input).map(|m| (m.start, m.end)).collect()
+}
+
+#[test]
+fn test_whole_input() {
+    let re = Regex::new(r"\Aabc\z").unwrap();
+    assert!(re.is_full_match("abc"));
+    assert_eq!(vec![(0, 3)], ranges(&re, "abc"));
+
+    assert!(re.find("xabc").is_none());
+    assert!(re.find("abcx").is_none());
+    assert!(re.find("abc abc").is_none());
+    assert!(re.captures("xabcx").is_none());
+    assert_eq!(
+        Some("abc".to_string()),
+        re.captures("abc").unwrap().get_str(0)
+    );
+}
+
+#[test]
+fn test_start() {
+    let re = Regex::new(r"\Aa+").unwrap();
+    assert_eq!(vec![(0, 2)], ranges(&re, "aa aa"));
+    assert!(ranges(&re, "baa").is_empty());
+
+    let re = RegExp::new(r"\Aa+").unwrap();
+    assert!(re.find_at("aaa", 0).is_some());
+    assert!(re.find_at("aaa", 1).is_none());
+    assert!(re.find_shortest_at("aaa", 1).is_none());
+
+    // Captures are only searched for at the start of input.
+    let re = Regex::new(r"\A(a)b").unwrap();
+    assert!(re.captures("cab").is_none());
+    assert_eq!(
+        Some("a".to_string()),
+        re.captures("abc").unwrap().get_str(1)
+    );
+}
+
+#[test]
+fn test_end() {
+    let re = Regex::new(r"a+\z").unwrap();
+    assert_eq!(vec![(3, 5)], ranges(&re, "aa aa"));
+    assert!(ranges(&re, "aab").is_empty());
+
+    let re = RegExp::new_with_dfa(r"a+\z").unwrap();
+    assert!(re.find("aab").is_none());
+    assert_eq!(4, re.find_at("baaa", 1).unwrap().end);
+    // The shortest match must also reach the end of input.
+    assert_eq!(4, re.find_shortest_at("baaa", 1).unwrap().end);
+
+    let re = Regex::new(r"(b)a*\z").unwrap();
+    let caps = re.captures("bab baa").unwrap();
+    assert_eq!(
+        (4, 7),
+        (caps.get(0).unwrap().start, caps.get(0).unwrap().end)
+    );
+}
+
+#[test]
+fn test_empty() {
+    let re = Regex::new(r"\A\z").unwrap();
+    assert!(re.is_full_match(""));
+    assert!(!re.is_full_match("a"));
+    assert_eq!(vec![(0, 0)], ranges(&re, ""));
+    assert!(ranges(&re, "a").is_empty());
+}
+
+#[test]
+fn test_escaping() {
+    // Anchors are escapes only outside of char classes; elsewhere, `A` and `z` are literals.
+    let re = Regex::new(r"[\A\z]+").unwrap();
+    assert!(re.is_full_match("Azz"));
+
+    let re = Regex::new(r"\\A").unwrap();
+    assert!(re.is_full_match(r"\A"));
+
+    // Grouping a union keeps every branch anchored.
+    let re = Regex::new(r"\A(a|b)\z").unwrap();
+    assert!(re.is_full_match("a"));
+    assert!(re.find("ab").is_none());
+
+    let re = Regex::new(r"(a|b)\z").unwrap();
+    let ranges: Vec<_> = re.find_all("axa").map(|m| (m.start, m.end)).collect();
+    assert_eq!(vec![(2, 3)], ranges);
+}
+
+#[test]
+fn test_misplaced() {
+    let misplaced = |expr| matches!(Regex::new(expr), Err(ParseError::MisplacedAnchor));
+
+    assert!(misplaced(r"a\Ab"));
+    assert!(misplaced(r"a\zb"));
+    assert!(misplaced(r"(\Aa)"));
+    assert!(misplaced(r"(a\z)"));
+    assert!(misplaced(r"\Aa|b"));
+    assert!(misplaced(r"a|\Ab"));
+    assert!(misplaced(r"a|b\z"));
+    assert!(misplaced(r"\Aa|b\z"));
+    assert!(misplaced(r"\A\Aa"));
+    assert!(misplaced(r"a\z\z"));
+    assert!(misplaced(r"a\z*"));
+}
+
+#[test]
+fn test_unsupported() {
+    // Parsing to an NFA alone cannot represent anchors.
+    let parser: NFAParser<CharClass> = NFAParser::new();
+    let nfa: Result<NFA<CharClass>, _> = parser.parse(r"\Aa");
+    assert!(matches!(nfa, Err(ParseError::UnsupportedAnchor)));
+
+    let (_, anchors): (NFA<CharClass>, _) = parser.parse_anchored(r"\Aa\z").unwrap();
+    assert!(anchors.start && anchors.end);
+}
@@ -0,0 +1,62 @@
+use regexp2::Regex;
+
+#[test]
+fn test_captures() {
+    let re = Regex::new(r"(\d+)-(\d+)").unwrap();
+
+    let caps = re.captures("from 10-234 on").unwrap();
+    assert_eq!(Some("10-234".to_string()), caps.get_str(0));
+    assert_eq!(Some("10".to_string()), caps.get_str(1));
+    assert_eq!(Some("234".to_string()), caps.get_str(2));
+    assert_eq!(None, caps.get_str(3));
+
+    let m = caps.get(2).unwrap();
+    assert_eq!((8, 11), (m.start, m.end));
+
+    assert!(re.captures("10-").is_none());
+}
+
+#[test]
+fn test_captures_nested() {
+    // Groups are numbered by their opening parentheses.
+    let re = Regex::new("((a)(b))c").unwrap();
+    let caps = re.captures("abc").unwrap();
+    let groups: Vec<_> = (0..4).map(|i| caps.get_str(i).unwrap()).collect();
+    assert_eq!(vec!["abc", "ab", "a", "b"], groups);
+}
+
+#[test]
+fn test_captures_longest() {
+    // Earlier groups match as much as possible.
+    let re = Regex::new("(a*)(a*)").unwrap();
+    let caps = re.captures("aaa").unwrap();
+    assert_eq!(Some("aaa".to_string()), caps.get_str(1));
+    assert_eq!(Some("".to_string()), caps.get_str(2));
+
+    // The overall match is the longest, even if an earlier alternative is shorter.
+    let re = Regex::new("(a|ab)(c|bcd)").unwrap();
+    let caps = re.captures("abcd").unwrap();
+    assert_eq!(Some("abcd".to_string()), caps.get_str(0));
+    assert_eq!(Some("a".to_string()), caps.get_str(1));
+    assert_eq!(Some("bcd".to_string()), caps.get_str(2));
+}
+
+#[test]
+fn test_captures_repeated() {
+    // Repeated groups report their last iteration.
+    let re = Regex::new("([a-z])+").unwrap();
+    let caps = re.captures("xyz").unwrap();
+    assert_eq!(Some("z".to_string()), caps.get_str(1));
+
+    // Groups in untaken alternatives do not participate.
+    let re = Regex::new("(a)|(b)").unwrap();
+    let caps = re.captures("b").unwrap();
+    assert!(caps.get(1).is_none());
+    assert_eq!(Some("b".to_string()), caps.get_str(2));
+    assert_eq!(3, caps.iter().count());
+
+    // Empty groups.
+    let re = Regex::new("a()b").unwrap();
+    let caps = re.captures("ab").unwrap();
+    assert_eq!(Some("".to_string()), caps.get_str(1));
+}
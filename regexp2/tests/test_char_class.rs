@@ -1,4 +1,9 @@
-use regexp2::RegExp;
+use regexp2::{
+    automata::NFA,
+    class::CharClass,
+    parser::{NFAParser, Parser},
+    RegExp,
+};
 
 include!("macros.rs");
 
@@ -58,6 +63,19 @@ fn test_negation_symbol() {
     run_tests!(&exprs, &valids, &invalids);
 }
 
+#[test]
+fn test_overlapping_ranges() {
+    let exprs = ["[a-cb-e]", "[a-cd-e]", "[b-ea-cc]"];
+    let valids = ["a", "b", "c", "d", "e"];
+    let invalids = ["", "f", "`", "ab"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    let exprs = ["[^a-cb-e]", "[^a-cd-e]"];
+    let valids = ["f", "`", "A", "-"];
+    let invalids = ["", "a", "c", "d", "e"];
+    run_tests!(&exprs, &valids, &invalids);
+}
+
 #[test]
 fn test_wildcard() {
     let exprs = ["."];
@@ -71,6 +89,26 @@ fn test_wildcard() {
     run_tests!(&exprs, &valids, &invalids);
 }
 
+#[test]
+fn test_wildcard_newline() {
+    let mut parser = NFAParser::new();
+
+    let nfa: NFA<CharClass> = parser.parse(".").unwrap();
+    assert!(nfa.is_match("a".chars()));
+    assert!(!nfa.is_match("\n".chars()));
+
+    parser.dot_matches_newline = true;
+    let nfa: NFA<CharClass> = parser.parse(".").unwrap();
+    assert!(nfa.is_match("a".chars()));
+    assert!(nfa.is_match("\n".chars()));
+    assert!(nfa.is_match("\u{10ffff}".chars()));
+
+    // Escaped or bracketed dots are unaffected.
+    let nfa: NFA<CharClass> = parser.parse(r"\.[.]").unwrap();
+    assert!(nfa.is_match("..".chars()));
+    assert!(!nfa.is_match("\n\n".chars()));
+}
+
 #[test]
 fn test_special_classes() {
     let exprs = [r"\d"];
@@ -116,3 +154,142 @@ fn test_special_classes() {
     let invalids = ["", "a", "A", "5", "_"];
     run_tests!(&exprs, &valids, &invalids);
 }
+
+#[test]
+fn test_negated_special_classes() {
+    let exprs = [r"[^\d]", r"\D"];
+    let valids = [" ", "a", "A", "-", "\n"];
+    let invalids = ["", "0", "5", "9", "４", "８"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    // The negation applies to the union of both shorthands.
+    let exprs = [r"[^\d\s]", r"[^\s\d]"];
+    let valids = ["a", "A", "-", "_", "["];
+    let invalids = ["", "0", "9", "４", " ", "\t", "\n", "\u{3000}"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    let exprs = [r"[^\D]"];
+    let valids = ["0", "5", "９"];
+    let invalids = ["", " ", "a", "-"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    let exprs = [r"[\w-]"];
+    let valids = ["a", "Z", "0", "_", "-"];
+    let invalids = ["", " ", "[", "]", "\\", "a-"];
+    run_tests!(&exprs, &valids, &invalids);
+}
+
+#[test]
+fn test_categories() {
+    let exprs = [r"\p{L}", r"\p{Letter}", r"[\p{L}]"];
+    let valids = ["a", "Z", "é", "何"];
+    let invalids = ["", "0", "_", " ", "ab"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    let exprs = [r"\p{Nd}", r"\p{Decimal_Number}", r"\d"];
+    let valids = ["0", "9", "４"];
+    let invalids = ["", "a", "-"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    let exprs = [r"\P{Nd}", r"[^\p{Nd}]", r"[\P{Nd}]"];
+    let valids = ["a", " ", "-", "\n"];
+    let invalids = ["", "0", "9", "４"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    // Without braces, \p is a literal.
+    let exprs = [r"\p"];
+    let valids = ["p"];
+    let invalids = ["", "\\p"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    for expr in &[r"\p{Lu}", r"\p{L", r"[\p{}]"] {
+        assert!(matches!(
+            RegExp::new(expr),
+            Err(regexp2::parser::ParseError::UnknownCategory)
+        ));
+    }
+}
+
+#[test]
+fn test_negated_combined_classes() {
+    // Negation applies to the shorthand and the ranges together.
+    let exprs = [r"[^\wA-F]", r"[^A-F\w]"];
+    let valids = [" ", "-", "é", "\n"];
+    let invalids = ["", "a", "A", "F", "G", "0", "_"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    let exprs = [r"[^\p{L}0-9_]", r"[^0-9\p{L}_]"];
+    let valids = [" ", "-", "٣", "\n"];
+    let invalids = ["", "a", "é", "何", "0", "9", "_"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    // A pending single char or dash is merged before negating.
+    let exprs = [r"[^\d-]", r"[^-\d]"];
+    let valids = ["a", " "];
+    let invalids = ["", "-", "0", "４"];
+    run_tests!(&exprs, &valids, &invalids);
+}
+
+#[test]
+fn test_literal_dash() {
+    // Leading dash.
+    let exprs = ["[-az]"];
+    let valids = ["-", "a", "z"];
+    let invalids = ["", "b", "y", "-a"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    // Trailing dash, after a range or a single char.
+    let exprs = ["[a-z-]"];
+    let valids = ["-", "a", "m", "z"];
+    let invalids = ["", "A", "0", "a-"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    let exprs = ["[a-]"];
+    let valids = ["-", "a"];
+    let invalids = ["", "b", "a-"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    // Escaped dash between chars.
+    let exprs = [r"[a\-z]", r"[\-az]", r"[az\-]"];
+    let valids = ["-", "a", "z"];
+    let invalids = ["", "b", "m", "y"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    // Escaped dash as the bound of a range.
+    let exprs = [r"[\--/]"];
+    let valids = ["-", ".", "/"];
+    let invalids = ["", ",", "0"];
+    run_tests!(&exprs, &valids, &invalids);
+}
+
+#[test]
+fn test_char_escapes() {
+    let exprs = [r"\t|\r|\n|\0", r"[\t\r\n\0]", r"[\0-\t]|[\n-\r]"];
+    let valids = ["\t", "\r", "\n", "\0"];
+    let invalids = ["", "t", "r", "n", "0", "\\t"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    let exprs = [r"\u{1F600}+", r"[\u{1f600}-\u{1F600}]+"];
+    let valids = ["😀", "😀😀"];
+    let invalids = ["", "u", "u{1F600}"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    // Escaped brackets and backslashes are literals in a char class.
+    let exprs = [r"[\[\]\\]"];
+    let valids = ["[", "]", "\\"];
+    let invalids = ["", "a", "[]"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    // Without braces, \u is a literal.
+    let exprs = [r"\u"];
+    let valids = ["u"];
+    let invalids = ["", "\\u"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    for expr in &[r"\u{", r"\u{}", r"\u{D800}", r"\u{110000}", r"[\u{g}]"] {
+        assert!(matches!(
+            RegExp::new(expr),
+            Err(regexp2::parser::ParseError::InvalidEscape)
+        ));
+    }
+}
@@ -0,0 +1,303 @@
+use regexp2::{
+    automata::dfa::Disjoin,
+    class::{CharClass, CharRange},
+};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+fn ranges(class: &CharClass) -> Vec<(char, char)> {
+    class.iter().map(|r| (r.start, r.end)).collect()
+}
+
+#[test]
+fn test_normalize_overlapping() {
+    let mut class: CharClass = vec![CharRange::new('a', 'c'), CharRange::new('b', 'e')].into();
+    class.normalize();
+    assert_eq!(vec![('a', 'e')], ranges(&class));
+
+    // A wide range covering several existing ones.
+    let mut class: CharClass = vec![
+        CharRange::new('b', 'c'),
+        CharRange::new('f', 'g'),
+        CharRange::new('j', 'k'),
+        CharRange::new('a', 'z'),
+    ]
+    .into();
+    class.normalize();
+    assert_eq!(vec![('a', 'z')], ranges(&class));
+}
+
+#[test]
+fn test_normalize_adjacent() {
+    let mut class: CharClass = vec![
+        CharRange::new('d', 'f'),
+        CharRange::new('a', 'c'),
+        CharRange::new('x', 'z'),
+    ]
+    .into();
+    class.normalize();
+    assert_eq!(vec![('a', 'f'), ('x', 'z')], ranges(&class));
+
+    let mut class: CharClass = vec!['a', 'b', 'c', 'e'].into();
+    class.normalize();
+    assert_eq!(vec![('a', 'c'), ('e', 'e')], ranges(&class));
+
+    // Ranges on either side of the surrogate code points.
+    let mut class: CharClass = vec![
+        CharRange::new('\u{d000}', '\u{d7ff}'),
+        CharRange::new('\u{e000}', '\u{e0ff}'),
+    ]
+    .into();
+    class.normalize();
+    assert_eq!(vec![('\u{d000}', '\u{e0ff}')], ranges(&class));
+}
+
+#[test]
+fn test_normalize_equality() {
+    let mut c1: CharClass = vec![CharRange::new('a', 'c'), CharRange::new('d', 'e')].into();
+    let mut c2: CharClass = vec![CharRange::new('a', 'e')].into();
+    c1.normalize();
+    c2.normalize();
+    assert_eq!(c1, c2);
+
+    let mut class: CharClass = vec![CharRange::new('\u{0}', 'm'), CharRange::new('n', 'z')].into();
+    class.normalize();
+    assert!(class.contains('n'));
+    assert_eq!(
+        vec![('{', '\u{d7ff}'), ('\u{e000}', '\u{10ffff}')],
+        ranges(&class.complement())
+    );
+}
+
+#[test]
+fn test_display_range() {
+    assert_eq!("a-z", CharRange::new('a', 'z').to_string());
+    assert_eq!("a", CharRange::new_single('a').to_string());
+    assert_eq!("\\]-\\^", CharRange::new(']', '^').to_string());
+    assert_eq!("\\n", CharRange::new_single('\n').to_string());
+    assert_eq!("α-ω", CharRange::new('α', 'ω').to_string());
+}
+
+#[test]
+fn test_display_class() {
+    let class: CharClass = vec![CharRange::new('a', 'z'), CharRange::new('0', '9')].into();
+    assert_eq!("[0-9a-z]", class.to_string());
+
+    let class: CharClass = vec!['-', '_', '\\'].into();
+    assert_eq!("[\\-\\\\_]", class.to_string());
+
+    assert_eq!("[^\\n]", CharClass::all_but_newline().to_string());
+    assert_eq!("[^0-9A-Z_a-z]", CharClass::word().complement().to_string());
+
+    // Ranges on either side of the surrogate code points are merged.
+    let class: CharClass = vec![
+        CharRange::new('\u{0}', 'a'),
+        CharRange::new('c', '\u{d7ff}'),
+        CharRange::new('\u{e000}', '\u{10ffff}'),
+    ]
+    .into();
+    assert_eq!("[^b]", class.to_string());
+}
+
+#[test]
+fn test_is_empty() {
+    assert!(CharClass::new().is_empty());
+
+    // The complement of the full range of characters.
+    let full: CharClass = CharRange::new('\0', '\u{10ffff}').into();
+    assert!(!full.is_empty());
+    assert!(full.complement().is_empty());
+
+    let class: CharClass = vec![CharRange::new('a', 'z'), CharRange::new('0', '9')].into();
+    assert!(!class.is_empty());
+    assert!(!class.complement().is_empty());
+
+    // A range with its bounds reversed contains nothing.
+    let reversed: CharClass = CharRange::new('z', 'a').into();
+    assert!(reversed.is_empty());
+}
+
+#[test]
+fn test_complement_bounds() {
+    // The complement of an empty class is every character, without the surrogate code points.
+    let all = vec![('\u{0}', '\u{d7ff}'), ('\u{e000}', '\u{10ffff}')];
+    assert_eq!(all, ranges(&CharClass::new().complement()));
+    assert_eq!(all, ranges(&CharClass::all()));
+    assert!(CharClass::all().complement().is_empty());
+
+    // Classes containing the lowest and highest characters, and ranges ending at the surrogate
+    // code points.
+    let class: CharClass = vec!['\u{0}', '\u{10ffff}'].into();
+    assert_eq!(
+        vec![('\u{1}', '\u{d7ff}'), ('\u{e000}', '\u{10fffe}')],
+        ranges(&class.complement())
+    );
+
+    let class: CharClass = CharRange::new('\u{e000}', '\u{10ffff}').into();
+    assert_eq!(vec![('\u{0}', '\u{d7ff}')], ranges(&class.complement()));
+
+    let class: CharClass = CharRange::new('\u{0}', '\u{d7ff}').into();
+    assert_eq!(
+        vec![('\u{e000}', '\u{10ffff}')],
+        ranges(&class.complement())
+    );
+
+    // A gap across the surrogate code points is split around them.
+    let class: CharClass = vec!['a', '\u{10000}'].into();
+    assert_eq!(
+        vec![
+            ('\u{0}', '`'),
+            ('b', '\u{d7ff}'),
+            ('\u{e000}', '\u{ffff}'),
+            ('\u{10001}', '\u{10ffff}')
+        ],
+        ranges(&class.complement())
+    );
+
+    // Complementing twice gives back the original characters.
+    for class in &[
+        CharClass::decimal_number(),
+        CharClass::word(),
+        CharClass::whitespace(),
+    ] {
+        let mut class = class.clone();
+        class.normalize();
+        assert_eq!(ranges(&class), ranges(&class.complement().complement()));
+    }
+}
+
+#[test]
+fn test_range_intersects() {
+    let a_c = CharRange::new('a', 'c');
+    let d_f = CharRange::new('d', 'f');
+    let b_e = CharRange::new('b', 'e');
+    let x_z = CharRange::new('x', 'z');
+
+    // Touching ranges have no characters in common.
+    assert!(!a_c.intersects(&d_f));
+    assert_eq!(None, a_c.intersection(&d_f));
+
+    // Overlapping ranges, in either order.
+    assert!(a_c.intersects(&b_e));
+    assert!(b_e.intersects(&a_c));
+    assert_eq!(Some(CharRange::new('b', 'c')), a_c.intersection(&b_e));
+    assert_eq!(Some(CharRange::new('d', 'e')), d_f.intersection(&b_e));
+    assert_eq!(
+        Some(d_f.clone()),
+        d_f.intersection(&CharRange::new('a', 'z'))
+    );
+
+    // Disjoint ranges.
+    assert!(!a_c.intersects(&x_z));
+    assert_eq!(None, x_z.intersection(&a_c));
+}
+
+#[test]
+fn test_range_merge() {
+    let a_c = CharRange::new('a', 'c');
+    let d_f = CharRange::new('d', 'f');
+
+    // Touching ranges, in either order.
+    assert_eq!(Some(CharRange::new('a', 'f')), a_c.merge(&d_f));
+    assert_eq!(Some(CharRange::new('a', 'f')), d_f.merge(&a_c));
+
+    // Overlapping and nested ranges.
+    assert_eq!(
+        Some(CharRange::new('a', 'e')),
+        a_c.merge(&CharRange::new('b', 'e'))
+    );
+    assert_eq!(
+        Some(CharRange::new('a', 'z')),
+        d_f.merge(&CharRange::new('a', 'z'))
+    );
+
+    // Disjoint ranges.
+    assert_eq!(None, a_c.merge(&CharRange::new('e', 'f')));
+    assert_eq!(None, CharRange::new('x', 'z').merge(&a_c));
+
+    // Ranges on either side of the surrogate code points are adjacent.
+    let below = CharRange::new('\u{d000}', '\u{d7ff}');
+    let above = CharRange::new('\u{e000}', '\u{e0ff}');
+    assert_eq!(
+        Some(CharRange::new('\u{d000}', '\u{e0ff}')),
+        below.merge(&above)
+    );
+}
+
+#[test]
+fn test_range_surrogates() {
+    let pairs = |ranges: Vec<CharRange>| -> Vec<(char, char)> {
+        ranges.iter().map(|r| (r.start, r.end)).collect()
+    };
+
+    // Ranges ending just below or beginning just above the surrogate code points.
+    assert_eq!(
+        vec![('\u{e000}', '\u{10ffff}')],
+        pairs(CharRange::new('\u{0}', '\u{d7ff}').complement())
+    );
+    assert_eq!(
+        vec![('\u{0}', '\u{d7ff}')],
+        pairs(CharRange::new('\u{e000}', '\u{10ffff}').complement())
+    );
+    assert_eq!(
+        vec![('\u{0}', '\u{d7fe}'), ('\u{e000}', '\u{10ffff}')],
+        pairs(CharRange::new('\u{d7ff}', '\u{d7ff}').complement())
+    );
+    assert_eq!(
+        vec![('\u{0}', '\u{d7ff}'), ('\u{e001}', '\u{10ffff}')],
+        pairs(CharRange::new('\u{e000}', '\u{e000}').complement())
+    );
+
+    // A range across the surrogate code points.
+    let across = CharRange::new('\u{d7fe}', '\u{e001}');
+    assert_eq!(
+        vec![('\u{0}', '\u{d7fd}'), ('\u{e002}', '\u{10ffff}')],
+        pairs(across.complement())
+    );
+    assert_eq!(
+        vec!['\u{d7fe}', '\u{d7ff}', '\u{e000}', '\u{e001}'],
+        across.chars().collect::<Vec<_>>()
+    );
+    assert!(across.contains('\u{d7ff}') && across.contains('\u{e000}'));
+
+    // Pieces of transitions that begin or end at the surrogate code points skip them.
+    let wide: CharClass = CharRange::new('\u{d000}', '\u{e0ff}').into();
+    let below: CharClass = CharRange::new('\u{d000}', '\u{d7ff}').into();
+    let above: CharClass = CharRange::new('\u{e000}', '\u{e0ff}').into();
+    let pieces: Vec<_> = CharClass::disjoin(vec![&wide, &below, &above])
+        .iter()
+        .flat_map(ranges)
+        .collect();
+    assert_eq!(
+        vec![('\u{d000}', '\u{d7ff}'), ('\u{e000}', '\u{e0ff}')],
+        pieces
+    );
+}
+
+#[test]
+fn test_from_chars() {
+    let class = CharClass::from_chars(vec!['a', 'b', 'c']);
+    assert_eq!(vec![('a', 'c')], ranges(&class));
+
+    // Order and duplicates do not matter; non-adjacent characters stay separate.
+    let class = CharClass::from_chars("zxayx".chars());
+    assert_eq!(vec![('a', 'a'), ('x', 'z')], ranges(&class));
+
+    assert!(CharClass::from_chars(Vec::new()).is_empty());
+}
+
+#[test]
+fn test_from_ranges() {
+    let class = CharClass::from_ranges(vec![('a', 'f'), ('d', 'k'), ('l', 'm'), ('0', '9')]);
+    assert_eq!(vec![('0', '9'), ('a', 'm')], ranges(&class));
+    assert!(class.contains('h'));
+    assert!(!class.contains('n'));
+}
+
+#[test]
+fn test_send_sync() {
+    assert_send_sync::<CharClass>();
+    assert_send_sync::<CharRange>();
+    assert_send_sync::<regexp2::automata::DFA<CharClass>>();
+    assert_send_sync::<regexp2::Regex>();
+}
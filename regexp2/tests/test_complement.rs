@@ -0,0 +1,44 @@
+use regexp2::{
+    automata::{DFA, NFA},
+    class::CharClass,
+    parser::{NFAParser, Parser},
+};
+
+fn dfa(expr: &str) -> DFA<CharClass> {
+    let nfa: NFA<CharClass> = NFAParser::new().parse(expr).unwrap();
+    nfa.into()
+}
+
+#[test]
+fn test_complement_plus() {
+    let complement = dfa("a+").complement();
+
+    for input in &["a", "aaa"] {
+        assert!(!complement.is_match(input.chars()), "{} matched", input);
+    }
+    for input in &["", "b", "ab", "aab", "ba", "\u{10FFFF}"] {
+        assert!(complement.is_match(input.chars()), "{} not matched", input);
+    }
+}
+
+#[test]
+fn test_double_complement() {
+    let exprs = ["a(b)*", "(a|b)*abb", "[a-c]+d?", "[^x]y"];
+    let inputs = [
+        "", "a", "b", "ab", "abb", "aabb", "babb", "abbb", "cd", "acd", "xy", "zy", "zyy",
+    ];
+
+    for expr in &exprs {
+        let d = dfa(expr);
+        let double = d.complement().complement();
+        for input in &inputs {
+            assert_eq!(
+                d.is_match(input.chars()),
+                double.is_match(input.chars()),
+                "{} on {}",
+                expr,
+                input
+            );
+        }
+    }
+}
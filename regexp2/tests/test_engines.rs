@@ -0,0 +1,56 @@
+use regexp2::RegExp;
+
+/// Patterns and inputs on which the NFA and DFA engines are compared.
+const PATTERNS: [&str; 10] = [
+    "a",
+    "abc",
+    "a*",
+    "(a|b)*abb",
+    "a+b?",
+    "(ab|a)(bc|c)?",
+    r"\d+(\.\d*)?",
+    "(a*)*b",
+    "x?",
+    r"[^\s]+\s*",
+];
+const INPUTS: [&str; 10] = [
+    "", "a", "b", "aaa", "abb abb", "ababb", "abc", "12.5x", "xx  y", "ba",
+];
+
+#[test]
+fn test_engines_agree() {
+    for &expr in PATTERNS.iter() {
+        let nfa_re = RegExp::new(expr).unwrap();
+        let dfa_re = RegExp::new_with_dfa(expr).unwrap();
+
+        for &input in INPUTS.iter() {
+            assert_eq!(
+                nfa_re.is_match(input),
+                dfa_re.is_match(input),
+                r#"engines disagree on whether "{}" matches "{}""#,
+                expr,
+                input
+            );
+
+            for start in 0..=input.chars().count() {
+                let range = |m: Option<regexp2::Match<char>>| m.map(|m| (m.range(), m.span));
+                assert_eq!(
+                    range(nfa_re.find_at(input, start)),
+                    range(dfa_re.find_at(input, start)),
+                    r#"engines disagree on the match of "{}" in "{}" at {}"#,
+                    expr,
+                    input,
+                    start
+                );
+                assert_eq!(
+                    range(nfa_re.find_shortest_at(input, start)),
+                    range(dfa_re.find_shortest_at(input, start)),
+                    r#"engines disagree on the shortest match of "{}" in "{}" at {}"#,
+                    expr,
+                    input,
+                    start
+                );
+            }
+        }
+    }
+}
@@ -34,3 +34,23 @@ fn test_kleene() {
     let invalids = [" ", "* ", " *", r"\*", r"\"];
     run_tests!(&exprs, &valids, &invalids);
 }
+
+#[test]
+fn test_stacked_quantifiers() {
+    // A quantifier applies to the quantified expression before it. Starring an expression that
+    // matches the empty string creates a cycle of epsilon transitions in the NFA.
+    let exprs = ["a**", "(a*)*", "a+*", "a*+", "a?*", "a*?", "(a?)+"];
+    let valids = ["", "a", "aa", "aaa"];
+    let invalids = [" ", "b", "ab"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    let exprs = ["a+?", "a?+"];
+    let valids = ["", "a", "aa"];
+    let invalids = [" ", "b", "ab"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    let exprs = ["a++", "(a+)+"];
+    let valids = ["a", "aa", "aaa"];
+    let invalids = ["", "b", "ab"];
+    run_tests!(&exprs, &valids, &invalids);
+}
@@ -1,4 +1,9 @@
-use regexp2::RegExp;
+use regexp2::{
+    automata::NFA,
+    class::CharClass,
+    parser::{NFAParser, Operator, ParseError, Parser},
+    RegExp,
+};
 
 macro_rules! run_invalid_tests {
     ($exprs:expr) => {{
@@ -12,7 +17,148 @@ macro_rules! run_invalid_tests {
 #[test]
 fn test_malformed() {
     let exprs = [
-        "(", ")", "a(", "(()", "*", "|", "*a", "**", "a|", "a)*", "(ab",
+        "(", ")", "a(", "(()", "*", "*a", "**", "a)*", "(ab", "(a|", "a|)",
     ];
     run_invalid_tests!(&exprs);
 }
+
+#[test]
+fn test_dangling_quantifier() {
+    let parser = NFAParser::new();
+    // At the start of the expression, directly after a left parenthesis, and directly after a
+    // union.
+    let starts = ["*", "+", "?", "*a", "+a", "?a"];
+    let parens = ["(*)", "(+a)", "a(?b)", "((*a))"];
+    let unions = ["a|*", "a|+b", "(a|?)", "a|b|*c"];
+
+    let exprs = starts.iter().chain(&parens).chain(&unions);
+    exprs.for_each(|&expr| {
+        let result: Result<NFA<CharClass>, _> = parser.parse(expr);
+        assert!(
+            matches!(result, Err(ParseError::DanglingQuantifier)),
+            r#""{}" did not fail with a dangling quantifier"#,
+            expr
+        );
+    });
+}
+
+#[test]
+fn test_too_large() {
+    // Each plus copies its operand twice, so nested pluses grow the NFA exponentially.
+    let expr = format!("{}a{}", "(".repeat(40), ")+".repeat(40));
+    let parser = NFAParser::new();
+    let result: Result<NFA<CharClass>, _> = parser.parse(&expr);
+    assert!(matches!(result, Err(ParseError::TooLarge)));
+
+    let mut parser = NFAParser::new();
+    parser.max_states = 10;
+    let result: Result<NFA<CharClass>, _> = parser.parse("abcdef");
+    assert!(matches!(result, Err(ParseError::TooLarge)));
+    let result: Result<NFA<CharClass>, _> = parser.parse("ab");
+    assert!(result.is_ok());
+}
+
+/// Parser that discards every operand, and so never produces output.
+struct DiscardParser;
+
+impl Parser<()> for DiscardParser {
+    fn shift_action(
+        &self,
+        _stack: &mut Vec<()>,
+        _op_stack: &mut Vec<Operator>,
+        _c: CharClass,
+    ) -> Result<(), ParseError> {
+        Ok(())
+    }
+
+    fn reduce_action(
+        &self,
+        _stack: &mut Vec<()>,
+        op_stack: &mut Vec<Operator>,
+    ) -> Result<(), ParseError> {
+        op_stack.pop();
+        Ok(())
+    }
+}
+
+#[test]
+fn test_no_output() {
+    for expr in &["", "a", "a|b*"] {
+        assert!(matches!(
+            DiscardParser.parse(expr),
+            Err(ParseError::NoOutput)
+        ));
+    }
+}
+
+#[test]
+fn test_braces() {
+    // A brace that does not begin a repetition count is a literal.
+    let re = RegExp::new("a{b}").unwrap();
+    assert!(re.is_match("a{b}"));
+    assert!(!re.is_match("ab"));
+
+    for expr in &["{", "}", "a{", "a{}", "a{,2}", "a{2", "a{2,b}", "a{1,2,3}"] {
+        let re = RegExp::new(expr).unwrap();
+        assert!(re.is_match(expr), r#""{}" did not match itself"#, expr);
+    }
+
+    // Braces in a character class are literals.
+    let re = RegExp::new("[{2}]").unwrap();
+    assert!(re.is_match("{"));
+    assert!(re.is_match("}"));
+
+    // An escaped brace is always a literal.
+    let re = RegExp::new(r"a\{2}").unwrap();
+    assert!(re.is_match("a{2}"));
+    assert!(!re.is_match("aa"));
+
+    // Repetition counts are reserved for bounded repetition.
+    let parser = NFAParser::new();
+    for expr in &["a{2}", "a{2,}", "a{2,5}", "(ab){10}", "{2}"] {
+        let result: Result<NFA<CharClass>, _> = parser.parse(expr);
+        assert!(
+            matches!(result, Err(ParseError::UnsupportedRepetition)),
+            r#""{}" did not fail with an unsupported repetition"#,
+            expr
+        );
+    }
+}
+
+/// Parser that keeps every operand but discards operators without combining their operands, and
+/// so leaves several items on the stack.
+struct DanglingParser;
+
+impl Parser<()> for DanglingParser {
+    fn shift_action(
+        &self,
+        stack: &mut Vec<()>,
+        _op_stack: &mut Vec<Operator>,
+        _c: CharClass,
+    ) -> Result<(), ParseError> {
+        stack.push(());
+        Ok(())
+    }
+
+    fn reduce_action(
+        &self,
+        _stack: &mut Vec<()>,
+        op_stack: &mut Vec<Operator>,
+    ) -> Result<(), ParseError> {
+        op_stack.pop();
+        Ok(())
+    }
+}
+
+#[test]
+fn test_malformed_expression() {
+    for expr in &["ab", "a|b", "(ab)*"] {
+        assert!(matches!(
+            DanglingParser.parse(expr),
+            Err(ParseError::MalformedExpression)
+        ));
+    }
+
+    // A single operand reduces to a single item.
+    assert!(DanglingParser.parse("a*").is_ok());
+}
@@ -0,0 +1,34 @@
+use regexp2::{
+    automata::{DFA, NFA},
+    class::CharClass,
+    parser::{NFAParser, Parser},
+};
+
+/// Keywords of a hypothetical language, many of which share prefixes or suffixes.
+const KEYWORDS: [&str; 50] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "case", "catch", "class",
+    "const", "continue", "crate", "default", "do", "dyn", "else", "enum", "extern", "false",
+    "final", "finally", "fn", "for", "if", "impl", "in", "let", "loop", "macro", "match", "mod",
+    "move", "mut", "override", "priv", "pub", "ref", "return", "self", "static", "struct", "super",
+    "trait", "true", "try", "type", "unsafe", "use", "while",
+];
+
+#[test]
+fn test_minimize_keywords() {
+    let expr = KEYWORDS.join("|");
+    let nfa: NFA<CharClass> = NFAParser::new().parse(&expr).unwrap();
+    let dfa: DFA<CharClass> = nfa.into();
+    let minimal = dfa.minimize();
+
+    // The subset construction already shares common prefixes, but every keyword still ends in
+    // its own final state; minimization shares the common suffixes as well.
+    assert_eq!(177, dfa.total_states);
+    assert_eq!(101, minimal.total_states);
+
+    for keyword in KEYWORDS.iter() {
+        assert!(minimal.is_match(keyword.chars()), "{} not matched", keyword);
+    }
+    for input in &["", "a", "asy", "asyncc", "breaks", "types", "whil"] {
+        assert!(!minimal.is_match(input.chars()), "{} matched", input);
+    }
+}
@@ -0,0 +1,34 @@
+use regexp2::{
+    automata::{dfa::DFAFromNFA, NFA},
+    class::CharClass,
+    parser::{NFAParser, Parser},
+};
+
+#[test]
+fn test_nfa_states_for() {
+    let nfa: NFA<CharClass> = NFAParser::new().parse("ab|ac").unwrap();
+    let nfa_finals = nfa.final_states.clone();
+    let nfa_initial_closure = nfa.epsilon_closure(nfa.initial_state);
+
+    let subset: DFAFromNFA<CharClass> = nfa.into();
+    let dfa = &subset.dfa;
+
+    // Every DFA state has a set of NFA states.
+    assert_eq!(dfa.total_states, subset.nfa_mapping.len());
+    assert_eq!(
+        &nfa_initial_closure,
+        subset.nfa_states_for(dfa.initial_state)
+    );
+
+    // A DFA state is final exactly when it represents a final NFA state. The final state after
+    // "ab" only represents the final state of the first alternative.
+    let (m, state) = dfa.find("ab".chars()).unwrap();
+    assert_eq!(2, m.end);
+
+    let nfa_states = subset.nfa_states_for(state);
+    assert_eq!(1, nfa_states.intersection(&nfa_finals).count());
+    for s in 0..dfa.total_states {
+        let has_final = !subset.nfa_states_for(s).is_disjoint(&nfa_finals);
+        assert_eq!(dfa.is_final_state(&s), has_final);
+    }
+}
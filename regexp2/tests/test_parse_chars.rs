@@ -0,0 +1,58 @@
+use regexp2::{
+    automata::NFA,
+    class::CharClass,
+    parser::{NFAParser, Parser},
+};
+
+/// Return the states and transitions of an NFA in a form that can be compared.
+fn structure(nfa: &NFA<CharClass>) -> (usize, usize, Vec<usize>, Vec<String>) {
+    let mut finals: Vec<_> = nfa.final_states.iter().copied().collect();
+    finals.sort_unstable();
+
+    let mut transitions: Vec<_> = (&nfa.transition)
+        .into_iter()
+        .map(|(src, t, dests)| {
+            let mut dests: Vec<_> = dests.iter().collect();
+            dests.sort_unstable();
+            format!("{} {:?} {:?}", src, t, dests)
+        })
+        .collect();
+    transitions.sort();
+
+    (nfa.initial_state, nfa.total_states, finals, transitions)
+}
+
+#[test]
+fn test_parse_chars() {
+    let parser = NFAParser::new();
+    for expr in &[
+        "",
+        "(a|b)*abb",
+        r"[^a-z\d]+\.?",
+        r"\p{L}\w*",
+        "a{b}",
+        r"\Aab\z",
+    ] {
+        let from_str: Result<(NFA<CharClass>, _), _> = parser.parse_anchored(expr);
+        let from_chars: Result<(NFA<CharClass>, _), _> = parser.parse_anchored_chars(expr.chars());
+        let ((from_str, str_anchors), (from_chars, chars_anchors)) =
+            (from_str.unwrap(), from_chars.unwrap());
+        assert_eq!(structure(&from_str), structure(&from_chars), "{}", expr);
+        assert_eq!(str_anchors, chars_anchors);
+    }
+
+    // Characters from any cloneable iterator, such as a composition of fragments.
+    let fragments = ["[a-z]", "+", r"\d"];
+    let composed: NFA<CharClass> = parser
+        .parse_chars(fragments.iter().flat_map(|f| f.chars()))
+        .unwrap();
+    let whole: NFA<CharClass> = parser.parse(r"[a-z]+\d").unwrap();
+    assert_eq!(structure(&whole), structure(&composed));
+    assert!(composed.is_match("abc1".chars()));
+
+    // The same errors are returned.
+    let err: Result<NFA<CharClass>, _> = parser.parse_chars("a{2}".chars());
+    assert!(err.is_err());
+    let err: Result<NFA<CharClass>, _> = parser.parse_chars("(a".chars());
+    assert!(err.is_err());
+}
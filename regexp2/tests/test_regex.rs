@@ -0,0 +1,167 @@
+use regexp2::Regex;
+
+fn ranges(re: &Regex, input: &str) -> Vec<(usize, usize)> {
+    re.find_all(input).map(|m| (m.start, m.end)).collect()
+}
+
+#[test]
+fn test_is_full_match() {
+    let re = Regex::new("(a|b)*abb").unwrap();
+    assert_eq!("(a|b)*abb", re.as_str());
+    assert!(re.is_full_match("abb"));
+    assert!(re.is_full_match("aababb"));
+    assert!(!re.is_full_match("abba"));
+    assert!(!re.is_full_match("cabb"));
+
+    // Unlike find, only the entire input is matched.
+    assert!(re.find("cabb").is_some());
+}
+
+#[test]
+fn test_is_match_fn() {
+    assert!(regexp2::is_match("(a|b)*abb", "aababb").unwrap());
+    assert!(!regexp2::is_match("(a|b)*abb", "abba").unwrap());
+    assert!(regexp2::is_match(r"\Aa+\z", "aaa").unwrap());
+
+    // The same inputs match as with a compiled regex.
+    let re = Regex::new(r"[a-z]+\d*").unwrap();
+    for input in &["", "abc", "abc12", "12", "ab1c"] {
+        let matched = regexp2::is_match(r"[a-z]+\d*", input).unwrap();
+        assert_eq!(re.is_full_match(input), matched, "{}", input);
+    }
+
+    assert!(regexp2::is_match("a)", "a").is_err());
+}
+
+#[test]
+fn test_find() {
+    let re = Regex::new(r"\d+").unwrap();
+
+    let m = re.find("abc 123 45").unwrap();
+    assert_eq!((4, 7), (m.start, m.end));
+    assert_eq!(vec!['1', '2', '3'], m.span);
+
+    assert!(re.find("abc").is_none());
+    assert!(re.find("").is_none());
+
+    // Positions are character indexes.
+    let m = Regex::new("b+").unwrap().find("何bb").unwrap();
+    assert_eq!((1, 3), (m.start, m.end));
+}
+
+#[test]
+fn test_find_all() {
+    let re = Regex::new(r"\d+").unwrap();
+    assert_eq!(vec![(4, 7), (8, 10)], ranges(&re, "abc 123 45"));
+    assert_eq!(vec![(0, 1), (2, 3)], ranges(&re, "1a2"));
+    assert!(ranges(&re, "abc").is_empty());
+
+    // Leftmost-longest: "aa" is matched as a whole rather than as two "a".
+    let re = Regex::new("a|aa").unwrap();
+    assert_eq!(vec![(0, 2), (3, 4)], ranges(&re, "aaba"));
+}
+
+#[test]
+fn test_anchoring() {
+    // is_match is anchored at both ends, while find is not anchored at all.
+    let re = Regex::new("ab").unwrap();
+    assert!(re.is_full_match("ab"));
+    assert!(!re.is_full_match("xab"));
+    assert!(!re.is_full_match("abx"));
+
+    let m = re.find("xabx").unwrap();
+    assert_eq!((1, 3), (m.start, m.end));
+}
+
+#[test]
+fn test_empty() {
+    let re = Regex::new("").unwrap();
+    assert!(re.is_full_match(""));
+    assert!(!re.is_full_match("a"));
+
+    // The empty pattern matches at every position, including the end of input.
+    assert_eq!(vec![(0, 0), (1, 1), (2, 2)], ranges(&re, "ab"));
+    assert_eq!(vec![(0, 0)], ranges(&re, ""));
+
+    // Patterns that may match the empty string prefer non-empty matches.
+    let re = Regex::new("a*").unwrap();
+    assert_eq!(vec![(0, 2), (2, 2), (3, 4), (4, 4)], ranges(&re, "aaba"));
+}
+
+#[test]
+fn test_union_precedence() {
+    // Alternation binds more loosely than both concatenation and quantifiers before it.
+    let re = Regex::new("ab*|c").unwrap();
+    assert!(re.is_full_match("c"));
+    assert!(re.is_full_match("abb"));
+    assert!(!re.is_full_match("ac"));
+
+    let re = Regex::new("(a)b+|c|d?").unwrap();
+    assert!(re.is_full_match("c"));
+    assert!(re.is_full_match(""));
+    assert!(!re.is_full_match("ac"));
+}
+
+#[test]
+fn test_split() {
+    let re = Regex::new(r"\s*,\s*").unwrap();
+    assert_eq!(vec!["a", "b", "c"], re.split("a,b,c"));
+    assert_eq!(vec!["a", "b", "c d"], re.split("a , b,\tc d"));
+    assert_eq!(vec!["", "a", ""], re.split(", a ,"));
+    assert_eq!(vec!["a", "", "b"], re.split("a,,b"));
+    assert_eq!(vec!["αβ", "γ"], re.split("αβ , γ"));
+    assert_eq!(vec!["abc"], re.split("abc"));
+    assert_eq!(vec![""], re.split(""));
+
+    // Empty matches split between characters.
+    let re = Regex::new("a*").unwrap();
+    assert_eq!(vec!["", "b", "", "b", ""], re.split("baab"));
+    assert_eq!(vec!["", ""], re.split(""));
+}
+
+#[test]
+fn test_replace_all() {
+    let re = Regex::new(r"\s*,\s*").unwrap();
+    assert_eq!("a;b;c d", re.replace_all("a , b,\tc d", ";"));
+    assert_eq!("abc", re.replace_all("abc", ";"));
+    assert_eq!("αβγ", re.replace_all("α,β , γ", ""));
+
+    // Matches do not overlap.
+    let re = Regex::new("aa").unwrap();
+    assert_eq!("bba", re.replace_all("aaaaa", "b"));
+
+    // Empty matches insert the replacement between characters.
+    let re = Regex::new("a*").unwrap();
+    assert_eq!("-b--b-", re.replace_all("baab", "-"));
+    assert_eq!("-", re.replace_all("", "-"));
+}
+
+#[test]
+fn test_find_str() {
+    let re = Regex::new("b+").unwrap();
+
+    // "何" and "é" are 3 and 2 bytes long in UTF-8.
+    let input = "何bbé b";
+    let m = re.find_str(input).unwrap();
+    assert_eq!(3..5, m.range());
+    assert_eq!("bb", m.as_str());
+
+    let matches: Vec<_> = re.find_all_str(input).map(|m| m.range()).collect();
+    assert_eq!(vec![3..5, 8..9], matches);
+    for range in matches {
+        assert_eq!("b".repeat(range.len()), &input[range]);
+    }
+
+    // Multi-byte characters in the match itself.
+    let re = Regex::new("[é何]+").unwrap();
+    let m = re.find_str("a何é何b").unwrap();
+    assert_eq!(1..9, m.range());
+    assert_eq!("何é何", m.as_str());
+
+    // Empty matches are between characters.
+    let re = Regex::new("x*").unwrap();
+    let matches: Vec<_> = re.find_all_str("é何").map(|m| m.range()).collect();
+    assert_eq!(vec![0..0, 2..2, 5..5], matches);
+
+    assert!(Regex::new("c").unwrap().find_str("何bb").is_none());
+}
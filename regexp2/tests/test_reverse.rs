@@ -0,0 +1,64 @@
+use regexp2::{
+    automata::{DFA, NFA},
+    class::CharClass,
+    parser::{NFAParser, Parser},
+};
+
+fn dfa(expr: &str) -> DFA<CharClass> {
+    let nfa: NFA<CharClass> = NFAParser::new().parse(expr).unwrap();
+    nfa.into()
+}
+
+#[test]
+fn test_double_reverse() {
+    let exprs = [
+        "a(b)*",
+        "(a|b)*abb",
+        "(ab|ac)*d",
+        "a*|b*",
+        "[a-c]+d?",
+        "(a|b)(a|b)",
+    ];
+    let inputs = [
+        "", "a", "b", "ab", "ba", "abb", "aabb", "babb", "abab", "abacd", "acd", "d", "aa", "bbb",
+        "cd", "abcd",
+    ];
+
+    for expr in &exprs {
+        let d = dfa(expr);
+        let reversed = DFA::from(d.reverse());
+        let minimal = DFA::from(reversed.reverse());
+
+        // The language is preserved, and the DFA is no larger than before.
+        assert!(minimal.total_states <= d.total_states);
+        for input in &inputs {
+            assert_eq!(
+                d.is_match(input.chars()),
+                minimal.is_match(input.chars()),
+                r#""{}" disagrees on "{}""#,
+                expr,
+                input
+            );
+
+            let reversed_input: String = input.chars().rev().collect();
+            assert_eq!(
+                d.is_match(input.chars()),
+                reversed.is_match(reversed_input.chars()),
+                r#""{}" reversed disagrees on "{}""#,
+                expr,
+                input
+            );
+        }
+    }
+}
+
+#[test]
+fn test_double_reverse_reduces() {
+    // The language is a*, whose minimal DFA has a single state. Double reversal leaves at most one
+    // extra state for the new initial state.
+    let d = dfa("a*|(a*)*|aa*");
+    let reduced = DFA::from(DFA::from(d.reverse()).reverse());
+
+    assert!(d.total_states > 2);
+    assert_eq!(2, reduced.total_states);
+}
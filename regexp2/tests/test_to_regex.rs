@@ -0,0 +1,121 @@
+use regexp2::{
+    automata::{DFA, NFA},
+    class::CharClass,
+    parser::{NFAParser, Parser},
+};
+
+fn dfa(expr: &str) -> DFA<CharClass> {
+    let nfa: NFA<CharClass> = NFAParser::new().parse(expr).unwrap();
+    nfa.into()
+}
+
+/// Every string of up to `len` characters from the alphabet.
+fn strings(alphabet: &[char], len: usize) -> Vec<String> {
+    let mut all = vec![String::new()];
+    let mut last = vec![String::new()];
+    for _ in 0..len {
+        last = last
+            .iter()
+            .flat_map(|s| alphabet.iter().map(move |c| format!("{}{}", s, c)))
+            .collect();
+        all.extend(last.iter().cloned());
+    }
+    all
+}
+
+/// Assert that the expression constructed from the DFA of `expr` is accepted again by regexp2 and
+/// matches the same strings.
+fn assert_round_trip(expr: &str) {
+    assert_round_trip_over(expr, &['a', 'b', 'c'], 6);
+}
+
+/// Assert the same as [`assert_round_trip`], for the strings of up to `len` characters from the
+/// alphabet.
+fn assert_round_trip_over(expr: &str, alphabet: &[char], len: usize) {
+    let original = dfa(expr);
+    let regex = original.to_regex().unwrap();
+    let round_trip = dfa(&regex);
+
+    for s in strings(alphabet, len) {
+        assert_eq!(
+            original.is_match(s.chars()),
+            round_trip.is_match(s.chars()),
+            "{:?} on {} and {}",
+            s,
+            expr,
+            regex
+        );
+    }
+}
+
+#[test]
+fn test_to_regex() {
+    let regex = dfa("ab*").to_regex().unwrap();
+    assert_eq!("[a][b]*", regex);
+    assert_round_trip("ab*");
+
+    for expr in &[
+        "",
+        "a|b",
+        "(a|b)*abb",
+        "a*b*c*",
+        "(ab|ba)*c?",
+        "[a-b]+c|c[^a]",
+        "((a|b)(a|b))*",
+    ] {
+        assert_round_trip(expr);
+    }
+}
+
+#[test]
+fn test_to_regex_escapes() {
+    // Classes are written with escapes for special and non-printable chars, which must be read
+    // back as the same chars.
+    let alphabet = [
+        'a', 'b', 'x', '_', ' ', '\t', '\n', '\r', '\0', '\u{b}', '\u{7f}', '\\', '[', ']', '-',
+        '^', 'é', '\u{300}',
+    ];
+    for expr in &[
+        ".",
+        "a.b",
+        r"\s+",
+        r"\w*",
+        r"\\",
+        r"\[",
+        "]",
+        r"[\]\[\\^-]+",
+        "\t|\n|\r",
+        "\u{0}\u{b}*\u{7f}",
+        "[\u{0}-\t]a",
+        "a\u{300}",
+        r"\t\n|\0+",
+        r"[\0-\t]+|\u{b}",
+    ] {
+        assert_round_trip_over(expr, &alphabet, 3);
+    }
+
+    assert_eq!("[a][\\\\][\\[]", dfa(r"a\\\[").to_regex().unwrap());
+}
+
+#[test]
+fn test_to_regex_nfa() {
+    let nfa: NFA<CharClass> = NFAParser::new().parse("(a|b)*c").unwrap();
+    let regex = nfa.to_regex().unwrap();
+    let round_trip = dfa(&regex);
+    for s in strings(&['a', 'b', 'c'], 5) {
+        assert_eq!(
+            nfa.is_match(s.chars()),
+            round_trip.is_match(s.chars()),
+            "{:?}",
+            s
+        );
+    }
+}
+
+#[test]
+fn test_to_regex_empty_language() {
+    // No input is accepted once the only final state is removed.
+    let mut d = dfa("a");
+    d.final_states.clear();
+    assert_eq!(None, d.to_regex());
+}
@@ -19,3 +19,27 @@ fn test_union() {
     let invalids = ["", " ", "*a", r"\*"];
     run_tests!(&exprs, &valids, &invalids);
 }
+
+#[test]
+fn test_empty_branch() {
+    // An empty branch matches the empty string, so these are all equivalent to `a?`.
+    let exprs = ["(a|)", "(|a)", "a|", "|a", "(a||)", "((a)|)"];
+    let valids = ["", "a"];
+    let invalids = [" ", "b", "aa", "ab"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    let exprs = ["(a||b)", "(|a|b)", "(a|b|)"];
+    let valids = ["", "a", "b"];
+    let invalids = [" ", "c", "ab", "ba"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    let exprs = ["x(a|)y", "x(|a)y", "x(a|)(|)y"];
+    let valids = ["xy", "xay"];
+    let invalids = ["", "x", "y", "xaay", "xby"];
+    run_tests!(&exprs, &valids, &invalids);
+
+    let exprs = ["(|)", "()|()"];
+    let valids = [""];
+    let invalids = [" ", "a"];
+    run_tests!(&exprs, &valids, &invalids);
+}
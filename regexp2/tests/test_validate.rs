@@ -0,0 +1,74 @@
+use regexp2::{
+    automata::NFA,
+    class::CharClass,
+    parser::{NFAParser, Parser},
+};
+
+/// Check that validating each expression agrees with building its NFA.
+fn assert_agrees(parser: &NFAParser<CharClass>, exprs: &[&str]) {
+    for expr in exprs {
+        let nfa: Result<NFA<CharClass>, _> = parser.parse(expr);
+        let validated = parser.validate(expr);
+        assert_eq!(
+            nfa.map(|_| ()).map_err(|e| e.to_string()),
+            validated.map_err(|e| e.to_string()),
+            "{}",
+            expr
+        );
+    }
+}
+
+#[test]
+fn test_validate() {
+    let parser = NFAParser::new();
+    assert_agrees(
+        &parser,
+        &[
+            "",
+            "a",
+            "ab",
+            "a|b",
+            "(a|b)*abb",
+            "a+b?",
+            "[^a-z]",
+            r"\d+\w?",
+            "()",
+            "(a|)",
+            "a|",
+            "|",
+            "a{b}",
+            r"\{2}",
+            "[]",
+            "(",
+            ")",
+            "a(",
+            "(()",
+            "*",
+            "*a",
+            "**",
+            "a)*",
+            "(ab",
+            "(a|",
+            "a|)",
+            "a|*",
+            "(*)",
+            "a{2}",
+            r"\Aa",
+            r"a\z",
+            r"a\Ab",
+        ],
+    );
+}
+
+#[test]
+fn test_validate_verbose() {
+    let mut parser = NFAParser::new();
+    parser.verbose = true;
+
+    // Whitespace and comments are ignored only in verbose mode.
+    assert!(parser.validate("a | b # comment").is_ok());
+    assert_agrees(
+        &parser,
+        &["a | b # comment", "  # only a comment", "( a", "* a"],
+    );
+}
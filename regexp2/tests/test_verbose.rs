@@ -0,0 +1,57 @@
+use regexp2::{
+    automata::NFA,
+    class::CharClass,
+    parser::{NFAParser, Parser},
+};
+
+fn parse(parser: &NFAParser<CharClass>, expr: &str) -> NFA<CharClass> {
+    parser.parse(expr).unwrap()
+}
+
+#[test]
+fn test_verbose() {
+    let mut parser = NFAParser::new();
+    parser.verbose = true;
+
+    let verbose = parse(
+        &parser,
+        r"
+        [0-9]+        # integer part
+        (\. [0-9]*)?  # fractional part
+        ([eE] [+\-]? [0-9]+)?
+        ",
+    );
+    let compact = parse(&NFAParser::new(), r"[0-9]+(\.[0-9]*)?([eE][+\-]?[0-9]+)?");
+
+    let valids = ["0", "12", "1.", "1.5", "1e10", "2.5E-3"];
+    let invalids = ["", ".5", "1 .5", "1e", "1.5 # comment"];
+    for s in valids.iter() {
+        assert!(verbose.is_match(s.chars()), "{} did not match", s);
+        assert!(compact.is_match(s.chars()), "{} did not match", s);
+    }
+    for s in invalids.iter() {
+        assert!(!verbose.is_match(s.chars()), "{} matched", s);
+        assert!(!compact.is_match(s.chars()), "{} matched", s);
+    }
+}
+
+#[test]
+fn test_verbose_literals() {
+    let mut parser = NFAParser::new();
+    parser.verbose = true;
+
+    // Escaped whitespace and #, and whitespace and # in char classes, are literal.
+    let nfa = parse(&parser, r"a\ b \# [ #]");
+    assert!(nfa.is_match("a b# ".chars()));
+    assert!(nfa.is_match("a b##".chars()));
+    assert!(!nfa.is_match("ab#".chars()));
+
+    // Only comments and whitespace is the empty expression.
+    let nfa = parse(&parser, "  # nothing\n");
+    assert!(nfa.is_match("".chars()));
+
+    // Without verbose mode, whitespace and # are literal.
+    let nfa = parse(&NFAParser::new(), "a #b");
+    assert!(nfa.is_match("a #b".chars()));
+    assert!(!nfa.is_match("a".chars()));
+}